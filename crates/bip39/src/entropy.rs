@@ -0,0 +1,459 @@
+//! Turning physical randomness (coin flips, dice rolls or cards) into entropy.
+//!
+//! Every roll is turned into bits the same way, so anyone can check by hand
+//! that a mnemonic really came from the rolls they made:
+//!
+//! - the roll is numbered from zero (a d6 roll of `1` is `0`, `6` is `5`)
+//! - rolls that don't fit in a whole number of bits are thrown away
+//!   (rejection sampling), a d6 keeps `1`-`4` and a d20 keeps `1`-`16`
+//! - the kept rolls are written out as bits, most significant bit first,
+//!   one after another, until there is enough entropy
+//!
+//! Throwing rolls away means every kept roll is equally likely, so the
+//! entropy is never biased, even though more rolls are needed.
+//!
+//! Cards are read from a shuffled deck, so a card can't come up twice. Each
+//! card is first turned into its position among the cards still in the deck,
+//! which for a well shuffled deck is an independent roll with one less outcome
+//! than the card before it (52, then 51...), see [EntropySource::Card].
+
+use core::str;
+
+use Error;
+
+/// A source of physical randomness.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EntropySource {
+	/// Coin flips, written as `h`/`t` or `1`/`0`.
+	/// Every flip is kept and gives 1 bit.
+	CoinFlip,
+	/// Six sided dice rolls, written as `1` to `6`.
+	/// Rolls of `5` and `6` are thrown away, the others give 2 bits.
+	D6,
+	/// Twenty sided dice rolls, written as `1` to `20`.
+	/// Rolls of `17` to `20` are thrown away, the others give 4 bits.
+	D20,
+	/// Cards read one by one from the top of a well shuffled 52 card deck.
+	/// Written as the rank (`a`, `2`-`10`, `j`, `q`, `k`) followed by the suit
+	/// (`c`, `d`, `h`, `s`), ex: `10h` or `qs`.
+	///
+	/// A card can't come up twice in the same deck. When more cards are
+	/// needed than a deck holds, shuffle the whole deck again and keep reading,
+	/// every 52 cards start a new deck.
+	///
+	/// Cards are numbered clubs, diamonds, hearts then spades, from ace to king,
+	/// so `ac` is `0` and `ks` is `51`. Each card is turned into its position
+	/// among the cards still in the deck, the first card of a deck has 52
+	/// possible positions, the second 51, and so on. The positions are then
+	/// kept or thrown away like any other roll, so the first cards of a deck
+	/// give 5 bits and the last ones fewer.
+	Card,
+}
+
+impl EntropySource {
+	/// The number of possible outcomes of a single roll, for cards of the
+	/// first card of a deck.
+	pub fn outcomes(self) -> u16 {
+		match self {
+			EntropySource::CoinFlip => 2,
+			EntropySource::D6 => 6,
+			EntropySource::D20 => 20,
+			EntropySource::Card => 52,
+		}
+	}
+
+	/// The number of possible outcomes of the roll at `index`, cards have one
+	/// less outcome than the card before them in the same deck.
+	fn outcomes_at(self, index: usize) -> u16 {
+		match self {
+			EntropySource::Card => 52 - (index % 52) as u16,
+			_ => self.outcomes(),
+		}
+	}
+
+	/// The number of bits a kept roll gives, for cards of the first card of a
+	/// deck.
+	pub fn bits_per_roll(self) -> usize {
+		bits_for(self.outcomes())
+	}
+
+	/// Returns true if the roll (numbered from zero) is kept, rolls that don't
+	/// fit in [EntropySource::bits_per_roll] bits are thrown away. For cards
+	/// the roll is the card's position among the cards still in the deck.
+	pub fn is_kept(self, roll: u16) -> bool {
+		roll < 1 << self.bits_per_roll()
+	}
+
+	/// The number of kept rolls needed to create a mnemonic with the given
+	/// number of words.
+	pub fn rolls_needed(self, word_count: usize) -> Result<usize, Error> {
+		Ok(self.rolls_for_bits(::entropy_bits(word_count)?))
+	}
+
+	/// The number of kept rolls needed for `nb_bits` bits of entropy.
+	fn rolls_for_bits(self, nb_bits: usize) -> usize {
+		let mut bits = 0;
+		let mut rolls = 0;
+		while bits < nb_bits {
+			bits += bits_for(self.outcomes_at(rolls));
+			rolls += 1;
+		}
+
+		rolls
+	}
+
+	/// The number of rolls you can expect to make to create a mnemonic with
+	/// the given number of words, counting the rolls that are thrown away.
+	pub fn expected_rolls(self, word_count: usize) -> Result<usize, Error> {
+		let entropy_bits = ::entropy_bits(word_count)? as u64;
+
+		// the expected bits of every roll are added up in 1/2^16ths of a bit,
+		// rounded up, a roll gives its bits as often as it is kept
+		let mut expected_bits = 0;
+		let mut rolls = 0;
+		while expected_bits < entropy_bits << 16 {
+			let outcomes = self.outcomes_at(rolls) as u64;
+			let bits = bits_for(outcomes as u16) as u64;
+			if bits > 0 {
+				expected_bits += (((bits << bits) << 16) - 1) / outcomes + 1;
+			}
+			rolls += 1;
+		}
+
+		Ok(rolls)
+	}
+
+	/// Parses a single written roll, returning it numbered from zero.
+	pub fn parse_roll(self, roll: &str) -> Option<u16> {
+		match self {
+			EntropySource::CoinFlip => match roll {
+				"0" | "t" | "T" => Some(0),
+				"1" | "h" | "H" => Some(1),
+				_ => None,
+			},
+			EntropySource::D6 | EntropySource::D20 => match roll.parse::<u16>() {
+				Ok(roll) if roll >= 1 && roll <= self.outcomes() => Some(roll - 1),
+				_ => None,
+			},
+			EntropySource::Card => {
+				if roll.len() < 2 || !roll.is_char_boundary(roll.len() - 1) {
+					return None;
+				}
+
+				let (rank, suit) = roll.split_at(roll.len() - 1);
+				let rank = match rank {
+					"a" | "A" => 0,
+					"j" | "J" => 10,
+					"q" | "Q" => 11,
+					"k" | "K" => 12,
+					number => match number.parse::<u16>() {
+						Ok(number @ 2..=10) => number - 1,
+						_ => return None,
+					},
+				};
+				let suit = match suit {
+					"c" | "C" => 0,
+					"d" | "D" => 1,
+					"h" | "H" => 2,
+					"s" | "S" => 3,
+					_ => return None,
+				};
+
+				Some(suit * 13 + rank)
+			}
+		}
+	}
+
+	/// Parses written rolls separated by whitespace or commas, returning them
+	/// numbered from zero. Coin flips and d6 rolls can also be written without
+	/// separators, ex: `hhtht` or `316624`.
+	///
+	/// The error contains the index of the first roll that couldn't be parsed.
+	#[cfg(feature = "std")]
+	pub fn parse_rolls(self, rolls: &str) -> Result<Vec<u16>, Error> {
+		let single_char = match self {
+			EntropySource::CoinFlip | EntropySource::D6 => true,
+			EntropySource::D20 | EntropySource::Card => false,
+		};
+
+		let mut parsed = Vec::new();
+		for token in rolls
+			.split(|c: char| c.is_whitespace() || c == ',')
+			.filter(|t| !t.is_empty())
+		{
+			if single_char {
+				for c in token.chars() {
+					let mut buf = [0; 4];
+					let roll = self
+						.parse_roll(c.encode_utf8(&mut buf))
+						.ok_or(Error::InvalidRoll(parsed.len()))?;
+					parsed.push(roll);
+				}
+			} else {
+				parsed.push(
+					self.parse_roll(token)
+						.ok_or(Error::InvalidRoll(parsed.len()))?,
+				);
+			}
+		}
+
+		Ok(parsed)
+	}
+}
+
+impl str::FromStr for EntropySource {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<EntropySource, Error> {
+		match s {
+			"coin" | "coins" | "coin-flips" => Ok(EntropySource::CoinFlip),
+			"d6" | "dice" => Ok(EntropySource::D6),
+			"d20" => Ok(EntropySource::D20),
+			"card" | "cards" => Ok(EntropySource::Card),
+			_ => Err(Error::UnknownEntropySource),
+		}
+	}
+}
+
+/// The number of bits a kept roll with `outcomes` possible outcomes gives, the
+/// largest power of two that fits in the number of outcomes.
+fn bits_for(outcomes: u16) -> usize {
+	15 - outcomes.leading_zeros() as usize
+}
+
+/// Fills `entropy` with the bits of the kept rolls (numbered from zero),
+/// rolls that are left over once `entropy` is full are ignored.
+///
+/// Returns an error if there weren't enough kept rolls to fill `entropy`, or
+/// if a card comes up twice in the same deck.
+pub(crate) fn fill_from_rolls<I>(
+	source: EntropySource,
+	rolls: I,
+	entropy: &mut [u8],
+) -> Result<(), Error>
+where
+	I: IntoIterator<Item = u16>,
+{
+	let nb_bits = entropy.len() * 8;
+	let mut cursor = 0;
+	let mut kept = 0;
+
+	// the cards already drawn from the current deck, one bit per card
+	let mut deck: u64 = 0;
+
+	for (i, roll) in rolls.into_iter().enumerate() {
+		if roll >= source.outcomes() {
+			return Err(Error::InvalidRoll(i));
+		}
+
+		let roll = match source {
+			EntropySource::Card => {
+				// every card of the deck was drawn, it is shuffled again
+				if deck == (1 << 52) - 1 {
+					deck = 0;
+				}
+				if deck & 1 << roll != 0 {
+					return Err(Error::RepeatedCard(i));
+				}
+
+				// the position of the card among the cards still in the deck
+				let position = roll - (deck & ((1 << roll) - 1)).count_ones() as u16;
+				deck |= 1 << roll;
+				position
+			}
+			_ => roll,
+		};
+
+		let bits_per_roll = bits_for(source.outcomes_at(i));
+		if bits_per_roll == 0 || roll >= 1 << bits_per_roll {
+			continue;
+		}
+		kept += 1;
+
+		for j in 0..bits_per_roll {
+			if cursor == nb_bits {
+				break;
+			}
+			if (roll >> (bits_per_roll - 1 - j)) & 1 == 1 {
+				entropy[cursor / 8] |= 1 << (7 - (cursor % 8));
+			}
+			cursor += 1;
+		}
+
+		if cursor == nb_bits {
+			return Ok(());
+		}
+	}
+
+	Err(Error::NotEnoughRolls {
+		needed: source.rolls_for_bits(nb_bits),
+		kept,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use Mnemonic;
+
+	#[test]
+	fn test_bits_per_roll() {
+		assert_eq!(EntropySource::CoinFlip.bits_per_roll(), 1);
+		assert_eq!(EntropySource::D6.bits_per_roll(), 2);
+		assert_eq!(EntropySource::D20.bits_per_roll(), 4);
+		assert_eq!(EntropySource::Card.bits_per_roll(), 5);
+	}
+
+	#[test]
+	fn test_rolls_needed() {
+		let cases = [
+			(EntropySource::CoinFlip, [128, 192, 256], [128, 192, 256]),
+			(EntropySource::D6, [64, 96, 128], [96, 144, 192]),
+			(EntropySource::D20, [32, 48, 64], [40, 60, 80]),
+			(EntropySource::Card, [27, 45, 63], [38, 64, 83]),
+		];
+
+		for (source, needed, expected) in cases.iter() {
+			for (i, word_count) in [12, 18, 24].iter().enumerate() {
+				assert_eq!(source.rolls_needed(*word_count).unwrap(), needed[i]);
+				assert_eq!(source.expected_rolls(*word_count).unwrap(), expected[i]);
+			}
+		}
+
+		assert_eq!(
+			EntropySource::D6.rolls_needed(13),
+			Err(Error::BadWordCount(13))
+		);
+	}
+
+	#[test]
+	fn test_parse_roll() {
+		assert_eq!(EntropySource::CoinFlip.parse_roll("h"), Some(1));
+		assert_eq!(EntropySource::CoinFlip.parse_roll("0"), Some(0));
+		assert_eq!(EntropySource::CoinFlip.parse_roll("x"), None);
+		assert_eq!(EntropySource::D6.parse_roll("1"), Some(0));
+		assert_eq!(EntropySource::D6.parse_roll("6"), Some(5));
+		assert_eq!(EntropySource::D6.parse_roll("7"), None);
+		assert_eq!(EntropySource::D6.parse_roll("0"), None);
+		assert_eq!(EntropySource::D20.parse_roll("20"), Some(19));
+		assert_eq!(EntropySource::D20.parse_roll("21"), None);
+		assert_eq!(EntropySource::Card.parse_roll("ac"), Some(0));
+		assert_eq!(EntropySource::Card.parse_roll("10h"), Some(35));
+		assert_eq!(EntropySource::Card.parse_roll("KS"), Some(51));
+		assert_eq!(EntropySource::Card.parse_roll("1s"), None);
+		assert_eq!(EntropySource::Card.parse_roll("kx"), None);
+		assert_eq!(EntropySource::Card.parse_roll("é"), None);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn test_parse_rolls() {
+		assert_eq!(
+			EntropySource::D6.parse_rolls("3162 4, 5").unwrap(),
+			vec![2, 0, 5, 1, 3, 4]
+		);
+		assert_eq!(
+			EntropySource::CoinFlip.parse_rolls("hht t").unwrap(),
+			vec![1, 1, 0, 0]
+		);
+		assert_eq!(
+			EntropySource::D20.parse_rolls("20,1 17").unwrap(),
+			vec![19, 0, 16]
+		);
+		assert_eq!(
+			EntropySource::Card.parse_rolls("as 10d").unwrap(),
+			vec![39, 22]
+		);
+		assert_eq!(
+			EntropySource::D6.parse_rolls("1 2 7"),
+			Err(Error::InvalidRoll(2))
+		);
+		assert_eq!(
+			EntropySource::D20.parse_rolls("1 2 21"),
+			Err(Error::InvalidRoll(2))
+		);
+	}
+
+	#[test]
+	fn test_rejection_sampling() {
+		let mut entropy = [0u8; 1];
+		// 5 and 6 are thrown away, 4 3 2 1 are 11 10 01 00
+		fill_from_rolls(
+			EntropySource::D6,
+			[5, 3, 4, 2, 5, 1, 0].iter().cloned(),
+			&mut entropy,
+		)
+		.unwrap();
+		assert_eq!(entropy, [0b1110_0100]);
+
+		// 2c is position 1 of 52, ac position 0 of 51, 3c position 0 of 50
+		let mut entropy = [0u8; 1];
+		fill_from_rolls(EntropySource::Card, [1, 0, 2].iter().cloned(), &mut entropy).unwrap();
+		assert_eq!(entropy, [0b0000_1000]);
+
+		// ks is position 51 of 52 and thrown away
+		let mut entropy = [0u8; 1];
+		fill_from_rolls(
+			EntropySource::Card,
+			[51, 1, 0].iter().cloned(),
+			&mut entropy,
+		)
+		.unwrap();
+		assert_eq!(entropy, [0b0000_1000]);
+
+		let mut entropy = [0u8; 1];
+		assert_eq!(
+			fill_from_rolls(
+				EntropySource::D20,
+				[15, 16, 17].iter().cloned(),
+				&mut entropy
+			),
+			Err(Error::NotEnoughRolls { needed: 2, kept: 1 })
+		);
+	}
+
+	#[test]
+	fn test_from_rolls() {
+		// 128 tails is all zero entropy
+		let m =
+			Mnemonic::from_rolls(EntropySource::CoinFlip, 12, [0u16; 128].iter().cloned()).unwrap();
+		assert_eq!(
+			m.to_string(),
+			"abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+		);
+
+		// 64 rolls of 4 on a d6 is all one entropy, rolls of 5 and 6 are thrown away
+		let rolls = [3u16, 4, 5].iter().cloned().cycle().take(64 * 3);
+		let m = Mnemonic::from_rolls(EntropySource::D6, 12, rolls).unwrap();
+		assert_eq!(
+			m.to_string(),
+			"zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong"
+		);
+
+		// two decks in order, every card is the first card left in the deck
+		let m = Mnemonic::from_rolls(EntropySource::Card, 24, (0..52).chain(0..52)).unwrap();
+		assert_eq!(&m.to_entropy_array().0[..32], &[0u8; 32][..]);
+
+		// a card can only come up twice in different decks
+		assert_eq!(
+			Mnemonic::from_rolls(EntropySource::Card, 12, [5u16, 7, 5].iter().cloned()),
+			Err(Error::RepeatedCard(2))
+		);
+		assert_eq!(
+			Mnemonic::from_rolls(EntropySource::Card, 24, (0..52).chain(Some(0))),
+			Err(Error::NotEnoughRolls {
+				needed: 63,
+				kept: 52,
+			})
+		);
+
+		assert_eq!(
+			Mnemonic::from_rolls(EntropySource::D6, 12, [0u16; 63].iter().cloned()),
+			Err(Error::NotEnoughRolls {
+				needed: 64,
+				kept: 63,
+			})
+		);
+	}
+}
@@ -60,10 +60,14 @@ use zeroize::Zeroize;
 
 #[macro_use]
 mod internal_macros;
+pub mod entropy;
 mod language;
 mod pbkdf2;
+pub mod recovery;
 
+pub use entropy::EntropySource;
 pub use language::Language;
+pub use recovery::Completions;
 
 /// The minimum number of words in a mnemonic.
 #[allow(unused)]
@@ -73,7 +77,16 @@ const MIN_NB_WORDS: usize = 12;
 const MAX_NB_WORDS: usize = 24;
 
 /// The index used to indicate the mnemonic ended.
-const EOF: u16 = u16::max_value();
+const EOF: u16 = core::u16::MAX;
+
+/// The number of bits of entropy in a mnemonic with the given number of words.
+fn entropy_bits(word_count: usize) -> Result<usize, Error> {
+    if word_count < MIN_NB_WORDS || word_count % 6 != 0 || word_count > MAX_NB_WORDS {
+        return Err(Error::BadWordCount(word_count));
+    }
+
+    Ok(word_count / 3 * 32)
+}
 
 /// A structured used in the [Error::AmbiguousLanguages] variant that iterates
 /// over the possible languages.
@@ -120,6 +133,33 @@ pub enum Error {
     /// Use the helper methods of the inner struct to inspect
     /// which languages are possible.
     AmbiguousLanguages(AmbiguousLanguages),
+    /// A roll could not be parsed or is out of range for its [EntropySource].
+    /// Error contains the index of the roll.
+    InvalidRoll(usize),
+    /// There were not enough kept rolls to create the mnemonic.
+    NotEnoughRolls {
+        /// The number of kept rolls needed.
+        needed: usize,
+        /// The number of rolls that were kept.
+        kept: usize,
+    },
+    /// A card came up twice in the same deck, see [EntropySource::Card].
+    /// Error contains the index of the roll.
+    RepeatedCard(usize),
+    /// The name of the [EntropySource] is not known.
+    UnknownEntropySource,
+    /// More words are missing than can be recovered.
+    /// Error contains the number of missing words.
+    TooManyMissingWords(usize),
+    /// SeedQR only encodes 12 and 24 word mnemonics.
+    /// Error contains the number of words.
+    BadSeedQrWordCount(usize),
+    /// A SeedQR was not 48 or 96 digits, or a CompactSeedQR not 16 or 32 bytes.
+    /// Error contains the number of digits or bytes.
+    BadSeedQrLength(usize),
+    /// A SeedQR contains something other than digits.
+    /// Error contains the index of the character.
+    InvalidSeedQrDigit(usize),
 }
 
 impl fmt::Display for Error {
@@ -150,6 +190,44 @@ impl fmt::Display for Error {
                 }
                 Ok(())
             }
+            Error::InvalidRoll(i) => write!(f, "invalid roll (roll {})", i),
+            Error::NotEnoughRolls { needed, kept } => write!(
+                f,
+                "not enough rolls, {} rolls are needed but only {} were kept",
+                needed, kept,
+            ),
+            Error::RepeatedCard(i) => write!(
+                f,
+                "card came up twice in the same deck, shuffle the whole deck again after every 52 cards (roll {})",
+                i
+            ),
+            Error::UnknownEntropySource => write!(
+                f,
+                "unknown entropy source, expected one of: coin, d6, d20, cards"
+            ),
+            Error::TooManyMissingWords(c) => write!(
+                f,
+                "too many missing words, at most {} can be recovered: {}",
+                recovery::MAX_NB_MISSING_WORDS,
+                c,
+            ),
+            Error::BadSeedQrWordCount(c) => write!(
+                f,
+                "only 12 and 24 word mnemonics can be encoded as a SeedQR: {} words",
+                c,
+            ),
+            Error::BadSeedQrLength(c) => write!(
+                f,
+                "a SeedQR must be 48 or 96 digits, a CompactSeedQR 16 or 32 bytes: {}",
+                c,
+            ),
+            Error::InvalidSeedQrDigit(i) => {
+                write!(
+                    f,
+                    "SeedQR contains a character that is not a digit (character {})",
+                    i
+                )
+            }
         }
     }
 }
@@ -205,7 +283,7 @@ impl Mnemonic {
         if nb_bits % 32 != 0 {
             return Err(Error::BadEntropyBitCount(nb_bits));
         }
-        if !(MIN_ENTROPY_BITS..=MAX_ENTROPY_BITS).contains(&nb_bits) {
+        if nb_bits < MIN_ENTROPY_BITS || nb_bits > MAX_ENTROPY_BITS {
             return Err(Error::BadEntropyBitCount(nb_bits));
         }
 
@@ -267,16 +345,55 @@ impl Mnemonic {
     where
         R: rand_core::RngCore + rand_core::CryptoRng,
     {
-        if word_count < MIN_NB_WORDS || word_count % 6 != 0 || word_count > MAX_NB_WORDS {
-            return Err(Error::BadWordCount(word_count));
-        }
-
-        let entropy_bytes = (word_count / 3) * 4;
+        let entropy_bytes = entropy_bits(word_count)? / 8;
         let mut entropy = [0u8; (MAX_NB_WORDS / 3) * 4];
         rand_core::RngCore::fill_bytes(rng, &mut entropy[0..entropy_bytes]);
         Mnemonic::from_entropy_in(language, &entropy[0..entropy_bytes])
     }
 
+    /// Create a new [Mnemonic] in the given language from physical randomness.
+    /// The rolls are numbered from zero, see the [entropy] module for how they
+    /// are turned into entropy and [EntropySource::rolls_needed] for how many
+    /// are needed. Rolls left over once there is enough entropy are ignored.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate bip39;
+    ///
+    /// use bip39::{EntropySource, Language, Mnemonic};
+    ///
+    /// let rolls = [0, 1, 2, 3].iter().cycle().take(64).cloned();
+    /// let m = Mnemonic::from_rolls_in(Language::English, EntropySource::D6, 12, rolls).unwrap();
+    /// ```
+    pub fn from_rolls_in<I>(
+        language: Language,
+        source: EntropySource,
+        word_count: usize,
+        rolls: I,
+    ) -> Result<Mnemonic, Error>
+    where
+        I: IntoIterator<Item = u16>,
+    {
+        let entropy_bytes = entropy_bits(word_count)? / 8;
+        let mut entropy = [0u8; (MAX_NB_WORDS / 3) * 4];
+        entropy::fill_from_rolls(source, rolls, &mut entropy[0..entropy_bytes])?;
+        Mnemonic::from_entropy_in(language, &entropy[0..entropy_bytes])
+    }
+
+    /// Create a new English [Mnemonic] from physical randomness.
+    /// See [Mnemonic::from_rolls_in] for more info.
+    pub fn from_rolls<I>(
+        source: EntropySource,
+        word_count: usize,
+        rolls: I,
+    ) -> Result<Mnemonic, Error>
+    where
+        I: IntoIterator<Item = u16>,
+    {
+        Mnemonic::from_rolls_in(Language::English, source, word_count, rolls)
+    }
+
     /// Generate a new [Mnemonic] in the given language.
     /// For the different supported word counts, see documentation on [Mnemonic].
     ///
@@ -446,6 +563,93 @@ impl Mnemonic {
         })
     }
 
+    /// Find every word that completes the mnemonic with a valid checksum, when
+    /// given all but the last word of the mnemonic in normalized UTF8.
+    ///
+    /// Only some of the bits of the last word are entropy, the rest are the
+    /// checksum, so there are 8 valid last words for a 24 word mnemonic and
+    /// 128 for a 12 word mnemonic. They are returned in word list order.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate bip39;
+    ///
+    /// use bip39::{Language, Mnemonic};
+    ///
+    /// let words = ["abandon"; 11];
+    /// let last_words = Mnemonic::valid_last_words(Language::English, &words).unwrap();
+    /// assert_eq!(last_words.len(), 128);
+    /// assert_eq!(last_words[0], "about");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn valid_last_words(language: Language, words: &[&str]) -> Result<Vec<&'static str>, Error> {
+        let nb_words = words.len() + 1;
+        let nb_bytes_entropy = entropy_bits(nb_words)? / 8;
+        let nb_checksum_bits = nb_words / 3;
+        let nb_free_bits = 11 - nb_checksum_bits;
+
+        // The bits of the given words, followed by the entropy bits of the last word.
+        let mut bits = [false; MAX_NB_WORDS * 11];
+        for (i, word) in words.iter().enumerate() {
+            let idx = language.find_word(word).ok_or(Error::UnknownWord(i))?;
+
+            for j in 0..11 {
+                bits[i * 11 + j] = idx >> (10 - j) & 1 == 1;
+            }
+        }
+
+        let list = language.word_list();
+        let mut last_words = Vec::with_capacity(1 << nb_free_bits);
+        for free in 0..(1u16 << nb_free_bits) {
+            for j in 0..nb_free_bits {
+                bits[words.len() * 11 + j] = free >> (nb_free_bits - 1 - j) & 1 == 1;
+            }
+
+            let mut entropy = [0u8; MAX_NB_WORDS / 3 * 4];
+            for i in 0..nb_bytes_entropy {
+                for j in 0..8 {
+                    if bits[i * 8 + j] {
+                        entropy[i] += 1 << (7 - j);
+                    }
+                }
+            }
+
+            let check = sha256::Hash::hash(&entropy[0..nb_bytes_entropy]);
+            let checksum = (check[0] >> (8 - nb_checksum_bits)) as u16;
+            last_words.push(list[((free << nb_checksum_bits) | checksum) as usize]);
+        }
+
+        Ok(last_words)
+    }
+
+    /// Find every mnemonic with a valid checksum that can be made by filling in
+    /// the missing (`None`) words, the known words must be normalized UTF8.
+    /// See the [recovery] module for more info.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate bip39;
+    ///
+    /// use bip39::{Language, Mnemonic};
+    ///
+    /// let mut words = vec![Some("abandon"); 11];
+    /// words.push(None);
+    /// let completions = Mnemonic::completions_in(Language::English, &words).unwrap();
+    /// assert_eq!(completions.count(), 128);
+    /// ```
+    pub fn completions_in(language: Language, words: &[Option<&str>]) -> Result<Completions, Error> {
+        Completions::new(language, words)
+    }
+
+    /// Find every English mnemonic with a valid checksum that can be made by
+    /// filling in the missing (`None`) words.
+    /// See [Mnemonic::completions_in] for more info.
+    pub fn completions(words: &[Option<&str>]) -> Result<Completions, Error> {
+        Mnemonic::completions_in(Language::English, words)
+    }
+
     /// Parse a mnemonic in normalized UTF8.
     pub fn parse_normalized(s: &str) -> Result<Mnemonic, Error> {
         let lang = Mnemonic::language_of(s)?;
@@ -553,6 +757,102 @@ impl Mnemonic {
         let (arr, len) = self.to_entropy_array();
         arr[0..len].to_vec()
     }
+
+    /// Encode the mnemonic as a standard SeedQR, the index of each word as
+    /// four digits, zero padded. Only 12 and 24 word mnemonics can be encoded.
+    ///
+    /// The digits are meant to be put in a numeric mode QR code, version 2 (25x25)
+    /// for 12 words and version 3 (29x29) for 24 words, with low error correction.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate bip39;
+    ///
+    /// use bip39::Mnemonic;
+    ///
+    /// let m = Mnemonic::parse("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about").unwrap();
+    /// assert_eq!(m.to_seed_qr().unwrap(), format!("{}0003", "0000".repeat(11)));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_seed_qr(&self) -> Result<String, Error> {
+        let nb_words = self.word_count();
+        if nb_words != 12 && nb_words != 24 {
+            return Err(Error::BadSeedQrWordCount(nb_words));
+        }
+
+        Ok(self.words[0..nb_words]
+            .iter()
+            .map(|w| format!("{:04}", w))
+            .collect())
+    }
+
+    /// Encode the mnemonic as a CompactSeedQR, the entropy of the mnemonic
+    /// without the checksum. Only 12 and 24 word mnemonics can be encoded.
+    ///
+    /// The bytes are meant to be put in a byte mode QR code, version 1 (21x21)
+    /// for 12 words and version 2 (25x25) for 24 words, with low error correction.
+    #[cfg(feature = "std")]
+    pub fn to_compact_seed_qr(&self) -> Result<Vec<u8>, Error> {
+        let nb_words = self.word_count();
+        if nb_words != 12 && nb_words != 24 {
+            return Err(Error::BadSeedQrWordCount(nb_words));
+        }
+
+        Ok(self.to_entropy())
+    }
+
+    /// Decode a standard SeedQR into an English [Mnemonic].
+    /// See [Mnemonic::to_seed_qr] for the format.
+    pub fn from_seed_qr(digits: &str) -> Result<Mnemonic, Error> {
+        if digits.len() != 48 && digits.len() != 96 {
+            return Err(Error::BadSeedQrLength(digits.len()));
+        }
+
+        if let Some(i) = digits.bytes().position(|b| !b.is_ascii_digit()) {
+            return Err(Error::InvalidSeedQrDigit(i));
+        }
+
+        let nb_words = digits.len() / 4;
+        let mut words = [EOF; MAX_NB_WORDS];
+        let mut bytes = [0u8; 33];
+        for (i, word) in words.iter_mut().take(nb_words).enumerate() {
+            let idx = digits[i * 4..i * 4 + 4]
+                .parse::<u16>()
+                .expect("four digits");
+            if idx >= 2048 {
+                return Err(Error::UnknownWord(i));
+            }
+
+            for bit in 0..11 {
+                if idx & (1 << (10 - bit)) != 0 {
+                    let position = i * 11 + bit;
+                    bytes[position / 8] |= 1 << (7 - position % 8);
+                }
+            }
+            *word = idx;
+        }
+
+        // The checksum bits are dropped with the entropy, so the last word
+        // only matches if the checksum was valid.
+        let entropy_bytes = (nb_words / 3) * 4;
+        let mnemonic = Mnemonic::from_entropy_in(Language::English, &bytes[0..entropy_bytes])?;
+        if mnemonic.words != words {
+            return Err(Error::InvalidChecksum);
+        }
+
+        Ok(mnemonic)
+    }
+
+    /// Decode a CompactSeedQR into an English [Mnemonic].
+    /// See [Mnemonic::to_compact_seed_qr] for the format.
+    pub fn from_compact_seed_qr(bytes: &[u8]) -> Result<Mnemonic, Error> {
+        if bytes.len() != 16 && bytes.len() != 32 {
+            return Err(Error::BadSeedQrLength(bytes.len()));
+        }
+
+        Mnemonic::from_entropy_in(Language::English, bytes)
+    }
 }
 
 impl fmt::Display for Mnemonic {
@@ -605,6 +905,127 @@ mod tests {
 
     #[cfg(feature = "std")]
     #[test]
+    fn test_seed_qr() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+        let m = Mnemonic::parse(mnemonic).unwrap();
+
+        // abandon is word 0 and art is word 102
+        let digits = m.to_seed_qr().unwrap();
+        assert_eq!(digits, format!("{}0102", "0000".repeat(23)));
+        assert_eq!(Mnemonic::from_seed_qr(&digits).unwrap(), m);
+
+        let bytes = m.to_compact_seed_qr().unwrap();
+        assert_eq!(bytes, vec![0; 32]);
+        assert_eq!(Mnemonic::from_compact_seed_qr(&bytes).unwrap(), m);
+
+        for entropy in [[0x7f; 16], [0x80; 16], [0xff; 16]].iter() {
+            let m = Mnemonic::from_entropy(entropy).unwrap();
+            assert_eq!(Mnemonic::from_seed_qr(&m.to_seed_qr().unwrap()).unwrap(), m);
+            assert_eq!(
+                Mnemonic::from_compact_seed_qr(&m.to_compact_seed_qr().unwrap()).unwrap(),
+                m
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_seed_qr_vectors() {
+        // the SeedQR and CompactSeedQR examples from SeedSigner's specification, docs/seed_qr/README.md
+        let vectors = [
+            (
+                "attack pizza motion avocado network gather crop fresh patrol unusual wild holiday candy pony ranch winter theme error hybrid van cereal salon goddess expire",
+                "011513251154012711900771041507421289190620080870026613431420201617920614089619290300152408010643",
+                "0e74b64107f94cc0ccfae6a13dcbec3662154fec67e0e00999c07892597d190a",
+            ),
+            (
+                "forum undo fragile fade shy sign arrest garment culture tube off merit",
+                "073318950739065415961602009907670428187212261116",
+                "5bbd9d71a8ec7990831aff359d426545",
+            ),
+        ];
+
+        for vector in &vectors {
+            let m = Mnemonic::parse(vector.0).unwrap();
+            let bytes = Vec::<u8>::from_hex(vector.2).unwrap();
+
+            assert_eq!(m.to_seed_qr().unwrap(), vector.1);
+            assert_eq!(Mnemonic::from_seed_qr(vector.1).unwrap(), m);
+
+            assert_eq!(m.to_compact_seed_qr().unwrap(), bytes);
+            assert_eq!(Mnemonic::from_compact_seed_qr(&bytes).unwrap(), m);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_invalid_seed_qr() {
+        // abandon x 23, abandon has the wrong checksum
+        assert_eq!(
+            Mnemonic::from_seed_qr(&"0000".repeat(24)),
+            Err(Error::InvalidChecksum)
+        );
+        assert_eq!(
+            Mnemonic::from_seed_qr(&format!("{}2048", "0000".repeat(23))),
+            Err(Error::UnknownWord(23))
+        );
+        assert_eq!(
+            Mnemonic::from_seed_qr(&format!("{}01a2", "0000".repeat(23))),
+            Err(Error::InvalidSeedQrDigit(94))
+        );
+        assert_eq!(
+            Mnemonic::from_seed_qr(&"0000".repeat(18)),
+            Err(Error::BadSeedQrLength(72))
+        );
+        assert_eq!(
+            Mnemonic::from_compact_seed_qr(&[0; 24]),
+            Err(Error::BadSeedQrLength(24))
+        );
+
+        let m = Mnemonic::from_entropy(&[0; 24]).unwrap();
+        assert_eq!(m.to_seed_qr(), Err(Error::BadSeedQrWordCount(18)));
+        assert_eq!(m.to_compact_seed_qr(), Err(Error::BadSeedQrWordCount(18)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_valid_last_words() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+        let words = mnemonic.split_whitespace().collect::<Vec<_>>();
+
+        let last_words = Mnemonic::valid_last_words(Language::English, &words[..23]).unwrap();
+        assert_eq!(last_words.len(), 8);
+        assert!(last_words.contains(&"art"));
+
+        for last_word in last_words {
+            let mut words = words[..23].to_vec();
+            words.push(last_word);
+            assert!(Mnemonic::parse_in(Language::English, words.join(" ")).is_ok());
+        }
+
+        let last_words = Mnemonic::valid_last_words(Language::English, &words[..17]).unwrap();
+        assert_eq!(last_words.len(), 32);
+        let last_words = Mnemonic::valid_last_words(Language::English, &words[..11]).unwrap();
+        assert_eq!(last_words.len(), 128);
+        assert_eq!(
+            Mnemonic::valid_last_words(Language::English, &words[..10]),
+            Err(Error::BadWordCount(11))
+        );
+        assert_eq!(
+            Mnemonic::valid_last_words(Language::English, &["abandon", "zzz"]),
+            Err(Error::BadWordCount(3))
+        );
+        let mut words = words[..11].to_vec();
+        words[3] = "zzz";
+        assert_eq!(
+            Mnemonic::valid_last_words(Language::English, &words),
+            Err(Error::UnknownWord(3))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[allow(clippy::needless_range_loop)]
     fn test_ambiguous_languages() {
         let mut present = [false; language::MAX_NB_LANGUAGES];
         let mut present_vec = Vec::new();
@@ -757,9 +1178,9 @@ mod tests {
 		];
 
         for vector in &test_vectors {
-            let entropy = Vec::<u8>::from_hex(&vector.0).unwrap();
+            let entropy = Vec::<u8>::from_hex(vector.0).unwrap();
             let mnemonic_str = vector.1;
-            let seed = Vec::<u8>::from_hex(&vector.2).unwrap();
+            let seed = Vec::<u8>::from_hex(vector.2).unwrap();
 
             let mnemonic = Mnemonic::from_entropy(&entropy).unwrap();
 
@@ -782,7 +1203,7 @@ mod tests {
                 mnemonic_str
             );
 
-            #[cfg(features = "std")]
+            #[cfg(feature = "std")]
             {
                 assert_eq!(
                     &mnemonic.to_string(),
@@ -1037,7 +1458,7 @@ mod tests {
 		];
 
         for vector in &vectors {
-            let entropy = Vec::<u8>::from_hex(&vector.0).unwrap();
+            let entropy = Vec::<u8>::from_hex(vector.0).unwrap();
             let mnemonic_str = vector.1;
             let passphrase = vector.2;
             let seed = Vec::<u8>::from_hex(&vector.3).unwrap();
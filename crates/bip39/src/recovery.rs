@@ -0,0 +1,256 @@
+//! Recovering a mnemonic with some of its words missing.
+//!
+//! Every missing word could be any word in the word list, except for the last
+//! word, which is partly made of the checksum. So for each missing word there
+//! are 2048 possibilities, and for a missing last word only the bits that
+//! aren't the checksum need to be tried (8 possibilities for a 24 word
+//! mnemonic, 128 for a 12 word mnemonic).
+//!
+//! Of the possible mnemonics, only the ones with a valid checksum are returned.
+//! When the last word isn't missing that's roughly 1 in 256 for a 24 word
+//! mnemonic, and 1 in 16 for a 12 word mnemonic.
+
+use core::ops::Range;
+
+use bitcoin_hashes::{sha256, Hash};
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+use {Error, Language, Mnemonic, EOF, MAX_NB_WORDS};
+
+/// The maximum number of missing words, any more than this would take far too
+/// long to try, three missing words are already 2^33 candidates.
+pub const MAX_NB_MISSING_WORDS: usize = 2;
+
+/// An iterator over the mnemonics with a valid checksum that can be made by
+/// filling in the missing words, see [Mnemonic::completions_in].
+///
+/// The possible mnemonics are numbered from `0` to [Completions::nb_candidates],
+/// so the work can be shared between threads with [Completions::with_range].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "zeroize", derive(Zeroize), zeroize(drop))]
+pub struct Completions {
+	lang: Language,
+	/// The indices of the known words, missing words are filled in as we go.
+	words: [u16; MAX_NB_WORDS],
+	nb_words: usize,
+	/// The positions of the missing words.
+	missing: [usize; MAX_NB_MISSING_WORDS],
+	nb_missing: usize,
+	/// The next candidate to try.
+	next: u64,
+	/// The candidate to stop at.
+	end: u64,
+}
+
+impl Completions {
+	pub(crate) fn new(language: Language, words: &[Option<&str>]) -> Result<Completions, Error> {
+		let nb_words = words.len();
+		::entropy_bits(nb_words)?;
+
+		let mut completions = Completions {
+			lang: language,
+			words: [EOF; MAX_NB_WORDS],
+			nb_words,
+			missing: [0; MAX_NB_MISSING_WORDS],
+			nb_missing: 0,
+			next: 0,
+			end: 0,
+		};
+
+		for (i, word) in words.iter().enumerate() {
+			match word {
+				Some(word) => {
+					completions.words[i] = language.find_word(word).ok_or(Error::UnknownWord(i))?
+				}
+				None if completions.nb_missing == MAX_NB_MISSING_WORDS => {
+					let nb_missing = words.iter().filter(|w| w.is_none()).count();
+					return Err(Error::TooManyMissingWords(nb_missing));
+				}
+				None => {
+					completions.missing[completions.nb_missing] = i;
+					completions.nb_missing += 1;
+				}
+			}
+		}
+
+		completions.end = 1 << completions.nb_candidate_bits();
+		Ok(completions)
+	}
+
+	/// The number of bits of the last word that aren't the checksum.
+	fn nb_free_bits(&self) -> usize {
+		11 - self.nb_words / 3
+	}
+
+	fn last_word_missing(&self) -> bool {
+		self.missing[..self.nb_missing].contains(&(self.nb_words - 1))
+	}
+
+	fn nb_candidate_bits(&self) -> usize {
+		if self.last_word_missing() {
+			(self.nb_missing - 1) * 11 + self.nb_free_bits()
+		} else {
+			self.nb_missing * 11
+		}
+	}
+
+	/// The number of possible mnemonics that will be tried, most of them won't
+	/// have a valid checksum unless the last word is missing.
+	pub fn nb_candidates(&self) -> u64 {
+		1 << self.nb_candidate_bits()
+	}
+
+	/// Only try the possible mnemonics numbered in the given range.
+	pub fn with_range(mut self, range: Range<u64>) -> Completions {
+		self.end = range.end.min(self.nb_candidates());
+		self.next = range.start.min(self.end);
+		self
+	}
+
+	/// Fills in the missing words for the given candidate, returning the
+	/// mnemonic if its checksum is valid.
+	fn try_candidate(&mut self, mut candidate: u64) -> Option<Mnemonic> {
+		let last = self.nb_words - 1;
+		let nb_checksum_bits = self.nb_words / 3;
+
+		for i in 0..self.nb_missing {
+			let position = self.missing[i];
+			if position == last {
+				let free = candidate & ((1 << self.nb_free_bits()) - 1);
+				self.words[position] = (free as u16) << nb_checksum_bits;
+				candidate >>= self.nb_free_bits();
+			} else {
+				self.words[position] = (candidate & 0x7ff) as u16;
+				candidate >>= 11;
+			}
+		}
+
+		let nb_bytes_entropy = self.nb_words / 3 * 4;
+		let mut entropy = [0u8; MAX_NB_WORDS / 3 * 4];
+		for i in 0..nb_bytes_entropy * 8 {
+			if self.words[i / 11] >> (10 - i % 11) & 1 == 1 {
+				entropy[i / 8] |= 1 << (7 - i % 8);
+			}
+		}
+
+		let check = sha256::Hash::hash(&entropy[0..nb_bytes_entropy]);
+		#[cfg(feature = "zeroize")]
+		entropy.zeroize();
+		let checksum = (check[0] >> (8 - nb_checksum_bits)) as u16;
+
+		if self.last_word_missing() {
+			self.words[last] |= checksum;
+		} else if self.words[last] & ((1 << nb_checksum_bits) - 1) != checksum {
+			return None;
+		}
+
+		Some(Mnemonic {
+			lang: self.lang,
+			words: self.words,
+		})
+	}
+}
+
+impl Iterator for Completions {
+	type Item = Mnemonic;
+
+	fn next(&mut self) -> Option<Mnemonic> {
+		while self.next < self.end {
+			let candidate = self.next;
+			self.next += 1;
+
+			if let Some(mnemonic) = self.try_candidate(candidate) {
+				return Some(mnemonic);
+			}
+		}
+
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const MNEMONIC: &str =
+		"letter advice cage absurd amount doctor acoustic avoid letter advice cage above";
+
+	fn words_with_missing(missing: &[usize]) -> Vec<Option<&'static str>> {
+		MNEMONIC
+			.split(' ')
+			.enumerate()
+			.map(|(i, w)| if missing.contains(&i) { None } else { Some(w) })
+			.collect()
+	}
+
+	#[test]
+	fn test_one_missing_word() {
+		let completions =
+			Mnemonic::completions_in(Language::English, &words_with_missing(&[3])).unwrap();
+		assert_eq!(completions.nb_candidates(), 2048);
+
+		let completions = completions.map(|m| m.to_string()).collect::<Vec<_>>();
+		assert!(completions.contains(&MNEMONIC.to_string()));
+		// roughly 1 in 16 have a valid checksum
+		assert!(completions.len() > 64 && completions.len() < 256);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn test_missing_last_word() {
+		let completions =
+			Mnemonic::completions_in(Language::English, &words_with_missing(&[11])).unwrap();
+		assert_eq!(completions.nb_candidates(), 128);
+
+		// every candidate is valid, and they match the valid last words
+		let words = MNEMONIC.split(' ').take(11).collect::<Vec<_>>();
+		let last_words = Mnemonic::valid_last_words(Language::English, &words).unwrap();
+		let completions = completions
+			.map(|m| m.word_iter().last().unwrap())
+			.collect::<Vec<_>>();
+		assert_eq!(completions, last_words);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn test_two_missing_words_in_ranges() {
+		let completions =
+			Mnemonic::completions_in(Language::English, &words_with_missing(&[0, 11])).unwrap();
+		assert_eq!(completions.nb_candidates(), 2048 * 128);
+
+		let mnemonic = Mnemonic::parse(MNEMONIC).unwrap();
+		let half = completions.nb_candidates() / 2;
+		let found = completions
+			.clone()
+			.with_range(0..half)
+			.chain(completions.with_range(half..u64::MAX))
+			.filter(|m| *m == mnemonic)
+			.count();
+		assert_eq!(found, 1);
+	}
+
+	#[test]
+	fn test_completions_errors() {
+		let mut words = words_with_missing(&[0, 1]);
+		assert!(Mnemonic::completions_in(Language::English, &words).is_ok());
+
+		words[2] = None;
+		assert_eq!(
+			Mnemonic::completions_in(Language::English, &words).unwrap_err(),
+			Error::TooManyMissingWords(3)
+		);
+
+		words[2] = Some("zzz");
+		assert_eq!(
+			Mnemonic::completions_in(Language::English, &words).unwrap_err(),
+			Error::UnknownWord(2)
+		);
+
+		assert_eq!(
+			Mnemonic::completions_in(Language::English, &words[..10]).unwrap_err(),
+			Error::BadWordCount(10)
+		);
+	}
+}
@@ -0,0 +1,277 @@
+//! Contains just enough BIP32 key derivation to create fingerprints and receive addresses, keys
+//! only live as long as they are needed and are zeroed when dropped
+
+use bitcoin_hashes::{hash160, hmac, sha512, Hash, HashEngine};
+use k256::{
+    elliptic_curve::{group::Group, sec1::ToEncodedPoint, PrimeField},
+    FieldBytes, ProjectivePoint, Scalar,
+};
+use zeroize::Zeroize;
+
+/// Added to an index to derive a hardened child key
+pub const HARDENED: u32 = 1 << 31;
+
+pub struct ExtendedPrivateKey {
+    key: Scalar,
+    chain_code: [u8; 32],
+}
+
+pub struct ExtendedPublicKey {
+    point: ProjectivePoint,
+    chain_code: [u8; 32],
+}
+
+/// Splits the HMAC-SHA512 result into a key and a chain code, returns `None` for the (extremely
+/// unlikely) results that aren't valid keys
+fn split_hmac(engine: hmac::HmacEngine<sha512::Hash>) -> Option<(Scalar, [u8; 32])> {
+    let mut result = hmac::Hmac::<sha512::Hash>::from_engine(engine).into_inner();
+
+    let mut key_bytes = FieldBytes::default();
+    key_bytes.copy_from_slice(&result[0..32]);
+    let key: Option<Scalar> = Scalar::from_repr(key_bytes).into();
+    key_bytes.zeroize();
+    let mut chain_code = [0u8; 32];
+    chain_code.copy_from_slice(&result[32..64]);
+    result.zeroize();
+
+    Some((key?, chain_code))
+}
+
+impl ExtendedPrivateKey {
+    /// The BIP32 master key of a BIP39 seed
+    pub fn master(seed: &[u8]) -> Option<Self> {
+        let mut engine = hmac::HmacEngine::<sha512::Hash>::new(b"Bitcoin seed");
+        engine.input(seed);
+
+        let (key, chain_code) = split_hmac(engine)?;
+        if bool::from(key.is_zero()) {
+            return None;
+        }
+
+        Some(Self { key, chain_code })
+    }
+
+    /// Derives the hardened child key at `index` (without [HARDENED] added), returns `None` when
+    /// `index` is [HARDENED] or above
+    pub fn derive_hardened(&self, index: u32) -> Option<Self> {
+        let index = index.checked_add(HARDENED)?;
+
+        let mut engine = hmac::HmacEngine::<sha512::Hash>::new(&self.chain_code);
+        let mut key_bytes = self.key.to_repr();
+        engine.input(&[0]);
+        engine.input(&key_bytes);
+        engine.input(&index.to_be_bytes());
+        key_bytes.zeroize();
+
+        let (tweak, chain_code) = split_hmac(engine)?;
+        let key = tweak + self.key;
+        if bool::from(key.is_zero()) {
+            return None;
+        }
+
+        Some(Self { key, chain_code })
+    }
+
+    /// Derives a path of hardened child keys, ex: `[84, 0, 0]` for `m/84'/0'/0'`
+    pub fn derive_hardened_path(&self, path: &[u32]) -> Option<Self> {
+        let (first, rest) = path.split_first()?;
+        let mut key = self.derive_hardened(*first)?;
+
+        for index in rest {
+            key = key.derive_hardened(*index)?;
+        }
+
+        Some(key)
+    }
+
+    pub fn public_key(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            point: ProjectivePoint::GENERATOR * self.key,
+            chain_code: self.chain_code,
+        }
+    }
+}
+
+impl Drop for ExtendedPrivateKey {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.chain_code.zeroize();
+    }
+}
+
+impl ExtendedPublicKey {
+    /// Derives the normal (not hardened) child key at `index`, returns `None` when `index` is
+    /// [HARDENED] or above, hardened keys can't be derived from a public key
+    pub fn derive(&self, index: u32) -> Option<Self> {
+        if index >= HARDENED {
+            return None;
+        }
+
+        let mut engine = hmac::HmacEngine::<sha512::Hash>::new(&self.chain_code);
+        engine.input(&self.compressed());
+        engine.input(&index.to_be_bytes());
+
+        let (tweak, chain_code) = split_hmac(engine)?;
+        let point = ProjectivePoint::GENERATOR * tweak + self.point;
+        if bool::from(point.is_identity()) {
+            return None;
+        }
+
+        Some(Self { point, chain_code })
+    }
+
+    /// The public key in its compressed SEC1 encoding
+    pub fn compressed(&self) -> Vec<u8> {
+        self.point
+            .to_affine()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec()
+    }
+
+    /// The HASH160 of the compressed public key, the first 4 bytes are the key's fingerprint
+    pub fn hash160(&self) -> hash160::Hash {
+        hash160::Hash::hash(&self.compressed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // serializes a key the way BIP32 does, so it can be compared with the test vectors
+    fn serialize(
+        version: [u8; 4],
+        depth: u8,
+        parent: Option<&ExtendedPublicKey>,
+        index: u32,
+        chain_code: &[u8; 32],
+        key: &[u8],
+    ) -> String {
+        let fingerprint = parent.map_or([0; 4], |parent| {
+            let mut fingerprint = [0; 4];
+            fingerprint.copy_from_slice(&parent.hash160()[..4]);
+            fingerprint
+        });
+
+        let mut bytes = version.to_vec();
+        bytes.push(depth);
+        bytes.extend_from_slice(&fingerprint);
+        bytes.extend_from_slice(&index.to_be_bytes());
+        bytes.extend_from_slice(chain_code);
+        bytes.extend_from_slice(key);
+
+        bs58::encode(bytes).with_check().into_string()
+    }
+
+    fn xprv(
+        key: &ExtendedPrivateKey,
+        depth: u8,
+        parent: Option<&ExtendedPublicKey>,
+        index: u32,
+    ) -> String {
+        let mut key_data = vec![0];
+        key_data.extend_from_slice(&key.key.to_repr());
+
+        serialize(
+            [0x04, 0x88, 0xad, 0xe4],
+            depth,
+            parent,
+            index,
+            &key.chain_code,
+            &key_data,
+        )
+    }
+
+    fn xpub(
+        key: &ExtendedPublicKey,
+        depth: u8,
+        parent: Option<&ExtendedPublicKey>,
+        index: u32,
+    ) -> String {
+        serialize(
+            [0x04, 0x88, 0xb2, 0x1e],
+            depth,
+            parent,
+            index,
+            &key.chain_code,
+            &key.compressed(),
+        )
+    }
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-1
+    #[test]
+    fn derives_test_vector_1() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+
+        // m
+        let master = ExtendedPrivateKey::master(&seed).unwrap();
+        let master_public = master.public_key();
+        assert_eq!(
+            xprv(&master, 0, None, 0),
+            "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi"
+        );
+        assert_eq!(
+            xpub(&master_public, 0, None, 0),
+            "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"
+        );
+
+        // m/0'
+        let child = master.derive_hardened(0).unwrap();
+        let child_public = child.public_key();
+        assert_eq!(
+            xprv(&child, 1, Some(&master_public), HARDENED),
+            "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7"
+        );
+        assert_eq!(
+            xpub(&child_public, 1, Some(&master_public), HARDENED),
+            "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw"
+        );
+
+        // m/0'/1
+        let grandchild = child_public.derive(1).unwrap();
+        assert_eq!(
+            xpub(&grandchild, 2, Some(&child_public), 1),
+            "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ"
+        );
+    }
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-2
+    #[test]
+    fn derives_test_vector_2() {
+        let seed = hex::decode("fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542").unwrap();
+
+        // m
+        let master = ExtendedPrivateKey::master(&seed).unwrap();
+        let master_public = master.public_key();
+        assert_eq!(
+            xprv(&master, 0, None, 0),
+            "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U"
+        );
+        assert_eq!(
+            xpub(&master_public, 0, None, 0),
+            "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB"
+        );
+
+        // m/0
+        let child = master_public.derive(0).unwrap();
+        assert_eq!(
+            xpub(&child, 1, Some(&master_public), 0),
+            "xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH"
+        );
+    }
+
+    #[test]
+    fn rejects_indexes_that_are_already_hardened() {
+        let master = ExtendedPrivateKey::master(&[1; 64]).unwrap();
+
+        assert!(master.derive_hardened(HARDENED - 1).is_some());
+        assert!(master.derive_hardened(HARDENED).is_none());
+        assert!(master.derive_hardened(u32::MAX).is_none());
+        assert!(master.derive_hardened_path(&[84, HARDENED, 0]).is_none());
+
+        let public_key = master.public_key();
+        assert!(public_key.derive(HARDENED - 1).is_some());
+        assert!(public_key.derive(HARDENED).is_none());
+    }
+}
@@ -0,0 +1,213 @@
+//! Contains Bytewords, the encoding Blockchain Commons uses to show bytes as words: each byte is
+//! one of 256 four letter words, and a CRC32 checksum of the bytes is added to the end. Words can
+//! be written out in full, separated by spaces (standard) or dashes (URI), or shortened to their
+//! first and last letter (minimal), the way they are written in a UR.
+//! See: https://github.com/BlockchainCommons/Research/blob/master/papers/bcr-2020-012-bytewords.md
+
+use thiserror::Error;
+
+#[rustfmt::skip]
+pub const WORDS: [&str; 256] = [
+    "able", "acid", "also", "apex", "aqua", "arch", "atom", "aunt",
+    "away", "axis", "back", "bald", "barn", "belt", "beta", "bias",
+    "blue", "body", "brag", "brew", "bulb", "buzz", "calm", "cash",
+    "cats", "chef", "city", "claw", "code", "cola", "cook", "cost",
+    "crux", "curl", "cusp", "cyan", "dark", "data", "days", "deli",
+    "dice", "diet", "door", "down", "draw", "drop", "drum", "dull",
+    "duty", "each", "easy", "echo", "edge", "epic", "even", "exam",
+    "exit", "eyes", "fact", "fair", "fern", "figs", "film", "fish",
+    "fizz", "flap", "flew", "flux", "foxy", "free", "frog", "fuel",
+    "fund", "gala", "game", "gear", "gems", "gift", "girl", "glow",
+    "good", "gray", "grim", "guru", "gush", "gyro", "half", "hang",
+    "hard", "hawk", "heat", "help", "high", "hill", "holy", "hope",
+    "horn", "huts", "iced", "idea", "idle", "inch", "inky", "into",
+    "iris", "iron", "item", "jade", "jazz", "join", "jolt", "jowl",
+    "judo", "jugs", "jump", "junk", "jury", "keep", "keno", "kept",
+    "keys", "kick", "kiln", "king", "kite", "kiwi", "knob", "lamb",
+    "lava", "lazy", "leaf", "legs", "liar", "limp", "lion", "list",
+    "logo", "loud", "love", "luau", "luck", "lung", "main", "many",
+    "math", "maze", "memo", "menu", "meow", "mild", "mint", "miss",
+    "monk", "nail", "navy", "need", "news", "next", "noon", "note",
+    "numb", "obey", "oboe", "omit", "onyx", "open", "oval", "owls",
+    "paid", "part", "peck", "play", "plus", "poem", "pool", "pose",
+    "puff", "puma", "purr", "quad", "quiz", "race", "ramp", "real",
+    "redo", "rich", "road", "rock", "roof", "ruby", "ruin", "runs",
+    "rust", "safe", "saga", "scar", "sets", "silk", "skew", "slot",
+    "soap", "solo", "song", "stub", "surf", "swan", "taco", "task",
+    "taxi", "tent", "tied", "time", "tiny", "toil", "tomb", "toys",
+    "trip", "tuna", "twin", "ugly", "undo", "unit", "urge", "user",
+    "vast", "very", "veto", "vial", "vibe", "view", "visa", "void",
+    "vows", "wall", "wand", "warm", "wasp", "wave", "waxy", "webs",
+    "what", "when", "whiz", "wolf", "work", "yank", "yawn", "yell",
+    "yoga", "yurt", "zaps", "zero", "zest", "zinc", "zone", "zoom",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Style {
+    /// `able acid also`
+    Standard,
+
+    /// `able-acid-also`
+    Uri,
+
+    /// `aeadao`
+    Minimal,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum BytewordsError {
+    #[error("`{0}` is not a byteword")]
+    InvalidWord(String),
+
+    #[error("invalid bytewords, minimal bytewords have an even number of letters")]
+    InvalidLength,
+
+    #[error("the bytewords checksum doesn't match, one or more of the words are wrong or missing")]
+    InvalidChecksum,
+}
+
+pub fn encode(data: &[u8], style: Style) -> String {
+    let checksum = crc32(data).to_be_bytes();
+    let words = data.iter().chain(checksum.iter()).map(|byte| {
+        let word = WORDS[*byte as usize];
+        match style {
+            Style::Minimal => format!("{}{}", &word[0..1], &word[3..4]),
+            _ => word.to_string(),
+        }
+    });
+
+    let separator = match style {
+        Style::Standard => " ",
+        Style::Uri => "-",
+        Style::Minimal => "",
+    };
+
+    words.collect::<Vec<String>>().join(separator)
+}
+
+pub fn decode(text: &str, style: Style) -> Result<Vec<u8>, BytewordsError> {
+    let text = text.trim().to_lowercase();
+
+    let bytes = match style {
+        Style::Standard | Style::Uri => {
+            let separator = if style == Style::Uri { '-' } else { ' ' };
+            text.split(separator)
+                .filter(|word| !word.is_empty())
+                .map(|word| {
+                    WORDS
+                        .iter()
+                        .position(|byteword| *byteword == word)
+                        .map(|byte| byte as u8)
+                        .ok_or_else(|| BytewordsError::InvalidWord(word.to_string()))
+                })
+                .collect::<Result<Vec<u8>, _>>()?
+        }
+
+        Style::Minimal => {
+            if !text.is_ascii() || text.len() % 2 == 1 {
+                return Err(BytewordsError::InvalidLength);
+            }
+
+            text.as_bytes()
+                .chunks(2)
+                .map(|letters| {
+                    WORDS
+                        .iter()
+                        .position(|word| {
+                            word.as_bytes()[0] == letters[0] && word.as_bytes()[3] == letters[1]
+                        })
+                        .map(|byte| byte as u8)
+                        .ok_or_else(|| {
+                            BytewordsError::InvalidWord(
+                                String::from_utf8_lossy(letters).to_string(),
+                            )
+                        })
+                })
+                .collect::<Result<Vec<u8>, _>>()?
+        }
+    };
+
+    if bytes.len() < 4 {
+        return Err(BytewordsError::InvalidChecksum);
+    }
+
+    let (data, checksum) = bytes.split_at(bytes.len() - 4);
+    if crc32(data).to_be_bytes() != checksum {
+        return Err(BytewordsError::InvalidChecksum);
+    }
+
+    Ok(data.to_vec())
+}
+
+/// CRC-32 (ISO-HDLC), the same checksum zip files use
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, byte| {
+        (0..8).fold(crc ^ *byte as u32, |crc, _| {
+            if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // test vectors from the bytewords reference implementation
+    #[test]
+    fn calculates_the_checksum() {
+        assert_eq!(crc32(b"Hello, world!"), 0xebe6_c6e6);
+        assert_eq!(crc32(b"Wolf"), 0x598c_84dc);
+    }
+
+    #[test]
+    fn encodes_and_decodes_every_style() {
+        let data = vec![0, 1, 2, 128, 255];
+
+        let standard = "able acid also lava zoom jade need echo taxi";
+        assert_eq!(encode(&data, Style::Standard), standard);
+        assert_eq!(decode(standard, Style::Standard).unwrap(), data);
+
+        let uri = "able-acid-also-lava-zoom-jade-need-echo-taxi";
+        assert_eq!(encode(&data, Style::Uri), uri);
+        assert_eq!(decode(uri, Style::Uri).unwrap(), data);
+
+        assert_eq!(encode(&data, Style::Minimal), "aeadaolazmjendeoti");
+        assert_eq!(decode("aeadaolazmjendeoti", Style::Minimal).unwrap(), data);
+
+        assert_eq!(
+            encode(b"Some binary data", Style::Minimal),
+            "gujljnihcxidinjthsjpkkcxiehsjyhsnsgdmkht"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_bytewords() {
+        assert_eq!(
+            decode(
+                "able acid also lava zero jade need echo wolf",
+                Style::Standard
+            ),
+            Err(BytewordsError::InvalidChecksum)
+        );
+        assert_eq!(
+            decode("aeadaolazojendeowf", Style::Minimal),
+            Err(BytewordsError::InvalidChecksum)
+        );
+        assert_eq!(
+            decode("axxe tied also webs lung", Style::Standard),
+            Err(BytewordsError::InvalidWord("axxe".to_string()))
+        );
+        assert_eq!(
+            decode("wolf", Style::Standard),
+            Err(BytewordsError::InvalidChecksum)
+        );
+        assert_eq!(
+            decode("aea", Style::Minimal),
+            Err(BytewordsError::InvalidLength)
+        );
+    }
+}
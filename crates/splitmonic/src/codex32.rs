@@ -0,0 +1,691 @@
+//! Contains codex32 (BIP-93), a scheme for splitting and checksumming a secret that can be done by
+//! hand with paper computers (volvelles). Every share is a bech32 string: the `ms` prefix, the
+//! threshold, a 4 character identifier, the share index, the share's part of the secret and a BCH
+//! checksum, ex: `ms13cashsllhdmn9m42vcsamx24zrxgs3qqjzqud4m0d6nln`. Each character of a share is
+//! a point on a polynomial over GF(32), the secret is the share with index `s`.
+//!
+//! splitmonic splits the mnemonic's entropy, not its BIP32 seed, so the mnemonic can be recovered
+//! from the shares. Wallets that import codex32 use the secret as a BIP32 master seed directly,
+//! which is a different wallet than the mnemonic's.
+//! See: https://github.com/bitcoin/bips/blob/master/bip-0093.mediawiki
+
+use bip39::{Language, Mnemonic};
+use rand::{thread_rng, Rng, RngCore};
+use std::fmt;
+use thiserror::Error;
+use zeroize::Zeroize;
+
+const PREFIX: &str = "ms1";
+
+/// The bech32 characters, in the order of the field elements they stand for
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// The index of the share that is the secret
+const SECRET_INDEX: char = 's';
+
+/// The share indexes in the order shares are created, every bech32 character except `s`
+const SHARE_INDEXES: &str = "acdefghjklmnpqrtuvwxyz023456789";
+
+/// The number of characters in the header: the threshold, the identifier and the share index
+const HEADER_LENGTH: usize = 6;
+
+/// Logarithms of the elements of GF(32) to the base `z`, and their inverse
+#[rustfmt::skip]
+const LOG: [u8; 32] = [
+     0,  0,  1, 14,  2, 28, 15, 22,
+     3,  5, 29, 26, 16,  7, 23, 11,
+     4, 25,  6, 10, 30, 13, 27, 21,
+    17, 18,  8, 19, 24,  9, 12, 20,
+];
+
+#[rustfmt::skip]
+const EXP: [u8; 31] = [
+     1,  2,  4,  8, 16,  9, 18, 13,
+    26, 29, 19, 15, 30, 21,  3,  6,
+    12, 24, 25, 27, 31, 23,  7, 14,
+    28, 17, 11, 22,  5, 10, 20,
+];
+
+/// A BCH checksum, the generator polynomial and the residue a valid string has
+struct Checksum {
+    generator: &'static str,
+    target: &'static str,
+}
+
+/// The checksum of strings up to 93 characters long (secrets of up to 44 bytes)
+const SHORT_CHECKSUM: Checksum = Checksum {
+    generator: "em3gqeeelmcss",
+    target: "secretshare32",
+};
+
+/// The checksum of strings 125 to 127 characters long (63 and 64 byte secrets)
+const LONG_CHECKSUM: Checksum = Checksum {
+    generator: "02e6fe4xh4x9kyh",
+    target: "secretshare32ex",
+};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Codex32Error {
+    #[error("codex32 strings start with `ms1`")]
+    MissingPrefix,
+
+    #[error("invalid codex32 string length, codex32 strings are 48 to 93 or 125 to 127 characters long, found: {0}")]
+    InvalidLength(usize),
+
+    #[error("`{0}` is not a bech32 character")]
+    InvalidCharacter(char),
+
+    #[error("codex32 strings must be all lowercase or all uppercase")]
+    MixedCase,
+
+    #[error("invalid threshold `{0}`, the threshold must be between 2 and 9 (or 0 for an unshared secret)")]
+    InvalidThreshold(String),
+
+    #[error("invalid identifier `{0}`, identifiers are 4 bech32 characters")]
+    InvalidIdentifier(String),
+
+    #[error("an unshared codex32 secret must have the share index `s`, found: `{0}`")]
+    InvalidShareIndex(char),
+
+    #[error(
+        "the codex32 checksum doesn't match, one or more of the characters are wrong or missing"
+    )]
+    InvalidChecksum,
+
+    #[error("invalid number of shares, there must be between the threshold ({threshold}) and 31 shares, found: {count}")]
+    InvalidShareCount { threshold: u8, count: usize },
+
+    #[error("the codex32 shares have different identifiers, thresholds or lengths")]
+    MismatchedShares,
+
+    #[error("the codex32 share `{0}` was given twice")]
+    DuplicateShare(char),
+
+    #[error("not enough codex32 shares, gave {given}, expected {threshold}")]
+    NotEnoughShares { given: usize, threshold: u8 },
+
+    #[error("invalid secret length, codex32 secrets must be 16 to 44, 63 or 64 bytes, found: {0}")]
+    InvalidSecretLength(usize),
+
+    #[error("the codex32 shares failed verification, they did not recover the original mnemonic, do NOT use these shares")]
+    FailedVerification,
+}
+
+/// One codex32 share, or the secret itself when its index is `s`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Share {
+    pub threshold: u8,
+    pub identifier: String,
+    pub index: char,
+
+    /// every character after `ms1` as a field element: the header, the payload and the checksum
+    data: Vec<u8>,
+}
+
+impl Drop for Share {
+    fn drop(&mut self) {
+        self.data.zeroize()
+    }
+}
+
+impl fmt::Display for Share {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(PREFIX)?;
+        for value in &self.data {
+            write!(f, "{}", CHARSET[*value as usize] as char)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Share {
+    /// Creates a share from its part of the secret, the padding bits are zero
+    fn new(threshold: u8, identifier: &str, index: char, bytes: &[u8]) -> Self {
+        let header = format!("{}{}{}", threshold, identifier, index);
+        let mut data = header
+            .chars()
+            .map(|char| value_of(char).expect("header is made of bech32 characters"))
+            .collect::<Vec<u8>>();
+
+        let mut bits = 0u16;
+        let mut bit_count = 0;
+        for byte in bytes {
+            bits = bits << 8 | *byte as u16;
+            bit_count += 8;
+
+            while bit_count >= 5 {
+                bit_count -= 5;
+                data.push((bits >> bit_count) as u8 & 0x1f);
+            }
+        }
+
+        if bit_count > 0 {
+            data.push((bits << (5 - bit_count)) as u8 & 0x1f);
+        }
+
+        let checksum = checksum_for(PREFIX.len() + data.len() + SHORT_CHECKSUM.target.len());
+        let mut residue = polymod(
+            checksum,
+            data.iter().copied().chain(values(checksum.target)),
+        );
+        data.append(&mut residue);
+
+        Self {
+            threshold,
+            identifier: identifier.to_string(),
+            index,
+            data,
+        }
+    }
+
+    /// Reads a codex32 string, checking its checksum and header
+    pub fn parse(text: &str) -> Result<Self, Codex32Error> {
+        let text = text.trim();
+
+        let has_lowercase = text.chars().any(|char| char.is_ascii_lowercase());
+        let has_uppercase = text.chars().any(|char| char.is_ascii_uppercase());
+        if has_lowercase && has_uppercase {
+            return Err(Codex32Error::MixedCase);
+        }
+
+        let text = text.to_lowercase();
+        let rest = text
+            .strip_prefix(PREFIX)
+            .ok_or(Codex32Error::MissingPrefix)?;
+
+        let checksum = match text.len() {
+            48..=93 => &SHORT_CHECKSUM,
+            125..=127 => &LONG_CHECKSUM,
+            length => return Err(Codex32Error::InvalidLength(length)),
+        };
+
+        let data = rest
+            .chars()
+            .map(|char| value_of(char).ok_or(Codex32Error::InvalidCharacter(char)))
+            .collect::<Result<Vec<u8>, _>>()?;
+
+        if polymod(checksum, data.iter().copied()) != values(checksum.target).collect::<Vec<u8>>() {
+            return Err(Codex32Error::InvalidChecksum);
+        }
+
+        // any padding after the last whole byte must be 4 bits or less
+        let payload_length = data.len() - HEADER_LENGTH - checksum.target.len();
+        if payload_length * 5 % 8 > 4 {
+            return Err(Codex32Error::InvalidLength(text.len()));
+        }
+
+        let mut header = rest.chars();
+        let threshold = match header.next() {
+            Some('0') => 0,
+            Some(char @ '2'..='9') => char as u8 - b'0',
+            char => {
+                return Err(Codex32Error::InvalidThreshold(
+                    char.map(String::from).unwrap_or_default(),
+                ))
+            }
+        };
+
+        let identifier = header.by_ref().take(4).collect::<String>();
+        let index = header.next().unwrap_or_default();
+
+        if threshold == 0 && index != SECRET_INDEX {
+            return Err(Codex32Error::InvalidShareIndex(index));
+        }
+
+        Ok(Self {
+            threshold,
+            identifier,
+            index,
+            data,
+        })
+    }
+
+    /// The share's part of the secret, without the padding bits
+    pub fn payload(&self) -> Vec<u8> {
+        let checksum = checksum_for(PREFIX.len() + self.data.len());
+        let payload = &self.data[HEADER_LENGTH..self.data.len() - checksum.target.len()];
+
+        let mut bytes = Vec::with_capacity(payload.len() * 5 / 8);
+        let mut bits = 0u16;
+        let mut bit_count = 0;
+        for value in payload {
+            bits = bits << 5 | *value as u16;
+            bit_count += 5;
+
+            if bit_count >= 8 {
+                bit_count -= 8;
+                bytes.push((bits >> bit_count) as u8);
+            }
+        }
+
+        bits.zeroize();
+        bytes
+    }
+}
+
+fn value_of(char: char) -> Option<u8> {
+    CHARSET
+        .iter()
+        .position(|bech32_char| *bech32_char as char == char)
+        .map(|value| value as u8)
+}
+
+fn values(text: &'static str) -> impl Iterator<Item = u8> {
+    text.chars()
+        .map(|char| value_of(char).expect("constant is made of bech32 characters"))
+}
+
+fn mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+
+    EXP[(LOG[a as usize] as usize + LOG[b as usize] as usize) % 31]
+}
+
+fn div(a: u8, b: u8) -> u8 {
+    if a == 0 {
+        return 0;
+    }
+
+    EXP[(LOG[a as usize] as usize + 31 - LOG[b as usize] as usize) % 31]
+}
+
+/// The checksum used by strings of this length, including the `ms1` prefix and the checksum
+fn checksum_for(length: usize) -> &'static Checksum {
+    if length <= 93 {
+        &SHORT_CHECKSUM
+    } else {
+        &LONG_CHECKSUM
+    }
+}
+
+/// The residue of the `ms` prefix followed by these field elements, modulo the generator
+fn polymod(checksum: &Checksum, data: impl Iterator<Item = u8>) -> Vec<u8> {
+    let generator = values(checksum.generator).collect::<Vec<u8>>();
+
+    let mut residue = vec![0; generator.len()];
+    residue[generator.len() - 1] = 1;
+
+    let prefix = PREFIX.trim_end_matches('1').bytes();
+    let expanded_prefix = prefix
+        .clone()
+        .map(|byte| byte >> 5)
+        .chain(Some(0))
+        .chain(prefix.map(|byte| byte & 0x1f));
+
+    for value in expanded_prefix.chain(data) {
+        let overflow = residue.remove(0);
+        residue.push(value);
+
+        for (coefficient, generator) in residue.iter_mut().zip(&generator) {
+            *coefficient ^= mul(*generator, overflow);
+        }
+    }
+
+    residue
+}
+
+/// The share at `index` of the polynomial going through `shares`, every character is
+/// interpolated, including the header and checksum, which stay valid
+fn interpolate(shares: &[Share], index: char) -> Share {
+    if let Some(share) = shares.iter().find(|share| share.index == index) {
+        return share.clone();
+    }
+
+    let x = value_of(index).expect("share index is a bech32 character");
+    let xs = shares
+        .iter()
+        .map(|share| value_of(share.index).expect("share index is a bech32 character"))
+        .collect::<Vec<u8>>();
+
+    // the lagrange basis polynomials at x, subtraction is the same as addition in GF(32)
+    let numerator = xs.iter().fold(1, |product, x_i| mul(product, x_i ^ x));
+    let mut data = vec![0; shares[0].data.len()];
+
+    for (i, share) in shares.iter().enumerate() {
+        let denominator = xs
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .fold(xs[i] ^ x, |product, (_, x_j)| mul(product, xs[i] ^ x_j));
+        let basis = div(numerator, denominator);
+
+        for (value, share_value) in data.iter_mut().zip(&share.data) {
+            *value ^= mul(basis, *share_value);
+        }
+    }
+
+    Share {
+        threshold: shares[0].threshold,
+        identifier: shares[0].identifier.clone(),
+        index,
+        data,
+    }
+}
+
+/// Splits a secret into `count` shares, any `threshold` of which recover it. The first
+/// `threshold - 1` shares are random, the rest are interpolated from them and the secret
+pub fn split<R: RngCore>(
+    secret: &[u8],
+    threshold: u8,
+    count: usize,
+    identifier: &str,
+    rng: &mut R,
+) -> Result<Vec<Share>, Codex32Error> {
+    if !matches!(secret.len(), 16..=44 | 63 | 64) {
+        return Err(Codex32Error::InvalidSecretLength(secret.len()));
+    }
+
+    if !(2..=9).contains(&threshold) {
+        return Err(Codex32Error::InvalidThreshold(threshold.to_string()));
+    }
+
+    if count < threshold as usize || count > SHARE_INDEXES.len() {
+        return Err(Codex32Error::InvalidShareCount { threshold, count });
+    }
+
+    let identifier = identifier.to_lowercase();
+    if identifier.chars().count() != 4 || identifier.chars().any(|char| value_of(char).is_none()) {
+        return Err(Codex32Error::InvalidIdentifier(identifier));
+    }
+
+    let mut indexes = SHARE_INDEXES.chars();
+
+    let mut shares = indexes
+        .by_ref()
+        .take(threshold as usize - 1)
+        .map(|index| {
+            let mut bytes = vec![0; secret.len()];
+            rng.fill_bytes(&mut bytes);
+
+            let share = Share::new(threshold, &identifier, index, &bytes);
+            bytes.zeroize();
+
+            share
+        })
+        .collect::<Vec<Share>>();
+
+    let mut points = shares.clone();
+    points.push(Share::new(threshold, &identifier, SECRET_INDEX, secret));
+
+    shares.extend(
+        indexes
+            .take(count - shares.len())
+            .map(|index| interpolate(&points, index)),
+    );
+
+    Ok(shares)
+}
+
+/// Recovers the secret from at least threshold shares
+pub fn recover(shares: &[Share]) -> Result<Vec<u8>, Codex32Error> {
+    let first = shares.first().ok_or(Codex32Error::NotEnoughShares {
+        given: 0,
+        threshold: 1,
+    })?;
+
+    if shares.iter().any(|share| {
+        share.threshold != first.threshold
+            || share.identifier != first.identifier
+            || share.data.len() != first.data.len()
+    }) {
+        return Err(Codex32Error::MismatchedShares);
+    }
+
+    for (i, share) in shares.iter().enumerate() {
+        if shares[..i].iter().any(|other| other.index == share.index) {
+            return Err(Codex32Error::DuplicateShare(share.index));
+        }
+    }
+
+    if first.threshold == 0 {
+        return Ok(first.payload());
+    }
+
+    if shares.len() < first.threshold as usize {
+        return Err(Codex32Error::NotEnoughShares {
+            given: shares.len(),
+            threshold: first.threshold,
+        });
+    }
+
+    Ok(interpolate(&shares[..first.threshold as usize], SECRET_INDEX).payload())
+}
+
+/// Splits the entropy of a mnemonic code into codex32 shares, and checks that the shares recover
+/// it. A random identifier is used when none is given
+pub fn split_mnemonic_code(
+    mnemonic_code: &str,
+    threshold: u8,
+    count: usize,
+    identifier: Option<&str>,
+) -> Result<Vec<Share>, crate::Error> {
+    let mnemonic = Mnemonic::parse_in(Language::English, mnemonic_code)?;
+    let mut entropy = mnemonic.to_entropy();
+
+    let mut rng = thread_rng();
+    let identifier = match identifier {
+        Some(identifier) => identifier.to_string(),
+        None => (0..4)
+            .map(|_| CHARSET[rng.gen_range(0..CHARSET.len())] as char)
+            .collect(),
+    };
+
+    let shares = split(&entropy, threshold, count, &identifier, &mut rng);
+    let verified = match &shares {
+        Ok(shares) => verify(shares, threshold, &entropy),
+        Err(_) => Ok(()),
+    };
+    entropy.zeroize();
+
+    let shares = shares?;
+    verified?;
+
+    Ok(shares)
+}
+
+/// Checks the last `threshold` shares recover the entropy they were split from
+fn verify(shares: &[Share], threshold: u8, entropy: &[u8]) -> Result<(), Codex32Error> {
+    let mut recovered = recover(&shares[shares.len() - threshold as usize..])?;
+    let verified = recovered == entropy;
+    recovered.zeroize();
+
+    if !verified {
+        return Err(Codex32Error::FailedVerification);
+    }
+
+    Ok(())
+}
+
+/// Recovers a mnemonic code from codex32 shares
+pub fn recover_mnemonic_code(shares: &[String]) -> Result<String, crate::Error> {
+    let shares = shares
+        .iter()
+        .map(|share| Share::parse(share))
+        .collect::<Result<Vec<Share>, _>>()?;
+
+    let mut entropy = recover(&shares)?;
+    let mnemonic = Mnemonic::from_entropy(&entropy);
+    entropy.zeroize();
+
+    Ok(mnemonic?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC_CODE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    fn parse_all(shares: &[&str]) -> Vec<Share> {
+        shares
+            .iter()
+            .map(|share| Share::parse(share).unwrap())
+            .collect()
+    }
+
+    // the test vectors are from BIP-93
+
+    #[test]
+    fn reads_an_unshared_secret() {
+        let secret = Share::parse("ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlw").unwrap();
+
+        assert_eq!(secret.threshold, 0);
+        assert_eq!(secret.identifier, "test");
+        assert_eq!(secret.index, 's');
+        assert_eq!(hex(&secret.payload()), "318c6318c6318c6318c6318c6318c631");
+        assert_eq!(recover(&[secret]).unwrap().len(), 16);
+
+        let long_secret = Share::parse("MS100C8VSM32ZXFGUHPCHTLUPZRY9X8GF2TVDW0S3JN54KHCE6MUA7LQPZYGSFJD6AN074RXVCEMLH8WU3TK925ACDEFGHJKLMNPQRSTUVWXY06FHPV80UNDVARHRAK").unwrap();
+        assert_eq!(hex(&long_secret.payload()), "dc5423251cb87175ff8110c8531d0952d8d73e1194e95b5f19d6f9df7c01111104c9baecdfea8cccc677fb9ddc8aec5553b86e528bcadfdcc201c17c638c47e9");
+    }
+
+    #[test]
+    fn interpolates_shares() {
+        let shares = parse_all(&[
+            "MS12NAMEA320ZYXWVUTSRQPNMLKJHGFEDCAXRPP870HKKQRM",
+            "MS12NAMECACDEFGHJKLMNPQRSTUVWXYZ023FTR2GDZMPY6PN",
+        ]);
+
+        assert_eq!(
+            interpolate(&shares, 'd').to_string(),
+            "ms12namedll4f8jlh4e5vdvuldlfxu2jhdnlsm97xvenrxeg"
+        );
+        assert_eq!(
+            interpolate(&shares, 's').to_string(),
+            "ms12names6xqguzttxkeqnjsjzv4jv3nz5k3kwgsphuh6evw"
+        );
+        assert_eq!(
+            hex(&recover(&shares).unwrap()),
+            "d1808e096b35b209ca12132b264662a5"
+        );
+
+        let shares = parse_all(&[
+            "ms13cashsllhdmn9m42vcsamx24zrxgs3qqjzqud4m0d6nln",
+            "ms13casha320zyxwvutsrqpnmlkjhgfedca2a8d0zehn8a0t",
+            "ms13cashcacdefghjklmnpqrstuvwxyz023949xq35my48dr",
+        ]);
+
+        assert_eq!(
+            interpolate(&shares, 'd').to_string(),
+            "ms13cashd0wsedstcdcts64cd7wvy4m90lm28w4ffupqs7rm"
+        );
+        assert_eq!(
+            interpolate(&shares, 'e').to_string(),
+            "ms13casheekgpemxzshcrmqhaydlp6yhms3ws7320xyxsar9"
+        );
+        assert_eq!(
+            interpolate(&shares, 'f').to_string(),
+            "ms13cashf8jh6sdrkpyrsp5ut94pj8ktehhw2hfvyrj48704"
+        );
+    }
+
+    #[test]
+    fn creates_a_checksummed_secret() {
+        let seed = [
+            0xff, 0xee, 0xdd, 0xcc, 0xbb, 0xaa, 0x99, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22,
+            0x11, 0x00, 0xff, 0xee, 0xdd, 0xcc, 0xbb, 0xaa, 0x99, 0x88, 0x77, 0x66, 0x55, 0x44,
+            0x33, 0x22, 0x11, 0x00,
+        ];
+
+        assert_eq!(
+            Share::new(0, "leet", 's', &seed).to_string(),
+            "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyqqtum9pgv99ycma"
+        );
+
+        // any padding bits are accepted
+        let other_padding = Share::parse(
+            "ms10leetsllhdmn9m42vcsamx24zrxgs3qrl7ahwvhw4fnzrhve25gvezzyq0pgjxpzx0ysaam",
+        )
+        .unwrap();
+        assert_eq!(other_padding.payload(), seed);
+    }
+
+    #[test]
+    fn rejects_invalid_strings() {
+        assert_eq!(
+            Share::parse("ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxxxxmazxdp4sx5q"),
+            Err(Codex32Error::InvalidChecksum)
+        );
+        assert_eq!(
+            Share::parse("ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx3n5n5gyweuvq3"),
+            Err(Codex32Error::InvalidChecksum)
+        );
+        assert!(matches!(
+            Share::parse("ms12testxxxxxxxxxxxxxxxxxxxxxxxxxxxxqmufxffdkzfac"),
+            Err(Codex32Error::InvalidLength(_))
+        ));
+        assert_eq!(
+            Share::parse("ms10testxxxxxxxxxxxxxxxxxxxxxxxxxxxx3wq9mzgrwag9"),
+            Err(Codex32Error::InvalidShareIndex('x'))
+        );
+        assert_eq!(
+            Share::parse("ms1testxxxxxxxxxxxxxxxxxxxxxxxxxxxxs9lz3we7s9wh4"),
+            Err(Codex32Error::InvalidThreshold("t".to_string()))
+        );
+        assert_eq!(
+            Share::parse("ms10testSxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlw"),
+            Err(Codex32Error::MixedCase)
+        );
+        assert_eq!(
+            Share::parse("0testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlw"),
+            Err(Codex32Error::MissingPrefix)
+        );
+    }
+
+    #[test]
+    fn splits_and_recovers_a_mnemonic_code() {
+        let shares = split_mnemonic_code(MNEMONIC_CODE, 3, 5, Some("TEST")).unwrap();
+
+        assert_eq!(shares.len(), 5);
+        assert!(shares
+            .iter()
+            .all(|share| share.to_string().starts_with("ms13test")));
+        assert_eq!(
+            shares.iter().map(|share| share.index).collect::<String>(),
+            "acdef"
+        );
+
+        let some_shares = vec![
+            shares[4].to_string(),
+            shares[0].to_string(),
+            shares[2].to_string(),
+        ];
+        assert_eq!(recover_mnemonic_code(&some_shares).unwrap(), MNEMONIC_CODE);
+
+        assert_eq!(
+            recover_mnemonic_code(&some_shares[..2]),
+            Err(Codex32Error::NotEnoughShares {
+                given: 2,
+                threshold: 3
+            }
+            .into())
+        );
+        assert_eq!(
+            recover_mnemonic_code(&[some_shares[0].clone(), some_shares[0].clone()]),
+            Err(Codex32Error::DuplicateShare('f').into())
+        );
+        assert_eq!(
+            split_mnemonic_code(MNEMONIC_CODE, 3, 5, Some("bio!")),
+            Err(Codex32Error::InvalidIdentifier("bio!".to_string()).into())
+        );
+    }
+
+    #[test]
+    fn verifies_the_shares_recover_the_mnemonic_code() {
+        let entropy = Mnemonic::parse(MNEMONIC_CODE).unwrap().to_entropy();
+        let shares = split(&entropy, 3, 5, "test", &mut thread_rng()).unwrap();
+
+        assert_eq!(verify(&shares, 3, &entropy), Ok(()));
+        assert_eq!(
+            verify(&shares, 3, &[1; 32]),
+            Err(Codex32Error::FailedVerification)
+        );
+    }
+}
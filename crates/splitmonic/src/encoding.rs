@@ -0,0 +1,514 @@
+//! Contains the encodings split shares can be written in. Every encoding implements
+//! [ShareEncoder], so a new one can be added without touching the shamir secret sharing itself.
+//!
+//! - words: the 3 set id words, the share id word and the share as a BIP39 mnemonic (default)
+//! - indexes, padded-indexes and binary: the same words as word numbers, see [crate::word_numbers]
+//! - hex, base32 and base58: the set id, the share and a 4 byte checksum as bytes
+//! - bytewords: the set id and the share as Bytewords, with their CRC32 checksum
+//!
+//! As bytes, a split share is:
+//!
+//! | set id (3 x 2 bytes) | share id (1 byte) | share |
+
+use crate::bytewords::{self, Style};
+use crate::word_numbers::{self, WordNumbers};
+use crate::wordlist::{English, Wordlist};
+use bip39::{Language, Mnemonic};
+use bitcoin_hashes::{sha256, Hash};
+use thiserror::Error;
+use zeroize::Zeroize;
+
+const SET_ID_LENGTH: usize = 6;
+const CHECKSUM_LENGTH: usize = 4;
+
+/// The lengths of the share after the share id, the entropy of a 12, 18 or 24 word mnemonic
+const SHARE_LENGTHS: [usize; 3] = [16, 24, 32];
+
+/// The RFC 4648 base32 alphabet, written without padding
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum EncodingError {
+    #[error("`{0}` is not valid hex")]
+    InvalidHex(String),
+
+    #[error("`{0}` is not a base32 character")]
+    InvalidBase32(char),
+
+    #[error("`{0}` is not valid base58")]
+    InvalidBase58(String),
+
+    #[error("the split share is too short, found {0} bytes")]
+    TooShort(usize),
+
+    #[error("the split share's set id is invalid")]
+    InvalidSetId,
+
+    #[error("the split share's share id is 0, share ids start at 1")]
+    InvalidShareId,
+
+    #[error("the split share is {0} bytes long, expected 16, 24 or 32 bytes after the share id")]
+    InvalidShareLength(usize),
+
+    #[error("the split share's checksum doesn't match, one or more of the characters are wrong")]
+    InvalidChecksum,
+}
+
+/// One share of a split mnemonic, with the set it belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitShare {
+    /// The word indexes of the 3 set id words, shared by every share of the set
+    pub set_id: [u16; 3],
+
+    /// The share, with the share id as the first byte
+    pub share: Vec<u8>,
+}
+
+impl SplitShare {
+    /// The 3 set id words
+    pub fn set_id_words(&self) -> String {
+        self.set_id
+            .iter()
+            .map(|index| English::get_word(*index as usize).unwrap_or_default())
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(SET_ID_LENGTH + self.share.len() + CHECKSUM_LENGTH);
+        for index in &self.set_id {
+            bytes.extend_from_slice(&index.to_be_bytes());
+        }
+        bytes.extend_from_slice(&self.share);
+
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, EncodingError> {
+        if bytes.len() < SET_ID_LENGTH + 2 {
+            return Err(EncodingError::TooShort(bytes.len()));
+        }
+
+        let mut set_id = [0; 3];
+        for (index, pair) in bytes[..SET_ID_LENGTH].chunks(2).enumerate() {
+            set_id[index] = u16::from_be_bytes([pair[0], pair[1]]);
+        }
+
+        if set_id.iter().any(|index| *index >= 2048) {
+            return Err(EncodingError::InvalidSetId);
+        }
+
+        let share = &bytes[SET_ID_LENGTH..];
+        if share[0] == 0 {
+            return Err(EncodingError::InvalidShareId);
+        }
+
+        if !SHARE_LENGTHS.contains(&(share.len() - 1)) {
+            return Err(EncodingError::InvalidShareLength(share.len() - 1));
+        }
+
+        Ok(Self {
+            set_id,
+            share: share.to_vec(),
+        })
+    }
+
+    // the bytes followed by the first 4 bytes of their SHA256
+    fn to_checked_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes();
+        let hash = sha256::Hash::hash(&bytes);
+        bytes.extend_from_slice(&hash[..CHECKSUM_LENGTH]);
+
+        bytes
+    }
+
+    fn from_checked_bytes(mut bytes: Vec<u8>) -> Result<Self, EncodingError> {
+        if bytes.len() < CHECKSUM_LENGTH {
+            return Err(EncodingError::TooShort(bytes.len()));
+        }
+
+        let (data, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LENGTH);
+        let split_share = if sha256::Hash::hash(data)[..CHECKSUM_LENGTH] == checksum[..] {
+            Self::from_bytes(data)
+        } else {
+            Err(EncodingError::InvalidChecksum)
+        };
+        bytes.zeroize();
+
+        split_share
+    }
+}
+
+impl Drop for SplitShare {
+    fn drop(&mut self) {
+        self.share.zeroize();
+    }
+}
+
+/// Turns split shares into text and back
+pub trait ShareEncoder {
+    fn encode(&self, split_share: &SplitShare) -> Result<String, crate::Error>;
+    fn decode(&self, text: &str) -> Result<SplitShare, crate::Error>;
+}
+
+/// The encodings that can be chosen by name
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    Words,
+    Indexes,
+    PaddedIndexes,
+    Binary,
+    Hex,
+    Base32,
+    Base58,
+    Bytewords,
+}
+
+impl Encoding {
+    pub fn encoder(self) -> &'static dyn ShareEncoder {
+        match self {
+            Self::Words => &Words,
+            Self::Indexes => &WordIndexes(WordNumbers::Decimal),
+            Self::PaddedIndexes => &WordIndexes(WordNumbers::Padded),
+            Self::Binary => &WordIndexes(WordNumbers::Binary),
+            Self::Hex => &Hex,
+            Self::Base32 => &Base32,
+            Self::Base58 => &Base58,
+            Self::Bytewords => &Bytewords,
+        }
+    }
+}
+
+impl std::str::FromStr for Encoding {
+    type Err = String;
+
+    fn from_str(encoding: &str) -> Result<Self, Self::Err> {
+        match encoding {
+            "words" => Ok(Self::Words),
+            "indexes" => Ok(Self::Indexes),
+            "padded-indexes" => Ok(Self::PaddedIndexes),
+            "binary" => Ok(Self::Binary),
+            "hex" => Ok(Self::Hex),
+            "base32" => Ok(Self::Base32),
+            "base58" => Ok(Self::Base58),
+            "bytewords" => Ok(Self::Bytewords),
+            _ => Err(format!("unknown share encoding: {}", encoding)),
+        }
+    }
+}
+
+/// Split phrases: the 3 set id words, the share id word and the share as a BIP39 mnemonic
+pub struct Words;
+
+impl ShareEncoder for Words {
+    fn encode(&self, split_share: &SplitShare) -> Result<String, crate::Error> {
+        let id_word = English::get_word(split_share.share[0] as usize)?;
+        let words = Mnemonic::from_entropy(&split_share.share[1..])?.to_string();
+
+        Ok(format!(
+            "{} {} {}",
+            split_share.set_id_words(),
+            id_word,
+            words
+        ))
+    }
+
+    fn decode(&self, text: &str) -> Result<SplitShare, crate::Error> {
+        let words = text.split_whitespace().collect::<Vec<&str>>();
+        if words.len() < 4 {
+            return Err(crate::Error::PhraseTooShort(words.len()));
+        }
+
+        let mut set_id = [0; 3];
+        for (index, word) in words[..3].iter().enumerate() {
+            set_id[index] = English::get_index(word)? as u16;
+        }
+
+        let id = English::get_index(words[3])?;
+        if id == 0 || id > u8::MAX as usize {
+            return Err(crate::Error::InvalidShareIndex(words[3].to_string()));
+        }
+
+        let mut share = Mnemonic::parse_in(Language::English, words[4..].join(" "))?.to_entropy();
+        share.insert(0, id as u8);
+
+        Ok(SplitShare { set_id, share })
+    }
+}
+
+/// Split phrases written as word numbers, any form of word number is read back
+pub struct WordIndexes(pub WordNumbers);
+
+impl ShareEncoder for WordIndexes {
+    fn encode(&self, split_share: &SplitShare) -> Result<String, crate::Error> {
+        let mut split_phrase = Words.encode(split_share)?;
+        let numbers = word_numbers::phrase_to_word_numbers(&split_phrase, self.0);
+        split_phrase.zeroize();
+
+        numbers
+    }
+
+    fn decode(&self, text: &str) -> Result<SplitShare, crate::Error> {
+        let mut split_phrase = word_numbers::word_numbers_to_phrase(text)?;
+        let split_share = Words.decode(&split_phrase);
+        split_phrase.zeroize();
+
+        split_share
+    }
+}
+
+/// The split share as lowercase hex, with a checksum
+pub struct Hex;
+
+impl ShareEncoder for Hex {
+    fn encode(&self, split_share: &SplitShare) -> Result<String, crate::Error> {
+        let mut bytes = split_share.to_checked_bytes();
+        let encoded = hex::encode(&bytes);
+        bytes.zeroize();
+
+        Ok(encoded)
+    }
+
+    fn decode(&self, text: &str) -> Result<SplitShare, crate::Error> {
+        let text = text.trim();
+        let bytes = hex::decode(text).map_err(|_| EncodingError::InvalidHex(text.to_string()))?;
+
+        Ok(SplitShare::from_checked_bytes(bytes)?)
+    }
+}
+
+/// The split share as unpadded RFC 4648 base32, with a checksum
+pub struct Base32;
+
+impl ShareEncoder for Base32 {
+    fn encode(&self, split_share: &SplitShare) -> Result<String, crate::Error> {
+        let mut bytes = split_share.to_checked_bytes();
+        let mut encoded = String::with_capacity((bytes.len() * 8).div_ceil(5));
+        let mut accumulator: u16 = 0;
+        let mut bits = 0;
+
+        for byte in &bytes {
+            accumulator = (accumulator << 8) | *byte as u16;
+            bits += 8;
+
+            while bits >= 5 {
+                bits -= 5;
+                encoded.push(BASE32_ALPHABET[((accumulator >> bits) & 0x1F) as usize] as char);
+            }
+        }
+
+        if bits > 0 {
+            encoded.push(BASE32_ALPHABET[((accumulator << (5 - bits)) & 0x1F) as usize] as char);
+        }
+        bytes.zeroize();
+
+        Ok(encoded)
+    }
+
+    fn decode(&self, text: &str) -> Result<SplitShare, crate::Error> {
+        let text = text.trim().trim_end_matches('=');
+        let mut bytes = Vec::with_capacity(text.len() * 5 / 8);
+        let mut accumulator: u16 = 0;
+        let mut bits = 0;
+
+        for char in text.chars() {
+            let value = BASE32_ALPHABET
+                .iter()
+                .position(|base32_char| *base32_char as char == char.to_ascii_uppercase())
+                .ok_or(EncodingError::InvalidBase32(char))?;
+
+            accumulator = (accumulator << 5) | value as u16;
+            bits += 5;
+
+            if bits >= 8 {
+                bits -= 8;
+                bytes.push((accumulator >> bits) as u8);
+            }
+        }
+
+        Ok(SplitShare::from_checked_bytes(bytes)?)
+    }
+}
+
+/// The split share as base58 (the bitcoin alphabet), with a checksum
+pub struct Base58;
+
+impl ShareEncoder for Base58 {
+    fn encode(&self, split_share: &SplitShare) -> Result<String, crate::Error> {
+        let mut bytes = split_share.to_checked_bytes();
+        let encoded = bs58::encode(&bytes).into_string();
+        bytes.zeroize();
+
+        Ok(encoded)
+    }
+
+    fn decode(&self, text: &str) -> Result<SplitShare, crate::Error> {
+        let text = text.trim();
+        let bytes = bs58::decode(text)
+            .into_vec()
+            .map_err(|_| EncodingError::InvalidBase58(text.to_string()))?;
+
+        Ok(SplitShare::from_checked_bytes(bytes)?)
+    }
+}
+
+/// The split share as Bytewords, which add their own CRC32 checksum
+pub struct Bytewords;
+
+impl ShareEncoder for Bytewords {
+    fn encode(&self, split_share: &SplitShare) -> Result<String, crate::Error> {
+        let mut bytes = split_share.to_bytes();
+        let encoded = bytewords::encode(&bytes, Style::Standard);
+        bytes.zeroize();
+
+        Ok(encoded)
+    }
+
+    fn decode(&self, text: &str) -> Result<SplitShare, crate::Error> {
+        let mut bytes = bytewords::decode(text, Style::Standard)?;
+        let split_share = SplitShare::from_bytes(&bytes);
+        bytes.zeroize();
+
+        Ok(split_share?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPLIT_PHRASE: &str = "embody fog drop about embrace visa adapt winner wine dash fabric snack drip auction deputy visit shift animal various bread country lecture assist marriage merit goat gravity glove";
+
+    const ENCODINGS: [Encoding; 8] = [
+        Encoding::Words,
+        Encoding::Indexes,
+        Encoding::PaddedIndexes,
+        Encoding::Binary,
+        Encoding::Hex,
+        Encoding::Base32,
+        Encoding::Base58,
+        Encoding::Bytewords,
+    ];
+
+    #[test]
+    fn every_encoding_round_trips() {
+        let split_share = Words.decode(SPLIT_PHRASE).unwrap();
+        assert_eq!(split_share.set_id_words(), "embody fog drop");
+        assert_eq!(split_share.share.len(), 33);
+
+        for encoding in ENCODINGS.iter() {
+            let encoder = encoding.encoder();
+            let encoded = encoder.encode(&split_share).unwrap();
+
+            assert_eq!(encoder.decode(&encoded).unwrap(), split_share);
+        }
+
+        assert_eq!(Words.encode(&split_share).unwrap(), SPLIT_PHRASE);
+    }
+
+    #[test]
+    fn word_indexes_are_the_word_numbers() {
+        let split_share = Words.decode(SPLIT_PHRASE).unwrap();
+        let numbers = WordIndexes(WordNumbers::Decimal)
+            .encode(&split_share)
+            .unwrap();
+
+        // embody is word 580, about is word 4
+        assert!(numbers.starts_with("580 "));
+        assert_eq!(numbers.split(' ').nth(3), Some("4"));
+
+        let binary = WordIndexes(WordNumbers::Binary)
+            .encode(&split_share)
+            .unwrap();
+        assert!(binary.starts_with("01001000011 "));
+        assert_eq!(
+            WordIndexes(WordNumbers::Decimal).decode(&binary).unwrap(),
+            split_share
+        );
+    }
+
+    #[test]
+    fn detects_changed_characters() {
+        let split_share = Words.decode(SPLIT_PHRASE).unwrap();
+
+        for encoding in [Encoding::Hex, Encoding::Base32, Encoding::Base58].iter() {
+            let encoder = encoding.encoder();
+            let mut encoded = encoder.encode(&split_share).unwrap().into_bytes();
+
+            // swap two different characters in the middle of the share
+            let middle = encoded.len() / 2;
+            let other = (middle + 1..encoded.len())
+                .find(|index| encoded[*index] != encoded[middle])
+                .unwrap();
+            encoded.swap(middle, other);
+
+            let changed = String::from_utf8(encoded).unwrap();
+            assert_eq!(
+                encoder.decode(&changed),
+                Err(EncodingError::InvalidChecksum.into())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_text() {
+        assert_eq!(
+            Hex.decode("xyz"),
+            Err(EncodingError::InvalidHex("xyz".to_string()).into())
+        );
+        assert_eq!(
+            Base32.decode("AB1"),
+            Err(EncodingError::InvalidBase32('1').into())
+        );
+        assert_eq!(
+            Base58.decode("0OIl"),
+            Err(EncodingError::InvalidBase58("0OIl".to_string()).into())
+        );
+        assert_eq!(Hex.decode("00"), Err(EncodingError::TooShort(1).into()));
+        assert_eq!(
+            Words.decode("embody fog drop"),
+            Err(crate::Error::PhraseTooShort(3))
+        );
+        assert_eq!(
+            Words.decode(&SPLIT_PHRASE.replace("drop about", "drop abandon")),
+            Err(crate::Error::InvalidShareIndex("abandon".to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_shares() {
+        let split_share = Words.decode(SPLIT_PHRASE).unwrap();
+
+        let mut bytes = split_share.to_bytes();
+        bytes[SET_ID_LENGTH] = 0;
+        assert_eq!(
+            SplitShare::from_bytes(&bytes),
+            Err(EncodingError::InvalidShareId)
+        );
+
+        let bytes = split_share.to_bytes();
+        assert_eq!(
+            SplitShare::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(EncodingError::InvalidShareLength(31))
+        );
+
+        let mut truncated = split_share.clone();
+        truncated.share.truncate(20);
+        for encoding in [
+            Encoding::Hex,
+            Encoding::Base32,
+            Encoding::Base58,
+            Encoding::Bytewords,
+        ]
+        .iter()
+        {
+            let encoder = encoding.encoder();
+            let encoded = encoder.encode(&truncated).unwrap();
+
+            assert_eq!(
+                encoder.decode(&encoded),
+                Err(EncodingError::InvalidShareLength(19).into())
+            );
+        }
+    }
+}
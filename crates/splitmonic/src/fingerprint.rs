@@ -0,0 +1,60 @@
+//! Contains helpers for creating a non-secret fingerprint of a mnemonic code, the fingerprint can
+//! be written down next to the split phrases and compared with the fingerprint of the recovered
+//! mnemonic code, to confirm the recovery was correct without loading it into a wallet
+
+use crate::{bip32::ExtendedPrivateKey, Error};
+use bip39::Mnemonic;
+use zeroize::Zeroize;
+
+/// When given a BIP39 mnemonic code, returns the BIP32 master key fingerprint as 8 hex characters,
+/// this is the same fingerprint hardware wallets show (without a passphrase)
+pub fn get_fingerprint(mnemonic_code: &str) -> Result<String, Error> {
+    get_fingerprint_with_passphrase(mnemonic_code, "")
+}
+
+/// Same as [get_fingerprint] but for a mnemonic code used with a BIP39 passphrase
+pub fn get_fingerprint_with_passphrase(
+    mnemonic_code: &str,
+    passphrase: &str,
+) -> Result<String, Error> {
+    let mut mnemonic = Mnemonic::parse(mnemonic_code)?;
+    let mut seed = mnemonic.to_seed(passphrase);
+    mnemonic.zeroize();
+
+    let master = ExtendedPrivateKey::master(&seed);
+    seed.zeroize();
+
+    let hash = master.ok_or(Error::Fingerprint)?.public_key().hash160();
+
+    Ok(hash[0..4]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gets_the_master_fingerprint() {
+        let mnemonic_code = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        assert_eq!(get_fingerprint(mnemonic_code).unwrap(), "73c5da0a");
+    }
+
+    #[test]
+    fn fingerprint_changes_with_passphrase() {
+        let mnemonic_code = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        assert_ne!(
+            get_fingerprint_with_passphrase(mnemonic_code, "TREZOR").unwrap(),
+            "73c5da0a"
+        );
+    }
+
+    #[test]
+    fn errors_on_invalid_mnemonic() {
+        assert!(get_fingerprint("abandon abandon").is_err())
+    }
+}
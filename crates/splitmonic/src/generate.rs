@@ -0,0 +1,159 @@
+//! Contains functions for generating a new mnemonic code, so it can be split right away without
+//! ever being written down
+
+use crate::Error;
+use bip39::{Language, Mnemonic};
+use bitcoin_hashes::{sha256, Hash, HashEngine};
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use zeroize::Zeroize;
+
+pub use bip39::EntropySource;
+
+/// Randomness supplied by the user, mixed in with the operating system's randomness
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExtraEntropy {
+    /// d6 dice rolls, ex: "3 1 6 6 2 4"
+    DiceRolls(String),
+    /// coin flips as h/t or 1/0, ex: "hhtht"
+    CoinFlips(String),
+}
+
+impl ExtraEntropy {
+    /// Parses the rolls or flips with [EntropySource::parse_rolls], the same way the rolls for
+    /// [mnemonic_code_from_rolls] are parsed, and returns them numbered from zero
+    pub fn rolls(&self) -> Result<Vec<u16>, Error> {
+        let (source, input) = match self {
+            Self::DiceRolls(input) => (EntropySource::D6, input),
+            Self::CoinFlips(input) => (EntropySource::CoinFlip, input),
+        };
+
+        Ok(source.parse_rolls(input)?)
+    }
+}
+
+/// Generates a new BIP39 mnemonic code with `word_count` (12, 18 or 24) words.
+///
+/// The mnemonic code is generated from the operating system's random number generator, if any dice
+/// rolls or coin flips are given, they are hashed together with the operating system's randomness.
+/// So the mnemonic code is at least as random as the better of the two.
+pub fn generate_mnemonic_code(
+    word_count: usize,
+    extra_entropy: Option<&ExtraEntropy>,
+) -> Result<String, Error> {
+    let mut os_entropy = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut os_entropy);
+
+    let mut engine = sha256::Hash::engine();
+    engine.input(&os_entropy);
+    os_entropy.zeroize();
+
+    if let Some(extra_entropy) = extra_entropy {
+        let mut rolls = extra_entropy.rolls()?;
+        for roll in rolls.iter() {
+            engine.input(&roll.to_be_bytes());
+        }
+        rolls.zeroize();
+    }
+
+    let mut seed = sha256::Hash::from_engine(engine).into_inner();
+    let mut rng = StdRng::from_seed(seed);
+    seed.zeroize();
+
+    let mut mnemonic = Mnemonic::generate_in_with(&mut rng, Language::English, word_count)?;
+    let mnemonic_code = mnemonic.to_string();
+    mnemonic.zeroize();
+
+    Ok(mnemonic_code)
+}
+
+/// Creates a BIP39 mnemonic code with `word_count` (12, 18 or 24) words only from the given coin
+/// flips, dice rolls or cards, without any randomness from the operating system.
+///
+/// Anyone can check the mnemonic code was created from the rolls, see [bip39::entropy] for how
+/// the rolls are turned into entropy, and [EntropySource::rolls_needed] for how many are needed.
+pub fn mnemonic_code_from_rolls(
+    word_count: usize,
+    source: EntropySource,
+    rolls: &str,
+) -> Result<String, Error> {
+    let mut rolls = source.parse_rolls(rolls)?;
+    let mut mnemonic = Mnemonic::from_rolls(source, word_count, rolls.iter().copied())?;
+    rolls.zeroize();
+
+    let mnemonic_code = mnemonic.to_string();
+    mnemonic.zeroize();
+
+    Ok(mnemonic_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_mnemonic_codes() {
+        for word_count in [12, 18, 24] {
+            let mnemonic_code = generate_mnemonic_code(word_count, None).unwrap();
+
+            assert_eq!(mnemonic_code.split(' ').count(), word_count);
+            assert!(Mnemonic::parse(&mnemonic_code).is_ok());
+        }
+    }
+
+    #[test]
+    fn mixes_in_dice_rolls() {
+        let dice_rolls = ExtraEntropy::DiceRolls("1 2 3 4 5 6 6 5 4 3 2 1".to_string());
+
+        let first = generate_mnemonic_code(24, Some(&dice_rolls)).unwrap();
+        let second = generate_mnemonic_code(24, Some(&dice_rolls)).unwrap();
+
+        // the same dice rolls never create the same mnemonic code
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn errors_on_invalid_extra_entropy() {
+        let dice_rolls = ExtraEntropy::DiceRolls("1 2 7".to_string());
+        let coin_flips = ExtraEntropy::CoinFlips("h, T, 1, x".to_string());
+
+        assert_eq!(
+            generate_mnemonic_code(24, Some(&dice_rolls)),
+            Err(Error::BIP39(bip39::Error::InvalidRoll(2)))
+        );
+        assert_eq!(
+            coin_flips.rolls(),
+            Err(Error::BIP39(bip39::Error::InvalidRoll(3)))
+        );
+        assert_eq!(
+            ExtraEntropy::CoinFlips("h, T, 1".to_string()).rolls(),
+            Ok(vec![1, 0, 1])
+        );
+    }
+
+    #[test]
+    fn creates_the_same_mnemonic_code_from_the_same_rolls() {
+        let rolls = "3 1 6 6 2 4 ".repeat(40);
+
+        let first = mnemonic_code_from_rolls(24, EntropySource::D6, &rolls).unwrap();
+        let second = mnemonic_code_from_rolls(24, EntropySource::D6, &rolls).unwrap();
+
+        assert_eq!(first, second);
+        assert!(Mnemonic::parse(&first).is_ok());
+    }
+
+    #[test]
+    fn errors_on_not_enough_rolls() {
+        assert_eq!(
+            mnemonic_code_from_rolls(12, EntropySource::CoinFlip, "hhtt"),
+            Err(Error::BIP39(bip39::Error::NotEnoughRolls {
+                needed: 128,
+                kept: 4
+            }))
+        );
+    }
+
+    #[test]
+    fn errors_on_invalid_word_count() {
+        assert!(generate_mnemonic_code(13, None).is_err())
+    }
+}
@@ -0,0 +1,201 @@
+//! Contains helpers for reading the metadata out of a single split phrase, without needing any
+//! of the other split phrases from the set
+
+use crate::share_file;
+use crate::validation::SPLIT_PHRASE_LENGTHS;
+use crate::word_numbers;
+use crate::wordlist::{English, Wordlist};
+use crate::{Error, NUMBER_OF_SHARES, THRESHOLD};
+use bip39::{Language, Mnemonic};
+use std::path::Path;
+
+/// The version of the split phrase format, the original format is a 3 word set id,
+/// followed by a 1 word share id, followed by a 24 (or 12, 18) word BIP39 mnemonic
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Everything that can be learned about a split phrase on its own
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitPhraseInfo {
+    /// the first 3 words, shared by all the phrases in the set
+    pub set_id: String,
+
+    /// the share number of this split phrase in the set (1 to 5)
+    pub share_index: u8,
+
+    /// the number of words in the split phrase, valid split phrases are 28 words long
+    /// (16 or 22 words long when splitting 12 or 18 word mnemonics)
+    pub word_count: usize,
+
+    /// whether the words after the share id have a valid BIP39 checksum
+    pub valid_checksum: bool,
+
+    /// the format version from the header of a versioned share file, otherwise the version of the
+    /// split phrase format
+    pub format_version: u8,
+
+    /// how many split phrases are needed to recover the mnemonic
+    pub threshold: u8,
+
+    /// how many split phrases were created in this set
+    pub number_of_shares: u8,
+}
+
+impl SplitPhraseInfo {
+    /// A split phrase is only usable for recovery if it is the right length and has a valid checksum
+    pub fn is_valid(&self) -> bool {
+        SPLIT_PHRASE_LENGTHS.contains(&self.word_count) && self.valid_checksum
+    }
+}
+
+/// When given a single split phrase, returns which set and share it is, without recovering anything
+pub fn inspect_split_phrase(split_phrase: &str) -> Result<SplitPhraseInfo, Error> {
+    let words: Vec<&str> = split_phrase.split_whitespace().collect();
+
+    if words.len() < 4 {
+        return Err(Error::PhraseTooShort(words.len()));
+    }
+
+    for word in &words {
+        English::get_index(word)?;
+    }
+
+    let share_index = English::get_index(words[3])?;
+    if share_index == 0 || share_index > NUMBER_OF_SHARES as usize {
+        return Err(Error::InvalidShareIndex(words[3].to_string()));
+    }
+
+    let valid_checksum = SPLIT_PHRASE_LENGTHS.contains(&words.len())
+        && Mnemonic::parse_in(Language::English, words[4..].join(" ")).is_ok();
+
+    Ok(SplitPhraseInfo {
+        set_id: words[0..3].join(" "),
+        share_index: share_index as u8,
+        word_count: words.len(),
+        valid_checksum,
+        format_version: FORMAT_VERSION,
+        threshold: THRESHOLD,
+        number_of_shares: NUMBER_OF_SHARES,
+    })
+}
+
+/// Same as [inspect_split_phrase], for the split phrase in a share file. When the file has a
+/// versioned header, the header is checked against the words and its format version, threshold
+/// and number of shares are reported, bare split phrases get the defaults
+pub fn inspect_share_file<P: AsRef<Path>>(path: P) -> Result<SplitPhraseInfo, Error> {
+    let (words, header) = share_file::read_share_file_with_header(path)?;
+    let split_phrase = words.join(" ");
+
+    // split phrases stamped as word numbers are read as words
+    let split_phrase = if word_numbers::are_word_numbers(&split_phrase) {
+        word_numbers::word_numbers_to_phrase(&split_phrase)?
+    } else {
+        split_phrase
+    };
+
+    let info = inspect_split_phrase(&split_phrase)?;
+
+    Ok(match header {
+        Some(header) => SplitPhraseInfo {
+            format_version: header.version,
+            threshold: header.threshold,
+            number_of_shares: header.number_of_shares,
+            ..info
+        },
+        None => info,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inspects_a_valid_split_phrase() {
+        let info = inspect_split_phrase("embody fog drop about embrace visa adapt winner wine dash fabric snack drip auction deputy visit shift animal various bread country lecture assist marriage merit goat gravity glove").unwrap();
+
+        assert_eq!(
+            info,
+            SplitPhraseInfo {
+                set_id: "embody fog drop".to_string(),
+                share_index: 3,
+                word_count: 28,
+                valid_checksum: true,
+                format_version: 1,
+                threshold: 3,
+                number_of_shares: 5,
+            }
+        );
+        assert!(info.is_valid())
+    }
+
+    #[test]
+    fn inspects_a_split_phrase_with_a_bad_checksum() {
+        let info = inspect_split_phrase("embody fog drop about embrace visa adapt winner wine dash fabric snack drip auction deputy visit shift animal various bread country lecture assist marriage merit goat glove gravity").unwrap();
+
+        assert_eq!(info.share_index, 3);
+        assert!(!info.valid_checksum);
+        assert!(!info.is_valid())
+    }
+
+    #[test]
+    fn inspects_a_split_phrase_that_is_too_short() {
+        let info = inspect_split_phrase("embody fog drop able network").unwrap();
+
+        assert_eq!(info.set_id, "embody fog drop");
+        assert_eq!(info.share_index, 2);
+        assert_eq!(info.word_count, 5);
+        assert!(!info.valid_checksum);
+
+        assert_eq!(
+            inspect_split_phrase("embody fog drop"),
+            Err(Error::PhraseTooShort(3))
+        );
+    }
+
+    #[test]
+    fn inspects_a_share_file_using_its_header() {
+        let split_phrase = "embody fog drop about embrace visa adapt winner wine dash fabric snack drip auction deputy visit shift animal various bread country lecture assist marriage merit goat gravity glove";
+        let header =
+            share_file::ShareFileHeader::for_split_phrase(split_phrase, None, None).unwrap();
+        let text = share_file::share_file_text(split_phrase, &header);
+
+        let dir = std::env::temp_dir().join(format!("splitmonic_inspect_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let versioned = dir.join("versioned.txt");
+        let mismatched = dir.join("mismatched.txt");
+        let bare = dir.join("bare.txt");
+        std::fs::write(&versioned, &text).unwrap();
+        std::fs::write(
+            &mismatched,
+            text.replace("# share: 3 of 5", "# share: 3 of 4"),
+        )
+        .unwrap();
+        std::fs::write(&bare, split_phrase).unwrap();
+
+        let info = inspect_share_file(&versioned).unwrap();
+        assert_eq!(info, inspect_split_phrase(split_phrase).unwrap());
+        assert_eq!(
+            (info.format_version, info.threshold, info.number_of_shares),
+            (header.version, header.threshold, header.number_of_shares)
+        );
+
+        assert!(matches!(
+            inspect_share_file(&mismatched),
+            Err(Error::ShareFile(share_file::ShareFileError::Invalid { .. }))
+        ));
+        assert_eq!(
+            inspect_share_file(&bare),
+            inspect_split_phrase(split_phrase)
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn errors_on_an_invalid_share_index() {
+        assert_eq!(
+            inspect_split_phrase("embody fog drop zoo network"),
+            Err(Error::InvalidShareIndex("zoo".to_string()))
+        );
+    }
+}
@@ -1,13 +1,61 @@
+mod bip32;
+pub mod bytewords;
+pub mod codex32;
+pub mod encoding;
+pub mod fingerprint;
+pub mod generate;
+pub mod inspect;
+pub mod missing_words;
+pub mod qr;
+pub mod secret;
+pub mod seed_xor;
 pub mod shamir;
+pub mod share_file;
+pub mod sskr;
+pub mod ssss;
 pub mod validation;
+pub mod vault;
+pub mod word_numbers;
 pub mod wordlist;
 
+use crate::encoding::{ShareEncoder, SplitShare};
 use crate::shamir::SecretData;
 use bip39::Mnemonic;
-use wordlist::{Wordlist, WordlistError};
+use wordlist::WordlistError;
 use zeroize::Zeroize;
 
 use thiserror::Error;
+
+/// The number of split phrases needed to recover the mnemonic code
+pub const THRESHOLD: u8 = 3;
+
+/// The number of split phrases created when splitting a mnemonic code
+pub const NUMBER_OF_SHARES: u8 = 5;
+
+/// How a mnemonic code is split, into splitmonic's own split phrases, SSKR shares, codex32 shares
+/// or Seed XOR parts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scheme {
+    Splitmonic,
+    Sskr,
+    Codex32,
+    Xor,
+}
+
+impl std::str::FromStr for Scheme {
+    type Err = String;
+
+    fn from_str(scheme: &str) -> Result<Self, Self::Err> {
+        match scheme {
+            "splitmonic" => Ok(Self::Splitmonic),
+            "sskr" => Ok(Self::Sskr),
+            "codex32" => Ok(Self::Codex32),
+            "xor" => Ok(Self::Xor),
+            _ => Err(format!("unknown split scheme: {}", scheme)),
+        }
+    }
+}
+
 #[derive(Debug, Error, PartialEq)]
 pub enum Error {
     #[error(transparent)]
@@ -19,94 +67,206 @@ pub enum Error {
     #[error(transparent)]
     Shamir(#[from] shamir::ShamirError),
 
+    #[error(transparent)]
+    ShareFile(#[from] share_file::ShareFileError),
+
+    #[error(transparent)]
+    Bytewords(#[from] bytewords::BytewordsError),
+
+    #[error(transparent)]
+    Encoding(#[from] encoding::EncodingError),
+
+    #[error(transparent)]
+    Sskr(#[from] sskr::SskrError),
+
+    #[error(transparent)]
+    Codex32(#[from] codex32::Codex32Error),
+
+    #[error(transparent)]
+    SeedXor(#[from] seed_xor::SeedXorError),
+
+    #[error(transparent)]
+    Vault(#[from] vault::VaultError),
+
+    #[error(transparent)]
+    Ssss(#[from] ssss::SsssError),
+
+    #[error(transparent)]
+    WordNumber(#[from] word_numbers::WordNumberError),
+
     #[error("error converting share(s) to phrase")]
     ShareToPhrase,
 
     #[error("not enough shares, gave {gave:?}, expected {expected:?}")]
     NotEnoughShares { gave: usize, expected: u8 },
 
-    #[error("unable to recover secret")]
-    UnableToRecoverSecret,
-
     #[error("all phrases must be from the same set, expected: {0}\nphrases given:\n {1}")]
     MismatchedSet(String, String),
+
+    #[error("split phrase is too short, expected at least 4 words, found: {0}")]
+    PhraseTooShort(usize),
+
+    #[error("the share id word `{0}` is not a valid share number")]
+    InvalidShareIndex(String),
+
+    #[error("unable to create a fingerprint for this mnemonic")]
+    Fingerprint,
+
+    #[error("invalid threshold, the threshold must be between 2 and the number of shares ({number_of_shares}), found: {threshold}")]
+    InvalidThreshold { threshold: u8, number_of_shares: u8 },
+
+    #[error("invalid secret length, secrets must be between 1 and 65535 bytes, found: {0}")]
+    InvalidSecretLength(usize),
+
+    #[error("secret phrase number {} is invalid, {}", .0 + 1, .1)]
+    InvalidSecretPhrase(usize, &'static str),
+
+    #[error("secret phrase number {} has an invalid checksum, one or more of the words are wrong or out of order", .0 + 1)]
+    SecretPhraseChecksum(usize),
+
+    #[error(
+        "too many missing words, at most {} can be recovered, found: {0}",
+        missing_words::MAX_MISSING_WORDS
+    )]
+    TooManyMissingWords(usize),
+
+    #[error("invalid fingerprint `{0}`, fingerprints are 8 hex characters")]
+    InvalidFingerprint(String),
+
+    #[error("unsupported address `{0}`, only legacy (1...), nested segwit (3...) and native segwit (bc1q...) addresses are supported")]
+    UnsupportedAddress(String),
+
+    #[error("invalid compact encoding, the padding of the {0} bytes is wrong")]
+    InvalidCompactEncoding(usize),
+
+    #[error("split phrases failed verification, split phrases {} did not recover the original mnemonic, do NOT use these split phrases", validation::phrase_numbers(.0))]
+    FailedVerification(Vec<usize>),
 }
 
 /// When given a BIP39 mnemonic code, returns a vec containing 5 split phrases.
 /// 3 of these 5 codes can later be used to recreate your original mnemonic code.
+///
+/// Split phrases are 28 words long for a 24 word mnemonic code (16 and 22 words for 12 and 18 words)
+///
+/// Before returning, the mnemonic code is recovered from every combination of 3 split phrases and
+/// checked against the mnemonic code that was split. Returns an error if any of the combinations
+/// does not recover the original mnemonic code.
 pub fn get_split_phrases(mnemonic_code: String) -> Result<Vec<String>, Error> {
-    use rand::Rng;
+    get_encoded_split_shares(mnemonic_code, &encoding::Words, true)
+}
 
-    let mut rng = rand::thread_rng();
+/// Same as [get_split_phrases], but the split shares are written with the given encoder, and are
+/// only verified if `verify` is true
+pub fn get_encoded_split_shares(
+    mnemonic_code: String,
+    encoder: &dyn ShareEncoder,
+    verify: bool,
+) -> Result<Vec<String>, Error> {
+    let mut mnemonic = Mnemonic::parse(&mnemonic_code)?;
+    let mut entropy = mnemonic.to_entropy();
+    mnemonic.zeroize();
+
+    // the set id is the same for all the shares of this set, this helps identify which set a
+    // share belongs to
+    let set_id = secret::random_set_id();
+
+    let split_shares = split::get_split_shares(mnemonic_code)
+        .and_then(|shares| {
+            shares
+                .iter()
+                .map(|share| {
+                    encoder.encode(&SplitShare {
+                        set_id,
+                        share: share.clone(),
+                    })
+                })
+                .collect::<Result<Vec<String>, Error>>()
+        })
+        .and_then(|split_shares| {
+            if verify {
+                verify::verify_split_phrases(&entropy, &split_shares, encoder)?;
+            }
+            Ok(split_shares)
+        });
+    entropy.zeroize();
 
-    let mut shares = split::get_split_shares(mnemonic_code)?;
+    split_shares
+}
 
-    let phrases = shares
-        .iter_mut()
-        .map(split::share_to_phrase)
-        .collect::<Result<Vec<String>, Error>>()?;
+/// When given a vector of at least 3 split phrases, returns the original mnemonic code
+pub fn recover_mnemonic_code(split_phrases: Vec<String>) -> Result<String, Error> {
+    recover_mnemonic_code_from(split_phrases, &encoding::Words)
+}
 
-    if shares.len() != phrases.len() {
-        return Err(Error::ShareToPhrase);
-    }
+/// Same as [recover_mnemonic_code], for split shares written with the given encoder
+pub fn recover_mnemonic_code_from(
+    split_shares: Vec<String>,
+    encoder: &dyn ShareEncoder,
+) -> Result<String, Error> {
+    let mut recovered = recover_entropy(split_shares, encoder)?;
 
-    // the first three words of all the phrases for this set are the same
-    // the helps identify which set it belongs to
-    let three_word_set_id = vec![
-        rng.gen_range(0..2048),
-        rng.gen_range(0..2048),
-        rng.gen_range(0..2048),
-    ]
-    .iter()
-    .map(|id| wordlist::English::get_word(*id as usize).unwrap())
-    .collect::<Vec<&'static str>>()
-    .join(" ");
-
-    let mut complete_phrases = Vec::with_capacity(5);
-    for phrase in phrases {
-        complete_phrases.push(format!("{} {}", &three_word_set_id, phrase))
-    }
+    let mnemonic = Mnemonic::from_entropy(&recovered)?.to_string();
+    recovered.zeroize();
 
-    Ok(complete_phrases)
+    Ok(mnemonic)
 }
 
-/// When given a vector of at least 3 split phrases, returns the original mnemonic code
-pub fn recover_mnemonic_code(mut split_phrases: Vec<String>) -> Result<String, Error> {
-    let number_of_split_phrases = split_phrases.len();
+fn recover_entropy(
+    mut split_shares: Vec<String>,
+    encoder: &dyn ShareEncoder,
+) -> Result<Vec<u8>, Error> {
+    let number_of_split_shares = split_shares.len();
 
-    if number_of_split_phrases < 3 {
+    if number_of_split_shares < THRESHOLD as usize {
         return Err(Error::NotEnoughShares {
-            gave: number_of_split_phrases,
-            expected: 3,
+            gave: number_of_split_shares,
+            expected: THRESHOLD,
         });
     }
 
-    let split_phrases_words = split_phrases_into_words(&split_phrases);
-    let split_phrases_without_set_ids = recover::verify_and_remove_set_id(split_phrases_words)?;
+    let decoded = decode_split_shares(&split_shares, encoder);
+    split_shares.zeroize();
 
-    let split_shares = split_phrases_without_set_ids
-        .into_iter()
-        .map(recover::words_to_share)
-        .collect::<Result<Vec<Vec<u8>>, Error>>()?;
-    split_phrases.zeroize();
+    let shares = decoded?
+        .iter()
+        .map(|split_share| split_share.share.clone())
+        .collect();
 
-    if split_shares.len() != number_of_split_phrases {
-        return Err(Error::UnableToRecoverSecret);
-    }
+    Ok(SecretData::recover_secret(THRESHOLD, shares)?)
+}
 
-    let mut recovered =
-        SecretData::recover_secret(3, split_shares).ok_or(Error::UnableToRecoverSecret)?;
+// decodes the split shares and checks they are all from the same set, and are all different shares
+fn decode_split_shares(
+    split_shares: &[String],
+    encoder: &dyn ShareEncoder,
+) -> Result<Vec<SplitShare>, Error> {
+    let decoded = split_shares
+        .iter()
+        .map(|split_share| encoder.decode(split_share))
+        .collect::<Result<Vec<SplitShare>, Error>>()?;
+
+    for (index, split_share) in decoded.iter().enumerate() {
+        if split_share.set_id != decoded[0].set_id {
+            return Err(Error::MismatchedSet(
+                decoded[0].set_id_words(),
+                split_shares[index].clone(),
+            ));
+        }
 
-    let mnemonic = Mnemonic::from_entropy(&recovered)?.to_string();
-    recovered.zeroize();
+        if decoded[..index]
+            .iter()
+            .any(|other| other.share[0] == split_share.share[0])
+        {
+            return Err(shamir::ShamirError::DuplicateShare(split_share.share[0]).into());
+        }
+    }
 
-    Ok(mnemonic)
+    Ok(decoded)
 }
 
 mod split {
-    //! Contains helper functions used for splitting the mnemonic code into phrases
+    //! Contains helper functions used for splitting the mnemonic code into shares
 
-    use crate::wordlist::{English, Wordlist};
     use crate::{shamir::SecretData, Error};
     use bip39::Mnemonic;
     use zeroize::Zeroize;
@@ -118,7 +278,7 @@ mod split {
         let mut entropy = mnemonic.to_entropy();
         mnemonic.zeroize();
 
-        let secret_data = SecretData::with_secret(&entropy, 3);
+        let secret_data = SecretData::with_secret(&entropy, crate::THRESHOLD);
         entropy.zeroize();
 
         Ok([
@@ -129,96 +289,75 @@ mod split {
             secret_data.get_share(5)?,
         ])
     }
-
-    pub(crate) fn share_to_phrase(share: &mut Vec<u8>) -> Result<String, Error> {
-        let id = share.remove(0);
-        let id_word = English::get_word(id as usize)?;
-
-        let words = Mnemonic::from_entropy(share).unwrap().to_string();
-        share.zeroize();
-
-        Ok(format!("{} {}", id_word, words))
-    }
 }
 
-mod recover {
-    //! Contains helper functions used for recovering the mnemonic code from the split phrases
-
-    use crate::{
-        wordlist::{English, Wordlist},
-        Error,
-    };
-    use bip39::{Language, Mnemonic};
+mod verify {
+    //! Contains helper functions used for verifying split phrases right after splitting
 
-    // verifies that all the phrases passed in are from the same set
-    // if they are from the same set, returns the phrase without the set id words
-    pub(crate) fn verify_and_remove_set_id(
-        split_phrases: Vec<Vec<&str>>,
-    ) -> Result<Vec<Vec<&str>>, Error> {
-        let mut set_id = Vec::with_capacity(3);
-        let mut without_ids = Vec::with_capacity(split_phrases.len());
+    use crate::{encoding::ShareEncoder, Error, NUMBER_OF_SHARES, THRESHOLD};
+    use subtle::ConstantTimeEq;
+    use zeroize::Zeroize;
 
-        for split_phrase in split_phrases {
-            if set_id.is_empty() {
-                set_id = split_phrase[0..3].to_vec()
-            }
+    // recovers the secret from every combination of `THRESHOLD` split phrases, and compares
+    // each recovered secret with the original, in constant time
+    pub(crate) fn verify_split_phrases(
+        entropy: &[u8],
+        split_phrases: &[String],
+        encoder: &dyn ShareEncoder,
+    ) -> Result<(), Error> {
+        if split_phrases.len() != NUMBER_OF_SHARES as usize {
+            return Err(Error::NotEnoughShares {
+                gave: split_phrases.len(),
+                expected: NUMBER_OF_SHARES,
+            });
+        }
 
-            if set_id[0..3] != split_phrase[0..3] {
-                return Err(Error::MismatchedSet(
-                    set_id
-                        .iter()
-                        .map(|str| str.to_string())
-                        .collect::<Vec<String>>()
-                        .join(" "),
-                    split_phrase
-                        .iter()
-                        .map(|str| str.to_string())
-                        .collect::<Vec<String>>()
-                        .join(" "),
-                ));
+        for combination in combinations(split_phrases.len(), THRESHOLD as usize) {
+            let phrases = combination
+                .iter()
+                .map(|index| split_phrases[*index].clone())
+                .collect();
+
+            let matches = match crate::recover_entropy(phrases, encoder) {
+                Ok(mut recovered) => {
+                    let matches = recovered.ct_eq(entropy).into();
+                    recovered.zeroize();
+                    matches
+                }
+                Err(_) => false,
+            };
+
+            if !matches {
+                return Err(Error::FailedVerification(combination));
             }
-
-            without_ids.push(split_phrase[3..].to_vec())
         }
 
-        Ok(without_ids)
+        Ok(())
     }
 
-    pub(crate) fn words_to_share(mut words: Vec<&str>) -> Result<Vec<u8>, Error> {
-        let id_word = words.remove(0);
-        let id = English::get_index(id_word)?;
+    // returns all the combinations of `k` indexes out of `n` indexes, in order
+    pub(crate) fn combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+        if k == 0 {
+            return vec![vec![]];
+        }
 
-        let mut share = Mnemonic::parse_in(Language::English, &words.join(" "))?.to_entropy();
+        if k > n {
+            return vec![];
+        }
 
-        share.insert(0, id as u8);
+        // every combination either includes the last index or it doesn't
+        let mut with_last = combinations(n - 1, k - 1);
+        for combination in with_last.iter_mut() {
+            combination.push(n - 1);
+        }
 
-        Ok(share)
+        let mut all = combinations(n - 1, k);
+        all.append(&mut with_last);
+        all.sort();
+        all
     }
 }
 
-// takes a vector of phrases and turns it into a vector of vector of words
-// ```rust, ignore
-// let phrases = vec!["hello there".to_string(), "how are you".to_string()];
-// let words_vector = split_phrases_into_words(&phrases);
-//
-// assert_eq!(words_vector, vec![
-//   vec!["hello", "there"],
-//   vec!["how", "are", "you"]
-// ])
-// ```
-fn split_phrases_into_words(split_phrases: &[String]) -> Vec<Vec<&str>> {
-    split_phrases
-        .iter()
-        .map(|phrase| {
-            phrase
-                .trim()
-                .split(' ')
-                .filter(|phrase| !phrase.is_empty())
-                .collect::<Vec<&str>>()
-        })
-        .collect()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,6 +394,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn verified_split_phrases_recover_from_every_combination() {
+        let mnemonic_code = "dance monitor unveil wood cycle uphold video elephant run unlock theme year divide text lyrics captain expose garlic bundle patrol praise net hour point";
+        let split_phrases = get_split_phrases(mnemonic_code.to_string()).unwrap();
+
+        for combination in verify::combinations(5, 3) {
+            let phrases = combination
+                .iter()
+                .map(|index| split_phrases[*index].clone())
+                .collect();
+
+            assert_eq!(recover_mnemonic_code(phrases).unwrap(), mnemonic_code)
+        }
+    }
+
+    #[test]
+    fn verification_fails_when_split_phrases_do_not_match() {
+        let mnemonic_code = "dance monitor unveil wood cycle uphold video elephant run unlock theme year divide text lyrics captain expose garlic bundle patrol praise net hour point";
+        let other_mnemonic_code = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+
+        let mut split_phrases = get_split_phrases(mnemonic_code.to_string()).unwrap();
+        let other_split_phrases = get_split_phrases(other_mnemonic_code.to_string()).unwrap();
+
+        // keep the set id, but replace the last share with one from another mnemonic
+        let other_share = other_split_phrases[4].splitn(4, ' ').last().unwrap();
+        let set_id = split_phrases[4]
+            .splitn(4, ' ')
+            .take(3)
+            .collect::<Vec<_>>()
+            .join(" ");
+        split_phrases[4] = format!("{} {}", set_id, other_share);
+
+        let entropy = Mnemonic::parse(mnemonic_code).unwrap().to_entropy();
+        let error =
+            verify::verify_split_phrases(&entropy, &split_phrases, &encoding::Words).unwrap_err();
+
+        assert_eq!(error, Error::FailedVerification(vec![0, 1, 4]));
+        assert!(error.to_string().contains("split phrases 1, 2, 5"));
+    }
+
+    #[test]
+    fn gets_all_combinations() {
+        let combinations = verify::combinations(5, 3);
+
+        assert_eq!(combinations.len(), 10);
+        assert_eq!(combinations[0], vec![0, 1, 2]);
+        assert_eq!(combinations[9], vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn split_and_recover_12_word_mnemonic() {
+        let mnemonic_code = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let split_phrases = get_split_phrases(mnemonic_code.to_string()).unwrap();
+
+        for split_phrase in &split_phrases {
+            assert_eq!(split_phrase.split(' ').count(), 16)
+        }
+
+        crate::validation::validate_split_phrases(split_phrases[2..].to_vec()).unwrap();
+        let recovered_mnemonic = recover_mnemonic_code(split_phrases[2..].to_vec()).unwrap();
+
+        assert_eq!(recovered_mnemonic, mnemonic_code)
+    }
+
     #[test]
     fn split_and_recover() {
         let mut rng = rand::thread_rng();
@@ -0,0 +1,301 @@
+//! Contains functions for recovering a mnemonic code with one or two missing words, by trying
+//! every word that gives the mnemonic code a valid checksum. If the fingerprint or an address of
+//! the mnemonic code is known, the results are narrowed down to the mnemonic code that matches it
+
+use crate::{bip32::ExtendedPrivateKey, Error};
+use bech32::FromBase32;
+use bip39::{Completions, Mnemonic};
+use bitcoin_hashes::{hash160, Hash};
+use std::thread;
+use zeroize::Zeroize;
+
+/// The maximum number of missing words that can be recovered
+pub const MAX_MISSING_WORDS: usize = bip39::recovery::MAX_NB_MISSING_WORDS;
+
+/// What marks a missing word in a mnemonic code, ex: `"abandon ? abandon ..."`
+pub const MISSING_WORD_MARKERS: [&str; 2] = ["?", "_"];
+
+/// The number of receive addresses of the first account that are checked against a known address
+pub const ADDRESS_GAP_LIMIT: u32 = 20;
+
+/// Something known about the mnemonic code that is being recovered, used to find the one correct
+/// mnemonic code among all the ones with a valid checksum
+#[derive(Debug, Clone, PartialEq)]
+pub enum Known {
+    /// The BIP32 master key fingerprint as 8 hex characters, see [crate::fingerprint]
+    Fingerprint(String),
+    /// One of the first [ADDRESS_GAP_LIMIT] receive addresses of the first account, either a
+    /// legacy (`1...`), nested segwit (`3...`) or native segwit (`bc1q...`) address
+    Address(String),
+}
+
+/// What the address of a mnemonic code is made of, the public key hash is compared to the hash in
+/// the known address, so the address is never fully created
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AddressScript {
+    P2pkh,
+    P2shP2wpkh,
+    P2wpkh,
+}
+
+impl AddressScript {
+    /// The BIP44, BIP49 and BIP84 derivation path purpose
+    fn purpose(self) -> u32 {
+        match self {
+            Self::P2pkh => 44,
+            Self::P2shP2wpkh => 49,
+            Self::P2wpkh => 84,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Matcher {
+    Any,
+    Fingerprint([u8; 4]),
+    Address(AddressScript, [u8; 20]),
+}
+
+impl Matcher {
+    fn new(known: Option<&Known>) -> Result<Self, Error> {
+        match known {
+            None => Ok(Self::Any),
+            Some(Known::Fingerprint(fingerprint)) => parse_fingerprint(fingerprint)
+                .map(Self::Fingerprint)
+                .ok_or_else(|| Error::InvalidFingerprint(fingerprint.to_string())),
+            Some(Known::Address(address)) => parse_address(address)
+                .map(|(script, hash)| Self::Address(script, hash))
+                .ok_or_else(|| Error::UnsupportedAddress(address.to_string())),
+        }
+    }
+
+    fn matches(&self, mnemonic: &Mnemonic) -> bool {
+        if let Self::Any = self {
+            return true;
+        }
+
+        let mut seed = mnemonic.to_seed("");
+        let master = ExtendedPrivateKey::master(&seed);
+        seed.zeroize();
+
+        let master = match master {
+            Some(master) => master,
+            None => return false,
+        };
+
+        match *self {
+            Self::Any => true,
+            Self::Fingerprint(fingerprint) => master.public_key().hash160()[0..4] == fingerprint,
+            Self::Address(script, hash) => {
+                // m/purpose'/0'/0'/0/index
+                let receive = master
+                    .derive_hardened_path(&[script.purpose(), 0, 0])
+                    .and_then(|account| account.public_key().derive(0));
+
+                let receive = match receive {
+                    Some(receive) => receive,
+                    None => return false,
+                };
+
+                (0..ADDRESS_GAP_LIMIT)
+                    .filter_map(|index| receive.derive(index))
+                    .map(|public_key| match script {
+                        AddressScript::P2shP2wpkh => {
+                            let mut redeem_script = vec![0x00, 0x14];
+                            redeem_script.extend_from_slice(&public_key.hash160());
+                            hash160::Hash::hash(&redeem_script)
+                        }
+                        _ => public_key.hash160(),
+                    })
+                    .any(|address_hash| address_hash[..] == hash[..])
+            }
+        }
+    }
+}
+
+fn parse_fingerprint(fingerprint: &str) -> Option<[u8; 4]> {
+    let fingerprint = fingerprint.trim();
+    if fingerprint.len() != 8 {
+        return None;
+    }
+
+    let mut bytes = [0u8; 4];
+    for (index, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(fingerprint.get(index * 2..index * 2 + 2)?, 16).ok()?;
+    }
+
+    Some(bytes)
+}
+
+fn parse_address(address: &str) -> Option<(AddressScript, [u8; 20])> {
+    let address = address.trim();
+
+    let (script, hash) = if address.to_lowercase().starts_with("bc1") {
+        let (hrp, data, _variant) = bech32::decode(address).ok()?;
+        let (version, program) = data.split_first()?;
+
+        if hrp != "bc" || version.to_u8() != 0 {
+            return None;
+        }
+
+        (AddressScript::P2wpkh, Vec::<u8>::from_base32(program).ok()?)
+    } else {
+        let decoded = bs58::decode(address).with_check(None).into_vec().ok()?;
+        let (version, hash) = decoded.split_first()?;
+
+        match version {
+            0x00 => (AddressScript::P2pkh, hash.to_vec()),
+            0x05 => (AddressScript::P2shP2wpkh, hash.to_vec()),
+            _ => return None,
+        }
+    };
+
+    if hash.len() != 20 {
+        return None;
+    }
+
+    let mut bytes = [0u8; 20];
+    bytes.copy_from_slice(&hash);
+    Some((script, bytes))
+}
+
+/// Splits a mnemonic code into its words, with `None` for each missing word
+pub fn parse_partial_mnemonic_code(mnemonic_code: &str) -> Vec<Option<&str>> {
+    mnemonic_code
+        .split_whitespace()
+        .map(|word| {
+            if MISSING_WORD_MARKERS.contains(&word) {
+                None
+            } else {
+                Some(word)
+            }
+        })
+        .collect()
+}
+
+/// When given a mnemonic code with one or two missing (`None`) words, returns every mnemonic code
+/// with a valid checksum that it could be. If the fingerprint or an address is known, only the
+/// mnemonic codes that match it are returned, which should be the one correct mnemonic code.
+///
+/// The work is shared across all the cores of the machine.
+pub fn find_missing_words(
+    words: &[Option<&str>],
+    known: Option<&Known>,
+) -> Result<Vec<String>, Error> {
+    let missing_words = words.iter().filter(|word| word.is_none()).count();
+    if missing_words > MAX_MISSING_WORDS {
+        return Err(Error::TooManyMissingWords(missing_words));
+    }
+
+    let matcher = Matcher::new(known)?;
+    let completions = Mnemonic::completions(words)?;
+
+    let threads = thread::available_parallelism()
+        .map(|threads| threads.get() as u64)
+        .unwrap_or(1);
+    let per_thread = completions.nb_candidates().div_ceil(threads);
+
+    let found = thread::scope(|scope| {
+        let handles = (0..threads)
+            .map(|thread| {
+                let completions = completions
+                    .clone()
+                    .with_range(thread * per_thread..(thread + 1) * per_thread);
+
+                scope.spawn(move || find_in(completions, matcher))
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("recovery thread panicked"))
+            .collect()
+    });
+
+    Ok(found)
+}
+
+fn find_in(completions: Completions, matcher: Matcher) -> Vec<String> {
+    completions
+        .filter(|mnemonic| matcher.matches(mnemonic))
+        .map(|mnemonic| mnemonic.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC_CODE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn finds_every_mnemonic_code_with_a_valid_checksum() {
+        let words = parse_partial_mnemonic_code(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon ?",
+        );
+        let found = find_missing_words(&words, None).unwrap();
+
+        assert_eq!(found.len(), 128);
+        assert!(found.contains(&MNEMONIC_CODE.to_string()));
+    }
+
+    #[test]
+    fn finds_the_mnemonic_code_with_the_known_fingerprint() {
+        let words = parse_partial_mnemonic_code(
+            "abandon abandon abandon abandon abandon _ abandon abandon abandon abandon abandon about",
+        );
+        let known = Known::Fingerprint("73c5da0a".to_string());
+
+        assert_eq!(
+            find_missing_words(&words, Some(&known)).unwrap(),
+            vec![MNEMONIC_CODE.to_string()]
+        );
+    }
+
+    #[test]
+    fn matches_every_address_type() {
+        let words = parse_partial_mnemonic_code(MNEMONIC_CODE);
+
+        for address in [
+            "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+            "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+        ] {
+            let known = Known::Address(address.to_string());
+
+            assert_eq!(
+                find_missing_words(&words, Some(&known)).unwrap(),
+                vec![MNEMONIC_CODE.to_string()]
+            );
+        }
+
+        let known = Known::Address("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2".to_string());
+        assert!(find_missing_words(&words, Some(&known)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn errors_on_too_many_missing_words() {
+        let words = parse_partial_mnemonic_code(
+            "? ? ? abandon abandon abandon abandon abandon abandon abandon abandon about",
+        );
+
+        assert_eq!(
+            find_missing_words(&words, None),
+            Err(Error::TooManyMissingWords(3))
+        );
+    }
+
+    #[test]
+    fn errors_on_invalid_known_values() {
+        let words = parse_partial_mnemonic_code(MNEMONIC_CODE);
+
+        assert_eq!(
+            find_missing_words(&words, Some(&Known::Fingerprint("73c5da".to_string()))),
+            Err(Error::InvalidFingerprint("73c5da".to_string()))
+        );
+        assert_eq!(
+            find_missing_words(&words, Some(&Known::Address("bc1qxyz".to_string()))),
+            Err(Error::UnsupportedAddress("bc1qxyz".to_string()))
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Contains the compact encoding of split phrases used in QR codes. Instead of the words, each
+//! word's 11 bit index in the word list is packed into bytes (most significant bit first, the last
+//! byte padded with zeros), the same way Compact SeedQR packs a mnemonic. A 28 word split phrase
+//! fits in 39 bytes, small enough for a QR code that is easy to scan.
+//!
+//! The words after the set id and share id of a split phrase are a BIP39 mnemonic, so they can
+//! also be shown as a SeedQR (see [bip39::Mnemonic::to_seed_qr]).
+
+use crate::wordlist::{English, Wordlist};
+use crate::Error;
+use bip39::{Language, Mnemonic};
+
+/// Packs the word indexes of a split phrase into bytes
+pub fn encode_compact(split_phrase: &str) -> Result<Vec<u8>, Error> {
+    let indexes = split_phrase
+        .split_whitespace()
+        .map(English::get_index)
+        .collect::<Result<Vec<usize>, _>>()?;
+
+    let mut bytes = vec![0u8; (indexes.len() * 11).div_ceil(8)];
+    for (word, index) in indexes.iter().enumerate() {
+        for bit in 0..11 {
+            if index & (1 << (10 - bit)) != 0 {
+                let position = word * 11 + bit;
+                bytes[position / 8] |= 1 << (7 - position % 8);
+            }
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Unpacks bytes created by [encode_compact] into a split phrase
+pub fn decode_compact(bytes: &[u8]) -> Result<String, Error> {
+    let word_count = bytes.len() * 8 / 11;
+
+    // only as many bytes as the words need, and the padding bits must be zeros
+    let padding_bits = bytes.len() * 8 - word_count * 11;
+    let padding = bytes
+        .last()
+        .map(|byte| byte & ((1u16 << padding_bits.min(8)) - 1) as u8)
+        .unwrap_or(0);
+
+    if word_count == 0 || padding_bits >= 8 || padding != 0 {
+        return Err(Error::InvalidCompactEncoding(bytes.len()));
+    }
+
+    (0..word_count)
+        .map(|word| {
+            let index = (0..11).fold(0, |index, bit| {
+                let position = word * 11 + bit;
+                let is_set = bytes[position / 8] & (1 << (7 - position % 8)) != 0;
+                (index << 1) | is_set as usize
+            });
+
+            Ok(English::get_word(index)?)
+        })
+        .collect::<Result<Vec<&str>, Error>>()
+        .map(|words| words.join(" "))
+}
+
+/// The two SeedQR formats, see [seed_qr_payload]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SeedQrFormat {
+    Standard,
+    Compact,
+}
+
+impl std::str::FromStr for SeedQrFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "standard" => Ok(Self::Standard),
+            "compact" => Ok(Self::Compact),
+            _ => Err(format!("unknown SeedQR format: {}", format)),
+        }
+    }
+}
+
+/// The data in the SeedQR of a 12 or 24 word mnemonic code, the 4 digit word indexes as ASCII for
+/// a standard SeedQR or the entropy for a CompactSeedQR
+pub fn seed_qr_payload(mnemonic_code: &str, format: SeedQrFormat) -> Result<Vec<u8>, Error> {
+    let mnemonic = Mnemonic::parse_in(Language::English, mnemonic_code)?;
+
+    Ok(match format {
+        SeedQrFormat::Standard => mnemonic.to_seed_qr()?.into_bytes(),
+        SeedQrFormat::Compact => mnemonic.to_compact_seed_qr()?,
+    })
+}
+
+/// The BIP39 mnemonic in a split phrase, the words after the 3 word set id and 1 word share id
+pub fn split_phrase_body(split_phrase: &str) -> Result<String, Error> {
+    let words = split_phrase.split_whitespace().collect::<Vec<&str>>();
+    if words.len() < 4 {
+        return Err(Error::PhraseTooShort(words.len()));
+    }
+
+    Ok(words[4..].join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPLIT_PHRASE: &str = "embody fog drop about embrace visa adapt winner wine dash fabric snack drip auction deputy visit shift animal various bread country lecture assist marriage merit goat gravity glove";
+
+    #[test]
+    fn packs_word_indexes_into_bytes() {
+        // abandon (0), ability (1), zoo (2047)
+        assert_eq!(
+            encode_compact("abandon ability zoo").unwrap(),
+            vec![
+                0b0000_0000,
+                0b0000_0000,
+                0b0000_0111,
+                0b1111_1111,
+                0b1000_0000
+            ]
+        );
+
+        let bytes = encode_compact(SPLIT_PHRASE).unwrap();
+        assert_eq!(bytes.len(), 39);
+        assert_eq!(decode_compact(&bytes).unwrap(), SPLIT_PHRASE);
+    }
+
+    #[test]
+    fn rejects_invalid_bytes() {
+        let mut bytes = encode_compact(SPLIT_PHRASE).unwrap();
+
+        // 28 words leave 4 bits of padding, which must be zeros
+        bytes[38] |= 1;
+        assert_eq!(
+            decode_compact(&bytes),
+            Err(Error::InvalidCompactEncoding(39))
+        );
+
+        // an extra byte the words don't need
+        assert_eq!(
+            decode_compact(&[0, 0, 0, 0]),
+            Err(Error::InvalidCompactEncoding(4))
+        );
+        assert_eq!(decode_compact(&[]), Err(Error::InvalidCompactEncoding(0)));
+    }
+
+    #[test]
+    fn creates_seed_qr_payloads_of_the_split_phrase_mnemonic() {
+        let body = split_phrase_body(SPLIT_PHRASE).unwrap();
+        assert!(body.starts_with("embrace visa adapt"));
+
+        let standard = seed_qr_payload(&body, SeedQrFormat::Standard).unwrap();
+        assert_eq!(standard.len(), 96);
+        assert!(standard.iter().all(u8::is_ascii_digit));
+        assert_eq!(
+            seed_qr_payload(&body, SeedQrFormat::Compact).unwrap().len(),
+            32
+        );
+
+        assert_eq!(
+            split_phrase_body("embody fog drop"),
+            Err(Error::PhraseTooShort(3))
+        );
+    }
+}
@@ -0,0 +1,352 @@
+//! Contains functions for splitting any secret (passwords, private keys, etc...) into secret phrases,
+//! not just BIP39 mnemonic codes.
+//!
+//! Each secret phrase is made up of a 3 word set id, followed by the share encoded as BIP39 words,
+//! 11 bits per word. The encoded share is:
+//!
+//! | threshold (1 byte) | share id (1 byte) | secret length (2 bytes) | share | checksum (4 bytes) |
+//!
+//! The checksum is the first 4 bytes of the SHA256 of the set id and everything before it.
+
+use crate::shamir::SecretData;
+use crate::wordlist::{English, Wordlist};
+use crate::Error;
+use bitcoin_hashes::{sha256, Hash, HashEngine};
+use rand::Rng;
+use zeroize::Zeroize;
+
+const HEADER_LENGTH: usize = 4;
+const CHECKSUM_LENGTH: usize = 4;
+
+/// When given any secret, returns `number_of_shares` secret phrases, any `threshold` of these can
+/// later be used to recover the secret
+pub fn split_secret(
+    secret: &[u8],
+    threshold: u8,
+    number_of_shares: u8,
+) -> Result<Vec<String>, Error> {
+    if threshold < 2 || threshold > number_of_shares {
+        return Err(Error::InvalidThreshold {
+            threshold,
+            number_of_shares,
+        });
+    }
+
+    if secret.is_empty() || secret.len() > u16::MAX as usize {
+        return Err(Error::InvalidSecretLength(secret.len()));
+    }
+
+    let set_id = random_set_id();
+    let secret_data = SecretData::with_secret(secret, threshold);
+
+    let mut secret_phrases = Vec::with_capacity(number_of_shares as usize);
+    for id in 1..=number_of_shares {
+        let mut share = secret_data.get_share(id)?;
+        let secret_phrase = share_to_secret_phrase(&set_id, threshold, &share);
+        share.zeroize();
+
+        secret_phrases.push(secret_phrase?)
+    }
+
+    Ok(secret_phrases)
+}
+
+/// When given at least `threshold` secret phrases from the same set, returns the original secret
+pub fn recover_secret(mut secret_phrases: Vec<String>) -> Result<Vec<u8>, Error> {
+    let decoded = decode_secret_phrases(&secret_phrases);
+    secret_phrases.zeroize();
+
+    let (threshold, shares) = decoded?;
+    if shares.len() < threshold as usize {
+        return Err(Error::NotEnoughShares {
+            gave: shares.len(),
+            expected: threshold,
+        });
+    }
+
+    Ok(SecretData::recover_secret(threshold, shares)?)
+}
+
+pub(crate) fn random_set_id() -> [u16; 3] {
+    let mut rng = rand::thread_rng();
+
+    [
+        rng.gen_range(0..2048),
+        rng.gen_range(0..2048),
+        rng.gen_range(0..2048),
+    ]
+}
+
+// returns the secret phrase for a share (with the share id as the first byte)
+pub(crate) fn share_to_secret_phrase(
+    set_id: &[u16; 3],
+    threshold: u8,
+    share: &[u8],
+) -> Result<String, Error> {
+    let mut share_bytes = encode_share(set_id, threshold, share);
+
+    let words = set_id
+        .iter()
+        .map(|index| *index as usize)
+        .chain(bytes_to_indexes(&share_bytes))
+        .map(English::get_word)
+        .collect::<Result<Vec<&'static str>, _>>();
+    share_bytes.zeroize();
+
+    Ok(words?.join(" "))
+}
+
+// checks the secret phrases are all from the same set, returns their threshold and shares (with the
+// share id as the first byte)
+pub(crate) fn decode_secret_phrases(
+    secret_phrases: &[String],
+) -> Result<(u8, Vec<Vec<u8>>), Error> {
+    let mut set_id = None;
+    let mut threshold = None;
+    let mut shares = Vec::with_capacity(secret_phrases.len());
+
+    for (index, secret_phrase) in secret_phrases.iter().enumerate() {
+        let (phrase_set_id, phrase_threshold, share) = decode_secret_phrase(index, secret_phrase)?;
+
+        if *set_id.get_or_insert(phrase_set_id) != phrase_set_id {
+            return Err(Error::MismatchedSet(
+                secret_phrases[0]
+                    .split_whitespace()
+                    .take(3)
+                    .collect::<Vec<&str>>()
+                    .join(" "),
+                secret_phrase.clone(),
+            ));
+        }
+
+        if *threshold.get_or_insert(phrase_threshold) != phrase_threshold {
+            return Err(Error::InvalidSecretPhrase(
+                index,
+                "the threshold doesn't match the other secret phrases",
+            ));
+        }
+
+        shares.push(share)
+    }
+
+    Ok((threshold.unwrap_or(2), shares))
+}
+
+// returns the share bytes prefixed with the header and followed by the checksum
+fn encode_share(set_id: &[u16; 3], threshold: u8, share: &[u8]) -> Vec<u8> {
+    let secret_length = (share.len() - 1) as u16;
+
+    let mut bytes = Vec::with_capacity(HEADER_LENGTH + share.len() + CHECKSUM_LENGTH);
+    bytes.push(threshold);
+    bytes.extend_from_slice(share);
+    bytes.insert(2, (secret_length >> 8) as u8);
+    bytes.insert(3, secret_length as u8);
+
+    let checksum = checksum(set_id, &bytes);
+    bytes.extend_from_slice(&checksum);
+
+    bytes
+}
+
+// returns the set id, threshold and share (with the share id as the first byte)
+fn decode_secret_phrase(
+    index: usize,
+    secret_phrase: &str,
+) -> Result<([u16; 3], u8, Vec<u8>), Error> {
+    let indexes = secret_phrase
+        .split_whitespace()
+        .map(English::get_index)
+        .collect::<Result<Vec<usize>, _>>()?;
+
+    if indexes.len() < 3 {
+        return Err(Error::InvalidSecretPhrase(
+            index,
+            "the secret phrase is too short",
+        ));
+    }
+
+    let set_id = [indexes[0] as u16, indexes[1] as u16, indexes[2] as u16];
+    let mut bytes = indexes_to_bytes(&indexes[3..]);
+
+    if bytes.len() < HEADER_LENGTH + CHECKSUM_LENGTH {
+        return Err(Error::InvalidSecretPhrase(
+            index,
+            "the secret phrase is too short",
+        ));
+    }
+
+    let secret_length = ((bytes[2] as usize) << 8) | bytes[3] as usize;
+    let encoded_length = HEADER_LENGTH + secret_length + CHECKSUM_LENGTH;
+
+    // anything after the checksum is padding, and must be empty
+    if bytes.len() < encoded_length || bytes[encoded_length..].iter().any(|byte| *byte != 0) {
+        bytes.zeroize();
+        return Err(Error::InvalidSecretPhrase(
+            index,
+            "the secret phrase doesn't match its secret length",
+        ));
+    }
+
+    let (data, rest) = bytes.split_at(HEADER_LENGTH + secret_length);
+    if checksum(&set_id, data)[..] != rest[..CHECKSUM_LENGTH] {
+        bytes.zeroize();
+        return Err(Error::SecretPhraseChecksum(index));
+    }
+
+    let threshold = data[0];
+    let mut share = Vec::with_capacity(secret_length + 1);
+    share.push(data[1]);
+    share.extend_from_slice(&data[HEADER_LENGTH..]);
+    bytes.zeroize();
+
+    Ok((set_id, threshold, share))
+}
+
+fn checksum(set_id: &[u16; 3], bytes: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+    let mut engine = sha256::Hash::engine();
+    for index in set_id {
+        engine.input(&index.to_be_bytes());
+    }
+    engine.input(bytes);
+
+    let hash = sha256::Hash::from_engine(engine);
+    let mut checksum = [0; CHECKSUM_LENGTH];
+    checksum.copy_from_slice(&hash[0..CHECKSUM_LENGTH]);
+    checksum
+}
+
+// splits the bytes into 11 bit word indexes, the last word is padded with 0 bits
+fn bytes_to_indexes(bytes: &[u8]) -> Vec<usize> {
+    let mut indexes = Vec::with_capacity((bytes.len() * 8).div_ceil(11));
+    let mut accumulator: usize = 0;
+    let mut bits = 0;
+
+    for byte in bytes {
+        accumulator = (accumulator << 8) | *byte as usize;
+        bits += 8;
+
+        while bits >= 11 {
+            bits -= 11;
+            indexes.push((accumulator >> bits) & 0x7FF);
+        }
+    }
+
+    if bits > 0 {
+        indexes.push((accumulator << (11 - bits)) & 0x7FF);
+    }
+
+    indexes
+}
+
+// joins the 11 bit word indexes back into bytes, dropping any leftover padding bits
+fn indexes_to_bytes(indexes: &[usize]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(indexes.len() * 11 / 8);
+    let mut accumulator: usize = 0;
+    let mut bits = 0;
+
+    for index in indexes {
+        accumulator = (accumulator << 11) | (*index & 0x7FF);
+        bits += 11;
+
+        while bits >= 8 {
+            bits -= 8;
+            bytes.push((accumulator >> bits) as u8);
+        }
+    }
+
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::seq::SliceRandom;
+
+    #[test]
+    fn converts_bytes_to_indexes_and_back() {
+        let bytes = vec![0, 255, 18, 52, 86, 120, 154, 188, 222, 240, 1];
+        let indexes = bytes_to_indexes(&bytes);
+
+        assert_eq!(indexes.len(), 8);
+        assert_eq!(indexes_to_bytes(&indexes), bytes);
+    }
+
+    #[test]
+    fn split_and_recover_secret() {
+        let mut rng = rand::thread_rng();
+        let secret = "correct horse battery staple".as_bytes();
+
+        let mut secret_phrases = split_secret(secret, 3, 5).unwrap();
+        assert_eq!(secret_phrases.len(), 5);
+
+        secret_phrases.shuffle(&mut rng);
+        secret_phrases.truncate(3);
+
+        assert_eq!(recover_secret(secret_phrases).unwrap(), secret);
+    }
+
+    #[test]
+    fn split_and_recover_with_other_thresholds() {
+        let secret = [7u8; 33];
+
+        let secret_phrases = split_secret(&secret, 2, 3).unwrap();
+        let recovered = recover_secret(secret_phrases[1..].to_vec()).unwrap();
+
+        assert_eq!(recovered, secret);
+    }
+
+    #[test]
+    fn errors_when_not_enough_secret_phrases() {
+        let secret_phrases = split_secret(b"hunter2", 4, 6).unwrap();
+
+        assert_eq!(
+            recover_secret(secret_phrases[0..3].to_vec()),
+            Err(Error::NotEnoughShares {
+                gave: 3,
+                expected: 4
+            })
+        );
+    }
+
+    #[test]
+    fn errors_on_invalid_threshold() {
+        assert_eq!(
+            split_secret(b"hunter2", 6, 5),
+            Err(Error::InvalidThreshold {
+                threshold: 6,
+                number_of_shares: 5
+            })
+        );
+        assert!(split_secret(b"hunter2", 1, 5).is_err());
+        assert_eq!(split_secret(b"", 3, 5), Err(Error::InvalidSecretLength(0)));
+    }
+
+    #[test]
+    fn errors_on_bad_checksum() {
+        let mut secret_phrases = split_secret(b"hunter2", 3, 5).unwrap();
+
+        // change a word in the middle of the share
+        let mut words: Vec<&str> = secret_phrases[1].split(' ').collect();
+        words[8] = if words[8] == "zoo" { "abandon" } else { "zoo" };
+        secret_phrases[1] = words.join(" ");
+
+        assert_eq!(
+            recover_secret(secret_phrases),
+            Err(Error::SecretPhraseChecksum(1))
+        );
+    }
+
+    #[test]
+    fn errors_on_mismatched_sets() {
+        let secret_phrases = split_secret(b"hunter2", 2, 3).unwrap();
+        let other_secret_phrases = split_secret(b"hunter2", 2, 3).unwrap();
+
+        let error = recover_secret(vec![
+            secret_phrases[0].clone(),
+            other_secret_phrases[1].clone(),
+        ])
+        .unwrap_err();
+
+        assert!(matches!(error, Error::MismatchedSet(_, _)));
+    }
+}
@@ -0,0 +1,188 @@
+//! Contains Seed XOR, Coldcard's scheme for splitting a mnemonic into N parts that are all needed
+//! to recover it. Every part is a valid mnemonic of the same length as the original, so each part
+//! can be used as a decoy wallet. The entropy of the mnemonic is the XOR of the entropy of all its
+//! parts, which makes it easy to check by hand.
+//! See: https://github.com/Coldcard/firmware/blob/master/docs/seed-xor.md
+
+use bip39::{Language, Mnemonic};
+use rand::{thread_rng, RngCore};
+use thiserror::Error;
+use zeroize::Zeroize;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SeedXorError {
+    #[error("invalid number of parts, a mnemonic must be split into at least 2 parts, found: {0}")]
+    InvalidPartCount(usize),
+
+    #[error(
+        "not enough Seed XOR parts, every part is needed and at least 2 were expected, found: {0}"
+    )]
+    NotEnoughParts(usize),
+
+    #[error("all Seed XOR parts must have the same number of words")]
+    MismatchedLengths,
+
+    #[error("the Seed XOR parts didn't recover the mnemonic")]
+    FailedVerification,
+}
+
+fn xor_into(entropy: &mut [u8], part: &[u8]) {
+    for (byte, part_byte) in entropy.iter_mut().zip(part) {
+        *byte ^= part_byte
+    }
+}
+
+/// Splits a mnemonic code into `parts` mnemonic codes that XOR to it. All but the last part are
+/// random, the last part is the XOR of the mnemonic and the random parts
+pub fn split_mnemonic_code(mnemonic_code: &str, parts: usize) -> Result<Vec<String>, crate::Error> {
+    if parts < 2 {
+        return Err(SeedXorError::InvalidPartCount(parts).into());
+    }
+
+    let mnemonic = Mnemonic::parse_in(Language::English, mnemonic_code)?;
+    let mut entropy = mnemonic.to_entropy();
+    let mut last_part = entropy.clone();
+
+    let mut rng = thread_rng();
+    let mut mnemonic_codes = Vec::with_capacity(parts);
+
+    for _ in 1..parts {
+        let mut part = vec![0; entropy.len()];
+        rng.fill_bytes(&mut part);
+
+        xor_into(&mut last_part, &part);
+        mnemonic_codes.push(Mnemonic::from_entropy(&part)?.to_string());
+        part.zeroize();
+    }
+
+    mnemonic_codes.push(Mnemonic::from_entropy(&last_part)?.to_string());
+    last_part.zeroize();
+
+    let verified = combine_entropy(&mnemonic_codes).map(|mut recovered| {
+        let verified = recovered == entropy;
+        recovered.zeroize();
+        verified
+    });
+    entropy.zeroize();
+
+    if !verified? {
+        return Err(SeedXorError::FailedVerification.into());
+    }
+
+    Ok(mnemonic_codes)
+}
+
+fn combine_entropy(mnemonic_codes: &[String]) -> Result<Vec<u8>, crate::Error> {
+    if mnemonic_codes.len() < 2 {
+        return Err(SeedXorError::NotEnoughParts(mnemonic_codes.len()).into());
+    }
+
+    let mut entropy = Vec::new();
+
+    for (index, mnemonic_code) in mnemonic_codes.iter().enumerate() {
+        let mut part = Mnemonic::parse_in(Language::English, mnemonic_code)?.to_entropy();
+
+        if index == 0 {
+            entropy = vec![0; part.len()];
+        } else if part.len() != entropy.len() {
+            entropy.zeroize();
+            part.zeroize();
+            return Err(SeedXorError::MismatchedLengths.into());
+        }
+
+        xor_into(&mut entropy, &part);
+        part.zeroize();
+    }
+
+    Ok(entropy)
+}
+
+/// Recovers a mnemonic code from all of its Seed XOR parts, in any order
+pub fn recover_mnemonic_code(mnemonic_codes: &[String]) -> Result<String, crate::Error> {
+    let mut entropy = combine_entropy(mnemonic_codes)?;
+    let mnemonic = Mnemonic::from_entropy(&entropy);
+    entropy.zeroize();
+
+    Ok(mnemonic?.to_string())
+}
+
+/// True when every phrase is a valid BIP39 mnemonic code, which split phrases never are, so
+/// Seed XOR parts can be told apart from split phrases
+pub fn are_parts(phrases: &[String]) -> bool {
+    phrases.len() >= 2
+        && phrases
+            .iter()
+            .all(|phrase| Mnemonic::parse_in(Language::English, phrase).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC_CODE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+
+    fn parts(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(ToString::to_string).collect()
+    }
+
+    // the example from Coldcard's Seed XOR docs
+    #[test]
+    fn recovers_the_coldcard_example() {
+        let coldcard_parts = parts(&[
+            "romance wink lottery autumn shop bring dawn tongue range crater truth ability miss spice fitness easy legal release recall obey exchange recycle dragon room",
+            "lion misery divide hurry latin fluid camp advance illegal lab pyramid unaware eager fringe sick camera series noodle toy crowd jeans select depth lounge",
+            "vault nominee cradle silk own frown throw leg cactus recall talent worry gadget surface shy planet purpose coffee drip few seven term squeeze educate",
+        ]);
+        let mnemonic_code = "silent toe meat possible chair blossom wait occur this worth option bag nurse find fish scene bench asthma bike wage world quit primary indoor";
+
+        assert!(are_parts(&coldcard_parts));
+        assert_eq!(
+            recover_mnemonic_code(&coldcard_parts).unwrap(),
+            mnemonic_code
+        );
+
+        let reordered = vec![
+            coldcard_parts[2].clone(),
+            coldcard_parts[0].clone(),
+            coldcard_parts[1].clone(),
+        ];
+        assert_eq!(recover_mnemonic_code(&reordered).unwrap(), mnemonic_code);
+
+        assert_ne!(
+            recover_mnemonic_code(&coldcard_parts[..2]).unwrap(),
+            mnemonic_code
+        );
+    }
+
+    #[test]
+    fn splits_and_recovers_a_mnemonic_code() {
+        let xor_parts = split_mnemonic_code(MNEMONIC_CODE, 4).unwrap();
+
+        assert_eq!(xor_parts.len(), 4);
+        assert!(xor_parts.iter().all(|part| part.split(' ').count() == 24));
+        assert!(are_parts(&xor_parts));
+        assert_eq!(recover_mnemonic_code(&xor_parts).unwrap(), MNEMONIC_CODE);
+    }
+
+    #[test]
+    fn rejects_invalid_parts() {
+        assert_eq!(
+            split_mnemonic_code(MNEMONIC_CODE, 1),
+            Err(SeedXorError::InvalidPartCount(1).into())
+        );
+        assert_eq!(
+            recover_mnemonic_code(&parts(&[MNEMONIC_CODE])),
+            Err(SeedXorError::NotEnoughParts(1).into())
+        );
+        assert_eq!(
+            recover_mnemonic_code(&parts(&[
+                MNEMONIC_CODE,
+                "vault nominee cradle silk own frown throw leg cactus recall talent wisdom"
+            ])),
+            Err(SeedXorError::MismatchedLengths.into())
+        );
+
+        let split_phrase = "embody fog drop about embrace visa adapt winner wine dash fabric snack drip auction deputy visit shift animal various bread country lecture assist marriage merit goat gravity glove";
+        assert!(!are_parts(&parts(&[MNEMONIC_CODE, split_phrase])));
+    }
+}
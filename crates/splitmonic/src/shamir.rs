@@ -7,7 +7,7 @@ use zeroize::Zeroize;
 
 #[cfg(test)]
 mod tests {
-    use super::SecretData;
+    use super::{SecretData, ShamirError};
     #[test]
     fn it_works() {}
 
@@ -63,7 +63,9 @@ mod tests {
         fn try_recover(n: u8, shares: &[Vec<u8>]) -> Option<String> {
             let shares = shares.iter().take(n as usize).cloned().collect::<Vec<_>>();
 
-            SecretData::recover_secret(n, shares).and_then(|bytes| String::from_utf8(bytes).ok())
+            SecretData::recover_secret(n, shares)
+                .ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok())
         }
 
         let secret_data = SecretData::with_secret("Hello World!".as_bytes(), 5);
@@ -81,6 +83,31 @@ mod tests {
 
         let recovered = try_recover(3, &shares);
         assert!(recovered.is_none());
+
+        assert_eq!(
+            SecretData::recover_secret(3, shares[..2].to_vec()),
+            Err(ShamirError::NotEnoughShares {
+                gave: 2,
+                expected: 3
+            })
+        );
+    }
+
+    #[test]
+    fn it_rejects_duplicate_and_mismatched_shares() {
+        let secret_data = SecretData::with_secret("Hello World!".as_bytes(), 2);
+
+        let s1 = secret_data.get_share(1).unwrap();
+        let s2 = secret_data.get_share(2).unwrap();
+
+        assert_eq!(
+            SecretData::recover_secret(2, vec![s2.clone(), s1.clone(), s2.clone()]),
+            Err(ShamirError::DuplicateShare(2))
+        );
+        assert_eq!(
+            SecretData::recover_secret(2, vec![s1, s2[..5].to_vec()]),
+            Err(ShamirError::MismatchedShareLengths)
+        );
     }
 }
 
@@ -94,6 +121,18 @@ pub struct SecretData {
 pub enum ShamirError {
     #[error("Unable to get shamir share")]
     InvalidShareCount,
+
+    #[error("not enough shares, gave {gave:?}, expected {expected:?}")]
+    NotEnoughShares { gave: usize, expected: u8 },
+
+    #[error("share {0} was given more than once, each share must be a different share of the set")]
+    DuplicateShare(u8),
+
+    #[error("the shares are different lengths, all the shares of a set are the same length")]
+    MismatchedShareLengths,
+
+    #[error("unable to recover the secret from the shares")]
+    UnableToRecoverSecret,
 }
 
 impl SecretData {
@@ -129,22 +168,22 @@ impl SecretData {
         Ok(share_bytes)
     }
 
-    pub fn recover_secret(threshold: u8, shares: Vec<Vec<u8>>) -> Option<Vec<u8>> {
-        if threshold as usize > shares.len() {
-            println!("Number of shares is below the threshold");
-            return None;
+    pub fn recover_secret(threshold: u8, shares: Vec<Vec<u8>>) -> Result<Vec<u8>, ShamirError> {
+        if threshold as usize > shares.len() || shares.is_empty() {
+            return Err(ShamirError::NotEnoughShares {
+                gave: shares.len(),
+                expected: threshold,
+            });
         }
         let mut xs: Vec<u8> = vec![];
 
         for share in shares.iter() {
-            if xs.contains(&share[0]) {
-                println!("Multiple shares with the same first byte");
-                return None;
+            if share.is_empty() || share.len() != shares[0].len() {
+                return Err(ShamirError::MismatchedShareLengths);
             }
 
-            if share.len() != shares[0].len() {
-                println!("Shares have different lengths");
-                return None;
+            if xs.contains(&share[0]) {
+                return Err(ShamirError::DuplicateShare(share[0]));
             }
 
             xs.push(share[0].to_owned());
@@ -160,7 +199,7 @@ impl SecretData {
             }
 
             match SecretData::full_lagrange(&xs, &fxs) {
-                None => return None,
+                None => return Err(ShamirError::UnableToRecoverSecret),
                 Some(resulting_poly) => {
                     my_coefficients.push(String::from_utf8_lossy(&resulting_poly[..]).to_string());
                     my_secret_data.push(resulting_poly[0]);
@@ -168,7 +207,7 @@ impl SecretData {
             }
         }
 
-        Some(my_secret_data)
+        Ok(my_secret_data)
     }
 
     fn accumulate_share_bytes(id: u8, coefficient_bytes: Vec<u8>) -> Result<u8, ShamirError> {
@@ -0,0 +1,794 @@
+//! Contains the writer and parser for files holding a split phrase (or a secret phrase). These
+//! formats are supported:
+//!
+//! - the versioned format splitmonic writes, a `#` header with the set id, share number,
+//!   threshold, language, creation date and tool version, then one `1: word` per line, then a
+//!   `# checksum:` line
+//! - the legacy numbered format older versions of splitmonic saved, just the `1: word` lines
+//! - plain text, words separated by spaces, commas or new lines
+//! - JSON, either a string, an array of words, or an object with a `split_phrase`,
+//!   `secret_phrase` or `words` field
+//!
+//! Words are read in any case and lowercased, word numbers and SSKR or codex32 shares are kept as
+//! they are written, anything else is reported as a malformed word. The split phrases of the other
+//! encodings (hex, base32, base58...) are read with [read_encoded_share_file], which keeps every
+//! token as it is written, so the selected encoding can read them.
+//!
+//! Files with more than one share in them are detected, so they are never merged into one
+//! nonsense phrase. In plain text files shares are separated by a blank line, in numbered files
+//! the numbering starting over at 1 starts a new share.
+
+use crate::wordlist::{English, Wordlist};
+use crate::{NUMBER_OF_SHARES, THRESHOLD};
+use bitcoin_hashes::{sha256, Hash};
+use serde_json::Value;
+use std::path::Path;
+use thiserror::Error;
+
+/// The version of the share file format written by [share_file_text]
+pub const SHARE_FILE_VERSION: u8 = 1;
+
+/// The first line of a versioned share file
+const SHARE_FILE_TITLE: &str = "# splitmonic split phrase";
+
+/// The JSON fields a split phrase can be in
+const JSON_FIELDS: [&str; 4] = ["split_phrase", "secret_phrase", "phrase", "words"];
+
+/// What is wrong with the contents of a share file
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum ShareFileProblem {
+    #[error("no words found")]
+    Empty,
+
+    #[error("line {line}: word {word} is not a word, a word number or a share, it contains something other than letters")]
+    NotAWord { line: usize, word: usize },
+
+    #[error("line {line}: expected word number {expected}, found {found}")]
+    OutOfOrder {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("line {line}: expected a numbered word, like `1: word`")]
+    NotNumbered { line: usize },
+
+    #[error("line {line}: expected a single word after the word number")]
+    NotOneWord { line: usize },
+
+    #[error("invalid JSON, {0}")]
+    Json(String),
+
+    #[error("unsupported share file format version {0}, this version of splitmonic supports up to version {}", SHARE_FILE_VERSION)]
+    UnsupportedVersion(String),
+
+    #[error("the header is missing the `{0}` field")]
+    MissingHeaderField(&'static str),
+
+    #[error("the header field `{0}` is invalid")]
+    InvalidHeaderField(&'static str),
+
+    #[error("the header field `{0}` doesn't match the words")]
+    HeaderMismatch(&'static str),
+
+    #[error("the checksum doesn't match the words, one or more words are wrong or out of order")]
+    ChecksumMismatch,
+}
+
+/// The header of a versioned share file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShareFileHeader {
+    pub version: u8,
+
+    /// the first 3 words, shared by all the phrases in the set
+    pub set_id: String,
+
+    /// the share number of this split phrase in the set (1 to 5)
+    pub share_index: u8,
+
+    /// how many split phrases were created in this set
+    pub number_of_shares: u8,
+
+    /// how many split phrases are needed to recover the mnemonic
+    pub threshold: u8,
+
+    /// the language of the word list the split phrase uses
+    pub language: String,
+
+    /// when the split phrase was created, as written by the tool that created it
+    pub created: Option<String>,
+
+    /// the name and version of the tool that created the split phrase
+    pub tool: Option<String>,
+}
+
+impl ShareFileHeader {
+    /// Creates the header for a split phrase, `created` and `tool` are only informational
+    pub fn for_split_phrase(
+        split_phrase: &str,
+        created: Option<String>,
+        tool: Option<String>,
+    ) -> Result<Self, crate::Error> {
+        let info = crate::inspect::inspect_split_phrase(split_phrase)?;
+
+        Ok(Self {
+            version: SHARE_FILE_VERSION,
+            set_id: info.set_id,
+            share_index: info.share_index,
+            number_of_shares: info.number_of_shares,
+            threshold: info.threshold,
+            language: "english".to_string(),
+            created,
+            tool,
+        })
+    }
+
+    /// Reads the header out of the `#` lines of a share file, returns `None` for files without a
+    /// header (legacy files)
+    pub fn parse(contents: &str) -> Result<Option<Self>, ShareFileProblem> {
+        let fields = header_fields(contents);
+        let field = |name: &'static str| {
+            fields
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+                .ok_or(ShareFileProblem::MissingHeaderField(name))
+        };
+
+        let version = match field("format") {
+            Ok(version) => version,
+            Err(_) => return Ok(None),
+        };
+
+        let version = match version.parse::<u8>() {
+            Ok(parsed) if (1..=SHARE_FILE_VERSION).contains(&parsed) => parsed,
+            _ => return Err(ShareFileProblem::UnsupportedVersion(version.to_string())),
+        };
+
+        let (share_index, number_of_shares) = field("share")?
+            .split_once(" of ")
+            .and_then(|(index, total)| {
+                Some((index.trim().parse().ok()?, total.trim().parse().ok()?))
+            })
+            .ok_or(ShareFileProblem::InvalidHeaderField("share"))?;
+
+        let threshold = field("threshold")?
+            .parse()
+            .map_err(|_| ShareFileProblem::InvalidHeaderField("threshold"))?;
+
+        Ok(Some(Self {
+            version,
+            set_id: field("set id")?.to_string(),
+            share_index,
+            number_of_shares,
+            threshold,
+            language: field("language").unwrap_or("english").to_string(),
+            created: field("created").ok().map(ToString::to_string),
+            tool: field("tool").ok().map(ToString::to_string),
+        }))
+    }
+
+    /// Checks the header and the checksum line match the words of the share
+    fn verify(&self, contents: &str, words: &[String]) -> Result<(), ShareFileProblem> {
+        let checksum = header_fields(contents)
+            .into_iter()
+            .find(|(key, _)| key == "checksum")
+            .map(|(_, checksum)| checksum)
+            .ok_or(ShareFileProblem::MissingHeaderField("checksum"))?;
+
+        if checksum != words_checksum(words) {
+            return Err(ShareFileProblem::ChecksumMismatch);
+        }
+
+        if self.language != "english" {
+            return Err(ShareFileProblem::InvalidHeaderField("language"));
+        }
+
+        if self.threshold != THRESHOLD {
+            return Err(ShareFileProblem::InvalidHeaderField("threshold"));
+        }
+
+        if self.number_of_shares != NUMBER_OF_SHARES {
+            return Err(ShareFileProblem::InvalidHeaderField("shares"));
+        }
+
+        if words.len() < 4 || words[0..3].join(" ") != self.set_id {
+            return Err(ShareFileProblem::HeaderMismatch("set id"));
+        }
+
+        match English::get_index(&words[3]) {
+            Ok(share_index) if share_index == self.share_index as usize => Ok(()),
+            _ => Err(ShareFileProblem::HeaderMismatch("share")),
+        }
+    }
+}
+
+/// The `key: value` fields in the `#` lines of a share file
+fn header_fields(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix('#'))
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_lowercase(), value.trim().to_string()))
+        .collect()
+}
+
+fn is_comment(line: &str) -> bool {
+    line.trim_start().starts_with('#')
+}
+
+/// The first 4 bytes of the SHA256 of the words joined by spaces, as hex. Used on the checksum line
+/// of share files, and anywhere else the words are copied to (ex: printed cards)
+pub fn words_checksum<S: AsRef<str>>(words: &[S]) -> String {
+    let words = words.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
+    let hash = sha256::Hash::hash(words.join(" ").as_bytes());
+
+    hash[0..4]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// The text of a versioned share file, the header, then one numbered word per line, then the
+/// checksum
+pub fn share_file_text(split_phrase: &str, header: &ShareFileHeader) -> String {
+    let words = split_phrase.split_whitespace().collect::<Vec<&str>>();
+
+    let mut lines = vec![
+        SHARE_FILE_TITLE.to_string(),
+        format!("# format: {}", header.version),
+        format!("# set id: {}", header.set_id),
+        format!(
+            "# share: {} of {}",
+            header.share_index, header.number_of_shares
+        ),
+        format!("# threshold: {}", header.threshold),
+        format!("# language: {}", header.language),
+    ];
+
+    if let Some(created) = &header.created {
+        lines.push(format!("# created: {}", created));
+    }
+
+    if let Some(tool) = &header.tool {
+        lines.push(format!("# tool: {}", tool));
+    }
+
+    lines.extend(
+        words
+            .iter()
+            .enumerate()
+            .map(|(index, word)| format!("{}: {}", index + 1, word)),
+    );
+    lines.push(format!("# checksum: {}", words_checksum(&words)));
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum ShareFileError {
+    #[error("unable to read file {path}: {reason}")]
+    Unreadable { path: String, reason: String },
+
+    #[error("invalid share file {path}, {problem}")]
+    Invalid {
+        path: String,
+        problem: ShareFileProblem,
+    },
+
+    #[error("file {path} contains {count} shares, only one share per file is supported")]
+    MultipleShares { path: String, count: usize },
+}
+
+/// How the tokens of a share file are read
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tokens {
+    /// words, word numbers and SSKR or codex32 shares, anything else is a malformed word
+    Words,
+
+    /// any token made of letters, digits and `:/-=`, for the split phrases of the other encodings
+    Encoded,
+}
+
+/// Reads the one share in a share file, and returns its words
+pub fn read_share_file<P: AsRef<Path>>(path: P) -> Result<Vec<String>, crate::Error> {
+    Ok(read_share_file_with_header(path)?.0)
+}
+
+/// Reads the one share in a share file written with another encoding (hex, base32, base58...),
+/// and returns its tokens as they are written
+pub fn read_encoded_share_file<P: AsRef<Path>>(path: P) -> Result<Vec<String>, crate::Error> {
+    Ok(read_share_file_as(path.as_ref(), Tokens::Encoded)?.0)
+}
+
+/// Reads the one share in a share file, and returns its words along with the header of versioned
+/// files, the header has already been checked against the words
+pub fn read_share_file_with_header<P: AsRef<Path>>(
+    path: P,
+) -> Result<(Vec<String>, Option<ShareFileHeader>), crate::Error> {
+    read_share_file_as(path.as_ref(), Tokens::Words)
+}
+
+fn read_share_file_as(
+    path: &Path,
+    tokens: Tokens,
+) -> Result<(Vec<String>, Option<ShareFileHeader>), crate::Error> {
+    let display_path = path.display().to_string();
+
+    let contents = std::fs::read_to_string(path).map_err(|error| ShareFileError::Unreadable {
+        path: display_path.clone(),
+        reason: error.to_string(),
+    })?;
+
+    let invalid = |problem| ShareFileError::Invalid {
+        path: display_path.clone(),
+        problem,
+    };
+
+    let mut shares = parse_shares_as(&contents, tokens).map_err(invalid)?;
+    let header = ShareFileHeader::parse(&contents).map_err(invalid)?;
+
+    match shares.len() {
+        1 => Ok((shares.remove(0), header)),
+        count => Err(ShareFileError::MultipleShares {
+            path: display_path,
+            count,
+        }
+        .into()),
+    }
+}
+
+/// Parses the contents of a share file, returns the words of each share found in it
+pub fn parse_shares(contents: &str) -> Result<Vec<Vec<String>>, ShareFileProblem> {
+    parse_shares_as(contents, Tokens::Words)
+}
+
+/// Parses the contents of a share file written with another encoding, returns the tokens of each
+/// share found in it as they are written
+pub fn parse_encoded_shares(contents: &str) -> Result<Vec<Vec<String>>, ShareFileProblem> {
+    parse_shares_as(contents, Tokens::Encoded)
+}
+
+fn parse_shares_as(contents: &str, tokens: Tokens) -> Result<Vec<Vec<String>>, ShareFileProblem> {
+    let contents = contents.trim_start_matches('\u{feff}');
+    let trimmed = contents.trim();
+
+    let shares = if trimmed.starts_with('{') || trimmed.starts_with('[') || trimmed.starts_with('"')
+    {
+        let value: Value = serde_json::from_str(trimmed)
+            .map_err(|error| ShareFileProblem::Json(error.to_string()))?;
+        parse_json(&value, tokens)?
+    } else if is_numbered(contents) {
+        parse_numbered(contents, tokens)?
+    } else {
+        parse_plain(contents, tokens)?
+    };
+
+    if shares.is_empty() || shares.iter().any(Vec::is_empty) {
+        return Err(ShareFileProblem::Empty);
+    }
+
+    // files with more than one share are reported by the caller, so there is nothing to verify
+    if let (Some(header), [words]) = (ShareFileHeader::parse(contents)?, shares.as_slice()) {
+        header.verify(contents, words)?;
+    }
+
+    Ok(shares)
+}
+
+/// Splits a line into the number in front of it (ex: `12: word`, `12. word`, `12) word`) and the
+/// rest of the line
+fn split_number(line: &str) -> Option<(usize, &str)> {
+    let line = line.trim();
+    let digits = line.find(|char: char| !char.is_ascii_digit())?;
+    if digits == 0 {
+        return None;
+    }
+
+    let rest = &line[digits..];
+    let rest = rest
+        .strip_prefix(':')
+        .or_else(|| rest.strip_prefix('.'))
+        .or_else(|| rest.strip_prefix(')'))?;
+
+    Some((line[..digits].parse().ok()?, rest))
+}
+
+fn is_numbered(contents: &str) -> bool {
+    contents
+        .lines()
+        .find(|line| !line.trim().is_empty() && !is_comment(line))
+        .and_then(split_number)
+        .is_some()
+}
+
+/// Checks a word only has letters in it and lowercases it, word numbers and SSKR (`ur:sskr/...`)
+/// and codex32 (`ms1...`) shares are kept as they are. With [Tokens::Encoded] any token made of
+/// letters, digits and the separators used by URs and base32 (`:/-=`) is kept as it is
+fn clean_word(word: &str, tokens: Tokens) -> Option<String> {
+    let lowercase = word.to_lowercase();
+    let is_token = word
+        .chars()
+        .all(|char| char.is_ascii_alphanumeric() || matches!(char, ':' | '/' | '-' | '='));
+
+    if is_token && tokens == Tokens::Encoded {
+        Some(word.to_string())
+    } else if English::contains_word(&lowercase) {
+        Some(lowercase)
+    } else if is_token
+        && (word.chars().all(|char| char.is_ascii_digit())
+            || lowercase.starts_with("ur:sskr/")
+            || lowercase.starts_with("ms1"))
+    {
+        Some(word.to_string())
+    } else if word.chars().all(char::is_alphabetic) {
+        Some(lowercase)
+    } else {
+        None
+    }
+}
+
+fn parse_numbered(contents: &str, tokens: Tokens) -> Result<Vec<Vec<String>>, ShareFileProblem> {
+    let mut shares: Vec<Vec<String>> = vec![];
+
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() || is_comment(line) {
+            continue;
+        }
+
+        let (number, rest) =
+            split_number(line).ok_or(ShareFileProblem::NotNumbered { line: line_number })?;
+
+        let mut words = rest.split_whitespace();
+        let word = match (words.next(), words.next()) {
+            (Some(word), None) => word,
+            _ => return Err(ShareFileProblem::NotOneWord { line: line_number }),
+        };
+
+        let word = clean_word(word, tokens).ok_or(ShareFileProblem::NotAWord {
+            line: line_number,
+            word: 1,
+        })?;
+
+        match shares.last_mut() {
+            Some(share) if number == share.len() + 1 => share.push(word),
+            _ if number == 1 => shares.push(vec![word]),
+            share => {
+                return Err(ShareFileProblem::OutOfOrder {
+                    line: line_number,
+                    expected: share.map_or(1, |share| share.len() + 1),
+                    found: number,
+                })
+            }
+        }
+    }
+
+    Ok(shares)
+}
+
+fn parse_plain(contents: &str, tokens: Tokens) -> Result<Vec<Vec<String>>, ShareFileProblem> {
+    let mut shares: Vec<Vec<String>> = vec![vec![]];
+
+    for (index, line) in contents.lines().enumerate() {
+        if is_comment(line) {
+            continue;
+        }
+
+        let words = line
+            .split(|char: char| char.is_whitespace() || char == ',')
+            .filter(|word| !word.is_empty())
+            .enumerate()
+            .map(|(word_index, word)| {
+                clean_word(word, tokens).ok_or(ShareFileProblem::NotAWord {
+                    line: index + 1,
+                    word: word_index + 1,
+                })
+            })
+            .collect::<Result<Vec<String>, ShareFileProblem>>()?;
+
+        let current = shares.last_mut().expect("always has a share");
+
+        // a blank line after some words starts a new share
+        match (words.is_empty(), current.is_empty()) {
+            (true, false) => shares.push(vec![]),
+            (true, true) => {}
+            (false, _) => current.extend(words),
+        }
+    }
+
+    shares.retain(|share| !share.is_empty());
+    Ok(shares)
+}
+
+fn parse_json(value: &Value, tokens: Tokens) -> Result<Vec<Vec<String>>, ShareFileProblem> {
+    match value {
+        Value::Array(values) if values.iter().all(Value::is_string) => {
+            let strings = values.iter().filter_map(Value::as_str).collect::<Vec<_>>();
+
+            // an array of single words is one share, an array of phrases is many shares
+            if strings
+                .iter()
+                .all(|string| string.split_whitespace().count() == 1)
+            {
+                Ok(vec![json_words(&strings.join(" "), tokens)?])
+            } else {
+                strings
+                    .iter()
+                    .map(|phrase| json_words(phrase, tokens))
+                    .collect()
+            }
+        }
+
+        Value::Array(values) => values.iter().try_fold(vec![], |mut shares, value| {
+            shares.extend(parse_json(value, tokens)?);
+            Ok(shares)
+        }),
+
+        Value::String(phrase) => Ok(vec![json_words(phrase, tokens)?]),
+
+        Value::Object(object) => {
+            if let Some(shares) = object
+                .get("split_phrases")
+                .or_else(|| object.get("secret_phrases"))
+            {
+                return parse_json(shares, tokens);
+            }
+
+            let field = JSON_FIELDS
+                .iter()
+                .find_map(|field| object.get(*field))
+                .ok_or_else(|| {
+                    ShareFileProblem::Json(format!(
+                        "expected an object with one of the fields {:?}",
+                        JSON_FIELDS
+                    ))
+                })?;
+
+            match field {
+                Value::String(phrase) => Ok(vec![json_words(phrase, tokens)?]),
+                Value::Array(words) if words.iter().all(Value::is_string) => {
+                    let words = words.iter().filter_map(Value::as_str).collect::<Vec<_>>();
+                    Ok(vec![json_words(&words.join(" "), tokens)?])
+                }
+                _ => Err(ShareFileProblem::Json(
+                    "expected the phrase to be a string or an array of words".to_string(),
+                )),
+            }
+        }
+
+        _ => Err(ShareFileProblem::Json(
+            "expected a string, an array or an object".to_string(),
+        )),
+    }
+}
+
+fn json_words(phrase: &str, tokens: Tokens) -> Result<Vec<String>, ShareFileProblem> {
+    let mut shares = parse_plain(phrase, tokens)?;
+
+    match shares.len() {
+        0 => Ok(vec![]),
+        _ => Ok(shares.remove(0)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 28] = [
+        "gun", "dismiss", "area", "ability", "laptop", "live", "ignore", "love", "ride", "deposit",
+        "upset", "enemy", "start", "leopard", "domain", "exile", "talent", "enroll", "north",
+        "position", "talk", "hope", "script", "parent", "tongue", "ride", "pepper", "brisk",
+    ];
+
+    fn words() -> Vec<String> {
+        WORDS.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_the_numbered_format() {
+        let contents = WORDS
+            .iter()
+            .enumerate()
+            .map(|(index, word)| format!("        {}: {}", index + 1, word))
+            .collect::<Vec<String>>()
+            .join("\r\n");
+
+        assert_eq!(parse_shares(&contents), Ok(vec![words()]));
+    }
+
+    #[test]
+    fn parses_plain_text() {
+        let contents = format!(
+            "{}\n{}\n",
+            WORDS[..14].join(", "),
+            WORDS[14..].join(" ").to_uppercase()
+        );
+
+        assert_eq!(parse_shares(&contents), Ok(vec![words()]));
+    }
+
+    #[test]
+    fn parses_json() {
+        let phrase = WORDS.join(" ");
+
+        for contents in [
+            format!("\"{}\"", phrase),
+            format!("{{\"split_phrase\": \"{}\"}}", phrase),
+            format!("{{\"words\": [\"{}\"]}}", WORDS.join("\", \"")),
+            format!("[\"{}\"]", WORDS.join("\",\"")),
+        ] {
+            assert_eq!(parse_shares(&contents), Ok(vec![words()]), "{}", contents);
+        }
+    }
+
+    #[test]
+    fn keeps_word_numbers_and_shares() {
+        let contents = "0580 01001000011\nUR:SSKR/GOAD ms10cashsxxx";
+
+        assert_eq!(
+            parse_shares(contents),
+            Ok(vec![vec![
+                "0580".to_string(),
+                "01001000011".to_string(),
+                "UR:SSKR/GOAD".to_string(),
+                "ms10cashsxxx".to_string(),
+            ]])
+        );
+
+        assert_eq!(
+            parse_shares("3mBq9NdrRw2pVc"),
+            Err(ShareFileProblem::NotAWord { line: 1, word: 1 })
+        );
+    }
+
+    #[test]
+    fn keeps_encoded_tokens() {
+        let contents = "3mBq9NdrRw2pVc\n0580 01001000011 0ff784df\nUR:SSKR/GOAD-ms10cashsxxx";
+
+        assert_eq!(
+            parse_encoded_shares(contents),
+            Ok(vec![vec![
+                "3mBq9NdrRw2pVc".to_string(),
+                "0580".to_string(),
+                "01001000011".to_string(),
+                "0ff784df".to_string(),
+                "UR:SSKR/GOAD-ms10cashsxxx".to_string(),
+            ]])
+        );
+
+        let numbered = "1: 0580\n2: 1001\n3: GYRO";
+        assert_eq!(
+            parse_encoded_shares(numbered),
+            Ok(vec![vec![
+                "0580".to_string(),
+                "1001".to_string(),
+                "GYRO".to_string()
+            ]])
+        );
+    }
+
+    #[test]
+    fn detects_more_than_one_share() {
+        let phrase = WORDS.join(" ");
+
+        let plain = format!("{}\n\n{}", phrase, phrase);
+        assert_eq!(parse_shares(&plain).unwrap().len(), 2);
+
+        let numbered = "1: gun\n2: dismiss\n1: area\n2: ability";
+        assert_eq!(parse_shares(numbered).unwrap().len(), 2);
+
+        let json = format!("{{\"split_phrases\": [\"{}\", \"{}\"]}}", phrase, phrase);
+        assert_eq!(parse_shares(&json).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn reports_malformed_contents() {
+        assert_eq!(
+            parse_shares("gun dismiss\narea ab1lity"),
+            Err(ShareFileProblem::NotAWord { line: 2, word: 2 })
+        );
+        assert_eq!(
+            parse_shares("1: gun\n3: dismiss"),
+            Err(ShareFileProblem::OutOfOrder {
+                line: 2,
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            parse_shares("1: gun\n2: dismiss area"),
+            Err(ShareFileProblem::NotOneWord { line: 2 })
+        );
+        assert_eq!(parse_shares(" \n\n"), Err(ShareFileProblem::Empty));
+        assert!(matches!(
+            parse_shares("{\"split_phrase\": "),
+            Err(ShareFileProblem::Json(_))
+        ));
+    }
+
+    const SPLIT_PHRASE: &str = "embody fog drop about embrace visa adapt winner wine dash fabric snack drip auction deputy visit shift animal various bread country lecture assist marriage merit goat gravity glove";
+
+    fn versioned_file() -> (ShareFileHeader, String) {
+        let header = ShareFileHeader::for_split_phrase(
+            SPLIT_PHRASE,
+            Some("2021-06-01T12:00:00Z".to_string()),
+            Some("splitmonic 0.1.0".to_string()),
+        )
+        .unwrap();
+        let text = share_file_text(SPLIT_PHRASE, &header);
+
+        (header, text)
+    }
+
+    #[test]
+    fn writes_and_parses_the_versioned_format() {
+        let (header, text) = versioned_file();
+
+        assert!(text.starts_with("# splitmonic split phrase\n# format: 1\n# set id: embody fog drop\n# share: 3 of 5\n# threshold: 3\n"));
+        assert!(text.contains("\n1: embody\n"));
+        assert!(text.contains("\n28: glove\n# checksum: "));
+
+        assert_eq!(ShareFileHeader::parse(&text), Ok(Some(header)));
+        assert_eq!(
+            parse_shares(&text),
+            Ok(vec![SPLIT_PHRASE
+                .split(' ')
+                .map(ToString::to_string)
+                .collect()])
+        );
+    }
+
+    #[test]
+    fn rejects_versioned_files_that_dont_match_their_header() {
+        let (_, text) = versioned_file();
+
+        assert_eq!(
+            parse_shares(&text.replace("28: glove", "28: gravity")),
+            Err(ShareFileProblem::ChecksumMismatch)
+        );
+        assert_eq!(
+            parse_shares(&text.replace("# format: 1", "# format: 2")),
+            Err(ShareFileProblem::UnsupportedVersion("2".to_string()))
+        );
+        assert_eq!(
+            parse_shares(&text.replace("# share: 3 of 5", "# share: 2 of 5")),
+            Err(ShareFileProblem::HeaderMismatch("share"))
+        );
+        assert_eq!(
+            parse_shares(&text.replace("# threshold: 3", "# threshold: 2")),
+            Err(ShareFileProblem::InvalidHeaderField("threshold"))
+        );
+        assert_eq!(
+            parse_shares(&text.replace("# share: 3 of 5", "# share: 3 of 6")),
+            Err(ShareFileProblem::InvalidHeaderField("shares"))
+        );
+
+        let without_checksum = text
+            .lines()
+            .filter(|line| !line.starts_with("# checksum"))
+            .collect::<Vec<&str>>()
+            .join("\n");
+        assert_eq!(
+            parse_shares(&without_checksum),
+            Err(ShareFileProblem::MissingHeaderField("checksum"))
+        );
+    }
+
+    #[test]
+    fn errors_with_the_path_of_unreadable_files() {
+        let path = std::env::temp_dir().join("splitmonic_missing_share_file.txt");
+
+        match read_share_file(&path) {
+            Err(crate::Error::ShareFile(ShareFileError::Unreadable {
+                path: error_path, ..
+            })) => {
+                assert_eq!(error_path, path.display().to_string())
+            }
+            other => panic!("expected an unreadable file error, got: {:?}", other),
+        }
+    }
+}
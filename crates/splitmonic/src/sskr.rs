@@ -0,0 +1,771 @@
+//! Contains Sharded Secret Key Reconstruction (SSKR), Blockchain Commons' format for splitting a
+//! secret into shares, so mnemonics split by splitmonic can be recovered by wallets that support
+//! SSKR and the other way around.
+//!
+//! Unlike split phrases, SSKR shares can be split into groups: the secret is split into groups
+//! with a group threshold, and each group's secret is split again into members with a member
+//! threshold. Every share starts with 5 bytes of metadata (the set identifier, the group threshold
+//! and count, the group index and member threshold, and the member index) followed by its part of
+//! the mnemonic's entropy. Shares are shown as Bytewords or as a `ur:sskr/` UR.
+//! See: https://github.com/BlockchainCommons/Research/blob/master/papers/bcr-2020-011-sskr.md
+
+use crate::bytewords::{self, Style};
+use bip39::{Language, Mnemonic};
+use bitcoin_hashes::{hmac, sha256, Hash, HashEngine};
+use rand::{thread_rng, RngCore};
+use thiserror::Error;
+use zeroize::Zeroize;
+
+/// The number of bytes of metadata at the start of every share
+const METADATA_LENGTH: usize = 5;
+
+/// The x coordinates of the secret and the digest used to check it in the shamir polynomial
+const SECRET_INDEX: u8 = 255;
+const DIGEST_INDEX: u8 = 254;
+const DIGEST_LENGTH: usize = 4;
+
+/// The CBOR tags SSKR shares are tagged with in Bytewords, the old and the current one
+const CBOR_TAG: [u8; 3] = [0xd9, 0x01, 0x35];
+const CBOR_TAG_V2: [u8; 3] = [0xd9, 0x9d, 0x75];
+
+const UR_PREFIX: &str = "ur:sskr/";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SskrError {
+    #[error("invalid group `{0}`, groups are written as threshold-of-count, ex: 2-of-3")]
+    InvalidGroup(String),
+
+    #[error("invalid SSKR spec, {0}")]
+    InvalidSpec(&'static str),
+
+    #[error("invalid secret length, SSKR secrets must be an even number of bytes between 16 and 32, found: {0}")]
+    InvalidSecretLength(usize),
+
+    #[error("invalid SSKR share, {0}")]
+    InvalidShare(&'static str),
+
+    #[error("the SSKR shares are from different sets or splits")]
+    MismatchedShares,
+
+    #[error("the same SSKR share was given twice (group {group}, member {member})")]
+    DuplicateShare { group: u8, member: u8 },
+
+    #[error("not enough SSKR shares, {0}")]
+    NotEnoughShares(String),
+
+    #[error("the SSKR shares didn't recover the secret, one of them is wrong")]
+    ChecksumFailure,
+}
+
+/// How many shares a group is split into, and how many of them are needed to recover it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroupSpec {
+    pub threshold: u8,
+    pub count: u8,
+}
+
+impl std::str::FromStr for GroupSpec {
+    type Err = SskrError;
+
+    fn from_str(group: &str) -> Result<Self, Self::Err> {
+        let invalid = || SskrError::InvalidGroup(group.to_string());
+
+        let mut parts = group.trim().splitn(2, "-of-");
+        let threshold = parts.next().and_then(|part| part.parse().ok());
+        let count = parts.next().and_then(|part| part.parse().ok());
+
+        match (threshold, count) {
+            (Some(threshold), Some(count)) => Ok(Self { threshold, count }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// One SSKR share, its metadata and its part of the secret
+#[derive(Debug, Clone, PartialEq)]
+pub struct Share {
+    pub identifier: u16,
+    pub group_threshold: u8,
+    pub group_count: u8,
+    pub group_index: u8,
+    pub member_threshold: u8,
+    pub member_index: u8,
+    value: Vec<u8>,
+}
+
+impl Drop for Share {
+    fn drop(&mut self) {
+        self.value.zeroize()
+    }
+}
+
+impl Share {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![
+            (self.identifier >> 8) as u8,
+            self.identifier as u8,
+            ((self.group_threshold - 1) << 4) | (self.group_count - 1),
+            (self.group_index << 4) | (self.member_threshold - 1),
+            self.member_index,
+        ];
+        bytes.extend_from_slice(&self.value);
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SskrError> {
+        if bytes.len() < METADATA_LENGTH {
+            return Err(SskrError::InvalidShare("it is too short"));
+        }
+
+        let value = bytes[METADATA_LENGTH..].to_vec();
+        validate_secret_length(value.len())?;
+
+        let share = Self {
+            identifier: (bytes[0] as u16) << 8 | bytes[1] as u16,
+            group_threshold: (bytes[2] >> 4) + 1,
+            group_count: (bytes[2] & 0xf) + 1,
+            group_index: bytes[3] >> 4,
+            member_threshold: (bytes[3] & 0xf) + 1,
+            member_index: bytes[4] & 0xf,
+            value,
+        };
+
+        if bytes[4] >> 4 != 0 {
+            return Err(SskrError::InvalidShare("the reserved bits must be zero"));
+        }
+
+        if share.group_threshold > share.group_count {
+            return Err(SskrError::InvalidShare(
+                "the group threshold is more than the number of groups",
+            ));
+        }
+
+        Ok(share)
+    }
+
+    /// The share as tagged CBOR in Bytewords, the way SSKR shares are written down. Uses the
+    /// current tag, the same as seedtool writes
+    pub fn to_bytewords(&self) -> String {
+        let mut cbor = CBOR_TAG_V2.to_vec();
+        cbor.extend(cbor_bytes(&self.to_bytes()));
+
+        bytewords::encode(&cbor, Style::Standard)
+    }
+
+    /// The share as a `ur:sskr/` UR, untagged CBOR in minimal Bytewords
+    pub fn to_ur(&self) -> String {
+        let cbor = cbor_bytes(&self.to_bytes());
+        format!("{}{}", UR_PREFIX, bytewords::encode(&cbor, Style::Minimal))
+    }
+
+    /// Reads a share written as Bytewords (with spaces or dashes, or minimal Bytewords of 2 letters
+    /// per byte) or as a `ur:sskr/` UR
+    pub fn parse(text: &str) -> Result<Self, crate::Error> {
+        let text = text.trim().to_lowercase();
+
+        let cbor = match text.strip_prefix(UR_PREFIX) {
+            Some(ur) => bytewords::decode(ur, Style::Minimal)?,
+            None if text.contains('-') => bytewords::decode(&text, Style::Uri)?,
+            None if text.contains(char::is_whitespace) => {
+                bytewords::decode(&text, Style::Standard)?
+            }
+            None => bytewords::decode(&text, Style::Minimal)?,
+        };
+
+        Ok(Self::from_bytes(from_cbor(&cbor)?)?)
+    }
+
+    /// The group and member of the share, and how many of each are needed
+    pub fn describe(&self) -> String {
+        format!(
+            "group {} ({} of {} groups needed), member {} ({} needed)",
+            self.group_index + 1,
+            self.group_threshold,
+            self.group_count,
+            self.member_index + 1,
+            self.member_threshold
+        )
+    }
+}
+
+fn validate_secret_length(length: usize) -> Result<(), SskrError> {
+    if !(16..=32).contains(&length) || length % 2 == 1 {
+        return Err(SskrError::InvalidSecretLength(length));
+    }
+
+    Ok(())
+}
+
+/// A CBOR byte string, SSKR shares are never longer than 37 bytes
+fn cbor_bytes(bytes: &[u8]) -> Vec<u8> {
+    let mut cbor = if bytes.len() < 24 {
+        vec![0x40 | bytes.len() as u8]
+    } else {
+        vec![0x58, bytes.len() as u8]
+    };
+    cbor.extend_from_slice(bytes);
+
+    cbor
+}
+
+/// The bytes of a CBOR byte string, optionally tagged as an SSKR share
+fn from_cbor(cbor: &[u8]) -> Result<&[u8], SskrError> {
+    let cbor = if cbor.starts_with(&CBOR_TAG) || cbor.starts_with(&CBOR_TAG_V2) {
+        &cbor[3..]
+    } else {
+        cbor
+    };
+
+    let (length, bytes) = match cbor.first() {
+        Some(header @ 0x40..=0x57) => ((header & 0x1f) as usize, &cbor[1..]),
+        Some(0x58) if cbor.len() > 1 => (cbor[1] as usize, &cbor[2..]),
+        _ => return Err(SskrError::InvalidShare("it is not a CBOR byte string")),
+    };
+
+    if bytes.len() != length {
+        return Err(SskrError::InvalidShare("the CBOR length is wrong"));
+    }
+
+    Ok(bytes)
+}
+
+fn gf256_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 == 1 {
+            product ^= a;
+        }
+
+        let carry = a & 0x80;
+        a <<= 1;
+        if carry != 0 {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+
+    product
+}
+
+fn gf256_inv(a: u8) -> u8 {
+    // a^254 is the inverse of a, as a^255 is 1
+    (0..254).fold(1, |inverse, _| gf256_mul(inverse, a))
+}
+
+/// The value at `x` of the polynomials going through the points `xs`, `ys` (one per byte)
+fn interpolate(xs: &[u8], ys: &[Vec<u8>], x: u8) -> Vec<u8> {
+    let mut result = vec![0u8; ys[0].len()];
+
+    for (i, (xi, yi)) in xs.iter().zip(ys).enumerate() {
+        let basis = xs
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .fold(1, |basis, (_, xj)| {
+                gf256_mul(basis, gf256_mul(x ^ xj, gf256_inv(xi ^ xj)))
+            });
+
+        for (byte, y) in result.iter_mut().zip(yi) {
+            *byte ^= gf256_mul(basis, *y);
+        }
+    }
+
+    result
+}
+
+fn create_digest(random: &[u8], secret: &[u8]) -> [u8; 32] {
+    let mut engine = hmac::HmacEngine::<sha256::Hash>::new(random);
+    engine.input(secret);
+    hmac::Hmac::<sha256::Hash>::from_engine(engine).into_inner()
+}
+
+/// Splits the secret the same way Blockchain Commons' bc-shamir does, so the shares are the same
+/// given the same random bytes: the first threshold - 2 shares are random, the rest are points on
+/// the polynomial going through them, a digest of the secret and the secret
+fn split_secret<R: RngCore>(threshold: u8, count: u8, secret: &[u8], rng: &mut R) -> Vec<Vec<u8>> {
+    if threshold == 1 {
+        return vec![secret.to_vec(); count as usize];
+    }
+
+    let mut shares = vec![vec![0u8; secret.len()]; count as usize];
+    for share in shares.iter_mut().take(threshold as usize - 2) {
+        rng.fill_bytes(share);
+    }
+
+    let mut digest = vec![0u8; secret.len()];
+    rng.fill_bytes(&mut digest[DIGEST_LENGTH..]);
+    let check = create_digest(&digest[DIGEST_LENGTH..], secret);
+    digest[..DIGEST_LENGTH].copy_from_slice(&check[..DIGEST_LENGTH]);
+
+    let mut xs = (0..threshold - 2).collect::<Vec<u8>>();
+    xs.extend_from_slice(&[DIGEST_INDEX, SECRET_INDEX]);
+    let mut ys = shares[..threshold as usize - 2].to_vec();
+    ys.push(digest);
+    ys.push(secret.to_vec());
+
+    for (index, share) in shares.iter_mut().enumerate().skip(threshold as usize - 2) {
+        *share = interpolate(&xs, &ys, index as u8);
+    }
+
+    ys.iter_mut().for_each(Zeroize::zeroize);
+    shares
+}
+
+fn recover_secret(indexes: &[u8], shares: &[Vec<u8>]) -> Result<Vec<u8>, SskrError> {
+    if shares.len() == 1 {
+        return Ok(shares[0].clone());
+    }
+
+    let mut digest = interpolate(indexes, shares, DIGEST_INDEX);
+    let secret = interpolate(indexes, shares, SECRET_INDEX);
+    let check = create_digest(&digest[DIGEST_LENGTH..], &secret);
+
+    let valid = digest[..DIGEST_LENGTH] == check[..DIGEST_LENGTH];
+    digest.zeroize();
+
+    if !valid {
+        return Err(SskrError::ChecksumFailure);
+    }
+
+    Ok(secret)
+}
+
+/// Splits a secret into groups of shares
+pub fn generate<R: RngCore>(
+    group_threshold: u8,
+    groups: &[GroupSpec],
+    secret: &[u8],
+    rng: &mut R,
+) -> Result<Vec<Vec<Share>>, SskrError> {
+    validate_secret_length(secret.len())?;
+
+    if groups.is_empty() || groups.len() > 16 {
+        return Err(SskrError::InvalidSpec(
+            "there must be between 1 and 16 groups",
+        ));
+    }
+
+    if group_threshold == 0 || group_threshold as usize > groups.len() {
+        return Err(SskrError::InvalidSpec(
+            "the group threshold must be between 1 and the number of groups",
+        ));
+    }
+
+    if groups
+        .iter()
+        .any(|group| group.count == 0 || group.count > 16 || group.threshold == 0)
+    {
+        return Err(SskrError::InvalidSpec(
+            "groups must have between 1 and 16 members",
+        ));
+    }
+
+    if groups.iter().any(|group| group.threshold > group.count) {
+        return Err(SskrError::InvalidSpec(
+            "a group's threshold must not be more than its number of members",
+        ));
+    }
+
+    let mut identifier = [0u8; 2];
+    rng.fill_bytes(&mut identifier);
+    let identifier = (identifier[0] as u16) << 8 | identifier[1] as u16;
+
+    let group_secrets = split_secret(group_threshold, groups.len() as u8, secret, rng);
+
+    Ok(groups
+        .iter()
+        .zip(group_secrets)
+        .enumerate()
+        .map(|(group_index, (group, mut group_secret))| {
+            let members = split_secret(group.threshold, group.count, &group_secret, rng);
+            group_secret.zeroize();
+
+            members
+                .into_iter()
+                .enumerate()
+                .map(|(member_index, value)| Share {
+                    identifier,
+                    group_threshold,
+                    group_count: groups.len() as u8,
+                    group_index: group_index as u8,
+                    member_threshold: group.threshold,
+                    member_index: member_index as u8,
+                    value,
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Recovers the secret from enough shares of enough groups
+pub fn combine(shares: &[Share]) -> Result<Vec<u8>, SskrError> {
+    let first = shares
+        .first()
+        .ok_or_else(|| SskrError::NotEnoughShares("no shares were given".to_string()))?;
+
+    if shares.iter().any(|share| {
+        share.identifier != first.identifier
+            || share.group_threshold != first.group_threshold
+            || share.group_count != first.group_count
+            || share.value.len() != first.value.len()
+    }) {
+        return Err(SskrError::MismatchedShares);
+    }
+
+    let mut group_indexes = vec![];
+    let mut group_secrets = vec![];
+
+    for group_index in 0..first.group_count {
+        let members = shares
+            .iter()
+            .filter(|share| share.group_index == group_index)
+            .collect::<Vec<&Share>>();
+
+        let member_threshold = match members.first() {
+            Some(member) => member.member_threshold,
+            None => continue,
+        };
+
+        if members
+            .iter()
+            .any(|member| member.member_threshold != member_threshold)
+        {
+            return Err(SskrError::MismatchedShares);
+        }
+
+        for (i, member) in members.iter().enumerate() {
+            if members[..i]
+                .iter()
+                .any(|other| other.member_index == member.member_index)
+            {
+                return Err(SskrError::DuplicateShare {
+                    group: group_index + 1,
+                    member: member.member_index + 1,
+                });
+            }
+        }
+
+        // groups without enough members can't be recovered, but the others might be enough
+        if members.len() < member_threshold as usize {
+            continue;
+        }
+
+        let members = &members[..member_threshold as usize];
+        let indexes = members
+            .iter()
+            .map(|member| member.member_index)
+            .collect::<Vec<u8>>();
+        let values = members
+            .iter()
+            .map(|member| member.value.clone())
+            .collect::<Vec<Vec<u8>>>();
+
+        group_indexes.push(group_index);
+        group_secrets.push(recover_secret(&indexes, &values)?);
+    }
+
+    if group_indexes.len() < first.group_threshold as usize {
+        return Err(SskrError::NotEnoughShares(format!(
+            "{} of {} groups are needed, but only {} had enough shares",
+            first.group_threshold,
+            first.group_count,
+            group_indexes.len()
+        )));
+    }
+
+    let threshold = first.group_threshold as usize;
+    let secret = recover_secret(&group_indexes[..threshold], &group_secrets[..threshold]);
+    group_secrets.iter_mut().for_each(Zeroize::zeroize);
+
+    secret
+}
+
+/// Splits the entropy of a mnemonic code into SSKR shares, and checks that the shares recover it
+pub fn split_mnemonic_code(
+    mnemonic_code: &str,
+    group_threshold: u8,
+    groups: &[GroupSpec],
+) -> Result<Vec<Vec<Share>>, crate::Error> {
+    let mnemonic = Mnemonic::parse_in(Language::English, mnemonic_code)?;
+    let mut entropy = mnemonic.to_entropy();
+
+    let shares = generate(group_threshold, groups, &entropy, &mut thread_rng())?;
+
+    let quorum = shares
+        .iter()
+        .zip(groups)
+        .take(group_threshold as usize)
+        .flat_map(|(members, group)| members.iter().take(group.threshold as usize))
+        .cloned()
+        .collect::<Vec<Share>>();
+
+    let verified = combine(&quorum)? == entropy;
+    entropy.zeroize();
+
+    if !verified {
+        return Err(SskrError::ChecksumFailure.into());
+    }
+
+    Ok(shares)
+}
+
+/// Recovers a mnemonic code from SSKR shares written as Bytewords or `ur:sskr/` URs
+pub fn recover_mnemonic_code(shares: &[String]) -> Result<String, crate::Error> {
+    let shares = shares
+        .iter()
+        .map(|share| Share::parse(share))
+        .collect::<Result<Vec<Share>, _>>()?;
+
+    let mut entropy = combine(&shares)?;
+    let mnemonic = Mnemonic::from_entropy(&entropy);
+    entropy.zeroize();
+
+    Ok(mnemonic?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The fake random number generator used by the bc-shamir and bc-sskr test vectors
+    struct FakeRng;
+
+    impl RngCore for FakeRng {
+        fn next_u32(&mut self) -> u32 {
+            let mut bytes = [0; 4];
+            self.fill_bytes(&mut bytes);
+            u32::from_le_bytes(bytes)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let mut bytes = [0; 8];
+            self.fill_bytes(&mut bytes);
+            u64::from_le_bytes(bytes)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            let mut byte = 0u8;
+            for b in dest.iter_mut() {
+                *b = byte;
+                byte = byte.wrapping_add(17);
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    // the shares from the bc-shamir test vectors, with the SSKR metadata in front of them:
+    // identifier 0011 (the first 2 fake random bytes), 1 of 1 groups, then the member threshold
+    // and index
+    #[test]
+    fn splits_like_the_reference_implementation() {
+        let secret = from_hex("0ff784df000c4380a5ed683f7e6e3dcf");
+        let group = GroupSpec {
+            threshold: 3,
+            count: 5,
+        };
+        let shares = generate(1, &[group], &secret, &mut FakeRng).unwrap();
+
+        let shares = shares[0]
+            .iter()
+            .map(|share| hex(&share.to_bytes()))
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            shares,
+            vec![
+                "001100020000112233445566778899aabbccddeeff",
+                "0011000201d43099fe444807c46921a4f33a2a798b",
+                "0011000202d9ad4e3bec2e1a7485698823abf05d36",
+                "00110002030d8cf5f6ec337bc764d1866b5d07ca42",
+                "00110002041aa7fe3199bc5092ef3816b074cabdf2",
+            ]
+        );
+
+        let secret = from_hex("204188bfa6b440a1bdfd6753ff55a8241e07af5c5be943db917e3efabc184b1a");
+        let group = GroupSpec {
+            threshold: 2,
+            count: 7,
+        };
+        let shares = generate(1, &[group], &secret, &mut FakeRng).unwrap();
+
+        assert_eq!(
+            hex(&shares[0][0].to_bytes()),
+            "00110001002dcd14c2252dc8489af3985030e74d5a48e8eff1478ab86e65b43869bf39d556"
+        );
+        assert_eq!(
+            hex(&shares[0][6].to_bytes()),
+            "001100010628a19475dcde9f09ba2e9e881979413592027216e60c8513cdee937c67b2c586"
+        );
+        assert_eq!(
+            combine(&[shares[0][3].clone(), shares[0][4].clone()]).unwrap(),
+            secret
+        );
+    }
+
+    #[test]
+    fn recovers_from_enough_groups() {
+        let secret = from_hex("204188bfa6b440a1bdfd6753ff55a8241e07af5c5be943db917e3efabc184b1a");
+        let groups = ["2-of-3".parse().unwrap(), "3-of-5".parse().unwrap()];
+        let shares = generate(2, &groups, &secret, &mut thread_rng()).unwrap();
+
+        assert_eq!(
+            shares[1][4].describe(),
+            "group 2 (2 of 2 groups needed), member 5 (3 needed)"
+        );
+
+        let quorum = vec![
+            shares[0][0].clone(),
+            shares[0][2].clone(),
+            shares[1][0].clone(),
+            shares[1][1].clone(),
+            shares[1][4].clone(),
+        ];
+        assert_eq!(combine(&quorum).unwrap(), secret);
+
+        assert!(matches!(
+            combine(&quorum[..4]),
+            Err(SskrError::NotEnoughShares(_))
+        ));
+        assert_eq!(
+            combine(&[quorum[0].clone(), quorum[0].clone()]),
+            Err(SskrError::DuplicateShare {
+                group: 1,
+                member: 1
+            })
+        );
+    }
+
+    // the SSKR shares of the seed 59f2293a5bce7d4de59e71b4207ac5d2 in seedtool's manual (seedtool-cli
+    // 0.4.0, MANUAL.md "Restoring Seeds with SSKR")
+    const SEEDTOOL_SHARES: [&str; 2] = [
+        "tuna next keep gyro acid yawn able acid able leaf idle mild legs play ugly atom liar slot scar film redo tent poem wasp maze calm scar need toil",
+        "tuna next keep gyro acid yawn able acid acid holy keep when luau cook jazz yank rock grim toil stub dice keys very ruby work crux peck down iron",
+    ];
+
+    // minimal Bytewords shares of the seed 7042842963c788571776c4adfa4ed8df, split into a 2-of-3,
+    // a 3-of-5 and a 1-of-2 group with a group threshold of 2 (MANUAL.md "Storing Seed Shares
+    // Using SSKR"), 2 shares of the first group and 1 of the last
+    const SEEDTOOL_GROUP_SHARES: [&str; 3] = [
+        "tantkpgohllabgadaeptlkndfzrdwfihtdmtrpeovtsofnfxfzvwtypkot",
+        "tantkpgohllabgadadyllamsenpklgcfceoxdycyjtvwfsjetbjtspuetl",
+        "tantkpgohllabgcxadntdrecrfcxledmsklujndwmnmyurhkmnzswnzcvd",
+    ];
+
+    #[test]
+    fn reads_and_writes_seedtool_shares() {
+        let shares = SEEDTOOL_SHARES
+            .iter()
+            .map(|text| Share::parse(text).unwrap())
+            .collect::<Vec<Share>>();
+
+        for (share, text) in shares.iter().zip(&SEEDTOOL_SHARES) {
+            assert_eq!(share.to_bytewords(), *text);
+            assert_eq!(Share::parse(&share.to_ur()).unwrap(), *share);
+        }
+        assert_eq!(
+            shares[1].to_ur(),
+            "ur:sskr/goadynaeadadhykpwnluckjzykrkgmtlsbdeksvyrywkeogmmypt"
+        );
+        assert_eq!(
+            shares[0].describe(),
+            "group 1 (1 of 1 groups needed), member 1 (2 needed)"
+        );
+        assert_eq!(
+            hex(&combine(&shares).unwrap()),
+            "59f2293a5bce7d4de59e71b4207ac5d2"
+        );
+
+        let shares = SEEDTOOL_GROUP_SHARES
+            .iter()
+            .map(|text| Share::parse(text).unwrap())
+            .collect::<Vec<Share>>();
+
+        for (share, text) in shares.iter().zip(&SEEDTOOL_GROUP_SHARES) {
+            let mut cbor = CBOR_TAG_V2.to_vec();
+            cbor.extend(cbor_bytes(&share.to_bytes()));
+            assert_eq!(bytewords::encode(&cbor, Style::Minimal), *text);
+        }
+        assert_eq!(
+            shares[2].describe(),
+            "group 3 (2 of 3 groups needed), member 2 (1 needed)"
+        );
+        assert_eq!(
+            hex(&combine(&shares).unwrap()),
+            "7042842963c788571776c4adfa4ed8df"
+        );
+        assert!(matches!(
+            combine(&shares[..2]),
+            Err(SskrError::NotEnoughShares(_))
+        ));
+    }
+
+    #[test]
+    fn writes_shares_as_bytewords_and_urs() {
+        let secret = from_hex("0ff784df000c4380a5ed683f7e6e3dcf");
+        let group = GroupSpec {
+            threshold: 3,
+            count: 5,
+        };
+        let shares = generate(1, &[group], &secret, &mut FakeRng).unwrap();
+        let share = &shares[0][1];
+
+        // tag 40309, then a 21 byte CBOR byte string
+        let bytewords = share.to_bytewords();
+        assert!(bytewords.starts_with("tuna next keep gyro able body able also acid"));
+        assert_eq!(Share::parse(&bytewords).unwrap(), *share);
+
+        let ur = share.to_ur();
+        assert!(ur.starts_with("ur:sskr/goaebyaeaoad"));
+        assert_eq!(Share::parse(&ur).unwrap(), *share);
+        assert_eq!(Share::parse(&ur.to_uppercase()).unwrap(), *share);
+
+        // shares written with the old tag 309 are still read
+        let mut cbor = CBOR_TAG.to_vec();
+        cbor.extend(cbor_bytes(&share.to_bytes()));
+        let old = bytewords::encode(&cbor, Style::Standard);
+        assert!(old.starts_with("tuna acid epic gyro"));
+        assert_eq!(Share::parse(&old).unwrap(), *share);
+
+        let mut wrong = bytewords.split(' ').collect::<Vec<&str>>();
+        wrong[10] = "zoom";
+        assert!(Share::parse(&wrong.join(" ")).is_err());
+    }
+
+    #[test]
+    fn splits_and_recovers_a_mnemonic_code() {
+        let mnemonic_code = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+        let groups = [GroupSpec {
+            threshold: 3,
+            count: 5,
+        }];
+        let shares = split_mnemonic_code(mnemonic_code, 1, &groups).unwrap();
+
+        let urs = shares[0]
+            .iter()
+            .skip(2)
+            .map(Share::to_ur)
+            .collect::<Vec<String>>();
+        assert_eq!(recover_mnemonic_code(&urs).unwrap(), mnemonic_code);
+
+        assert_eq!(
+            "2of3".parse::<GroupSpec>(),
+            Err(SskrError::InvalidGroup("2of3".to_string()))
+        );
+    }
+}
@@ -0,0 +1,851 @@
+//! Contains converters between splitmonic's secret phrases and the shares of `ssss-split` and
+//! `ssss-combine`, see: http://point-at-infinity.org/ssss/
+//!
+//! `ssss` doesn't split secrets over GF(256) like splitmonic and Vault. It works in GF(2^n), where
+//! n is 8 times the length of the secret (so secrets can be 1 to 128 bytes), and the polynomial it
+//! splits the secret with has a leading coefficient of 1. Secrets of 8 bytes or more also go
+//! through a diffusion layer (XTEA with a zero key) before they're split. So `ssss` shares can't be
+//! re-arranged into splitmonic's shares like Vault's, converting combines them into the secret and
+//! splits it again.
+//!
+//! `ssss` shares are written as `<index>-<hex>`, with the index zero padded to the width of the
+//! number of shares, or as `<token>-<index>-<hex>` when `ssss-split` was given a token. The shares
+//! don't record the threshold, it has to be given when importing them.
+//!
+//! The secret is read and written as bytes, the way `ssss-split -x` and `ssss-combine -x` read and
+//! show it as hex.
+
+use crate::secret;
+use bip39::{Language, Mnemonic};
+use rand::{thread_rng, RngCore};
+use std::collections::HashSet;
+use thiserror::Error;
+use zeroize::Zeroize;
+
+/// `ssss` has fields from GF(2^8) to GF(2^1024)
+const MAX_SECRET_LENGTH: usize = 128;
+
+/// Secrets at least this long go through the diffusion layer, which works on 8 bytes at a time
+const DIFFUSION_LENGTH: usize = 8;
+
+/// The diffusion layer is run from every other byte of the secret, this many times over
+const DIFFUSION_ROUNDS: usize = 40;
+
+const XTEA_DELTA: u32 = 0x9e37_79b9;
+const XTEA_CYCLES: u32 = 32;
+
+// the exponents between n and 0 of the irreducible pentanomials x^n + x^a + x^b + x^c + 1 that
+// `ssss` uses for GF(2^n), for n = 8, 16, ... 1024. Each is the irreducible pentanomial with the
+// smallest exponents, the first is the AES polynomial
+#[rustfmt::skip]
+const IRREDUCIBLE: [[u8; 3]; MAX_SECRET_LENGTH] = [
+    [4, 3, 1], [5, 3, 1], [4, 3, 1], [7, 3, 2], [5, 4, 3], [5, 3, 2], [7, 4, 2], [4, 3, 1],
+    [10, 9, 3], [9, 4, 2], [7, 6, 2], [10, 9, 6], [4, 3, 1], [5, 4, 3], [4, 3, 1], [7, 2, 1],
+    [5, 3, 2], [7, 4, 2], [6, 3, 2], [5, 3, 2], [15, 3, 2], [11, 3, 2], [9, 8, 7], [7, 2, 1],
+    [5, 3, 2], [9, 3, 1], [7, 3, 1], [9, 8, 3], [9, 4, 2], [8, 5, 3], [15, 14, 10], [10, 5, 2],
+    [9, 6, 2], [9, 3, 2], [9, 5, 2], [11, 10, 1], [7, 3, 2], [11, 2, 1], [9, 7, 4], [4, 3, 1],
+    [8, 3, 1], [7, 4, 1], [7, 2, 1], [13, 11, 6], [5, 3, 2], [7, 3, 2], [8, 7, 5], [12, 3, 2],
+    [13, 10, 6], [5, 3, 2], [5, 3, 2], [9, 5, 2], [9, 7, 2], [13, 4, 3], [4, 3, 1], [11, 6, 4],
+    [18, 9, 6], [19, 18, 13], [11, 3, 2], [15, 9, 6], [4, 3, 1], [16, 5, 2], [15, 14, 6],
+    [8, 5, 2], [15, 11, 2], [11, 6, 2], [7, 5, 3], [8, 3, 1], [19, 16, 9], [11, 9, 6], [15, 7, 6],
+    [13, 4, 3], [14, 13, 3], [13, 6, 3], [9, 5, 2], [19, 13, 6], [19, 10, 3], [11, 6, 5],
+    [9, 2, 1], [14, 3, 2], [13, 3, 1], [7, 5, 4], [11, 9, 8], [11, 6, 5], [23, 16, 9], [19, 14, 6],
+    [23, 10, 2], [8, 3, 2], [5, 4, 3], [9, 6, 4], [4, 3, 2], [13, 8, 6], [13, 11, 1], [13, 10, 3],
+    [11, 6, 5], [19, 17, 4], [15, 14, 7], [13, 9, 6], [9, 7, 3], [9, 7, 1], [14, 3, 2], [11, 8, 2],
+    [11, 6, 4], [13, 5, 2], [11, 5, 1], [11, 4, 1], [19, 10, 3], [21, 10, 6], [13, 3, 1],
+    [15, 7, 5], [19, 18, 10], [7, 5, 3], [12, 7, 2], [7, 5, 1], [14, 9, 6], [10, 3, 2],
+    [15, 13, 12], [12, 11, 9], [16, 9, 7], [12, 9, 3], [9, 5, 2], [17, 10, 6], [24, 9, 3],
+    [17, 15, 13], [5, 4, 3], [19, 17, 8], [15, 6, 3], [19, 6, 1],
+];
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SsssError {
+    #[error("ssss share number {} is not an ssss share, they look like 1-3f0a... or token-1-3f0a...", .0 + 1)]
+    InvalidShare(usize),
+
+    #[error("ssss share number {} has an index of 0, which is never a valid share", .0 + 1)]
+    ZeroIndex(usize),
+
+    #[error("ssss share number {} is too long, ssss shares are at most {} hex characters, found: {}", .0 + 1, MAX_SECRET_LENGTH * 2, .1)]
+    TooLong(usize, usize),
+
+    #[error("all the shares must be the same length")]
+    MismatchedLengths,
+
+    #[error("more than one share has the index {0}")]
+    DuplicateShare(u8),
+
+    #[error(
+        "invalid secret length, ssss can split secrets between 1 and {} bytes, found: {0}",
+        MAX_SECRET_LENGTH
+    )]
+    InvalidSecretLength(usize),
+}
+
+/// Splits a secret the way `ssss-split -x` does, into `number_of_shares` shares which `threshold`
+/// of can be combined by `ssss-combine -x`
+pub fn split_secret(
+    secret: &[u8],
+    threshold: u8,
+    number_of_shares: u8,
+) -> Result<Vec<String>, crate::Error> {
+    split_secret_with(secret, threshold, number_of_shares, &mut thread_rng())
+}
+
+// the coefficients of the polynomial after the secret are read from `rng`, lowest first
+fn split_secret_with<R: RngCore>(
+    secret: &[u8],
+    threshold: u8,
+    number_of_shares: u8,
+    rng: &mut R,
+) -> Result<Vec<String>, crate::Error> {
+    if threshold < 2 || threshold > number_of_shares {
+        return Err(crate::Error::InvalidThreshold {
+            threshold,
+            number_of_shares,
+        });
+    }
+
+    if secret.is_empty() || secret.len() > MAX_SECRET_LENGTH {
+        return Err(SsssError::InvalidSecretLength(secret.len()).into());
+    }
+
+    let field = Field::new(secret.len());
+
+    let mut diffused = secret.to_vec();
+    if diffused.len() >= DIFFUSION_LENGTH {
+        diffuse(&mut diffused, Direction::Forwards);
+    }
+
+    let mut coefficients = Vec::with_capacity(threshold as usize);
+    coefficients.push(field.element(&diffused));
+    diffused.zeroize();
+
+    for _ in 1..threshold {
+        let mut random = vec![0; secret.len()];
+        rng.fill_bytes(&mut random);
+        coefficients.push(field.element(&random));
+        random.zeroize();
+    }
+
+    let width = number_of_shares.to_string().len();
+    let shares = (1..=number_of_shares)
+        .map(|index| {
+            let x = field.element(&[index]);
+
+            // Horner's method, starting from the leading coefficient of 1
+            let mut y = x.clone();
+            for coefficient in coefficients[1..].iter().rev() {
+                xor_into(&mut y, coefficient);
+                y = field.mul(&y, &x);
+            }
+            xor_into(&mut y, &coefficients[0]);
+
+            let mut y_bytes = field.to_bytes(&y);
+            let share = format!("{:0width$}-{}", index, hex::encode(&y_bytes), width = width);
+            y.zeroize();
+            y_bytes.zeroize();
+
+            share
+        })
+        .collect();
+    coefficients.zeroize();
+
+    Ok(shares)
+}
+
+/// Combines `threshold` ssss shares the way `ssss-combine -x` does and returns the secret, when
+/// more shares are given only the first `threshold` are used
+pub fn recover_secret(ssss_shares: &[String], threshold: u8) -> Result<Vec<u8>, crate::Error> {
+    if threshold < 2 {
+        return Err(crate::Error::InvalidThreshold {
+            threshold,
+            number_of_shares: u8::MAX,
+        });
+    }
+
+    let mut shares = ssss_shares
+        .iter()
+        .enumerate()
+        .map(|(index, ssss_share)| parse_share(index, ssss_share))
+        .collect::<Result<Vec<(u8, Vec<u8>)>, SsssError>>()?;
+
+    let checked = check_shares(&shares, threshold);
+    let secret = checked.map(|_| combine_shares(&shares[..threshold as usize]));
+    for (_, y) in shares.iter_mut() {
+        y.zeroize();
+    }
+
+    secret
+}
+
+/// Converts secret phrases, at least the threshold of them from the same set, into
+/// `number_of_shares` ssss shares, which `threshold` of can recover the secret
+pub fn secret_phrases_to_ssss_shares(
+    secret_phrases: &[String],
+    threshold: u8,
+    number_of_shares: u8,
+) -> Result<Vec<String>, crate::Error> {
+    let mut secret = secret::recover_secret(secret_phrases.to_vec())?;
+    let ssss_shares = split_secret(&secret, threshold, number_of_shares);
+    secret.zeroize();
+
+    ssss_shares
+}
+
+/// Converts split phrases, at least 3 from the same set, into `number_of_shares` ssss shares of the
+/// entropy of the mnemonic code, which `threshold` of can recover it
+pub fn split_phrases_to_ssss_shares(
+    split_phrases: &[String],
+    threshold: u8,
+    number_of_shares: u8,
+) -> Result<Vec<String>, crate::Error> {
+    let mut mnemonic_code = crate::recover_mnemonic_code(split_phrases.to_vec())?;
+    let mnemonic = Mnemonic::parse_in(Language::English, &mnemonic_code);
+    mnemonic_code.zeroize();
+
+    let mut entropy = mnemonic?.to_entropy();
+    let ssss_shares = split_secret(&entropy, threshold, number_of_shares);
+    entropy.zeroize();
+
+    ssss_shares
+}
+
+/// Converts ssss shares, at least `threshold` of them, into `number_of_shares` secret phrases which
+/// `threshold` of can recover the secret
+pub fn ssss_shares_to_secret_phrases(
+    ssss_shares: &[String],
+    threshold: u8,
+    number_of_shares: u8,
+) -> Result<Vec<String>, crate::Error> {
+    let mut secret = recover_secret(ssss_shares, threshold)?;
+    let secret_phrases = secret::split_secret(&secret, threshold, number_of_shares);
+    secret.zeroize();
+
+    secret_phrases
+}
+
+// reads a share written as `<index>-<hex>` or `<token>-<index>-<hex>`, `index` is the position of
+// the share in its set, used in errors
+fn parse_share(index: usize, ssss_share: &str) -> Result<(u8, Vec<u8>), SsssError> {
+    let mut parts = ssss_share.trim().rsplitn(3, '-');
+
+    let (y, x) = match (parts.next(), parts.next()) {
+        (Some(y), Some(x)) => (y, x),
+        _ => return Err(SsssError::InvalidShare(index)),
+    };
+
+    let x = x
+        .parse::<u8>()
+        .map_err(|_| SsssError::InvalidShare(index))?;
+    if x == 0 {
+        return Err(SsssError::ZeroIndex(index));
+    }
+
+    if y.len() > MAX_SECRET_LENGTH * 2 {
+        return Err(SsssError::TooLong(index, y.len()));
+    }
+
+    match hex::decode(y) {
+        Ok(y) if !y.is_empty() => Ok((x, y)),
+        _ => Err(SsssError::InvalidShare(index)),
+    }
+}
+
+fn check_shares(shares: &[(u8, Vec<u8>)], threshold: u8) -> Result<(), crate::Error> {
+    if shares.len() < threshold as usize {
+        return Err(crate::Error::NotEnoughShares {
+            gave: shares.len(),
+            expected: threshold,
+        });
+    }
+
+    let mut xs = HashSet::with_capacity(shares.len());
+    for (x, y) in shares {
+        if y.len() != shares[0].1.len() {
+            return Err(SsssError::MismatchedLengths.into());
+        }
+
+        if !xs.insert(*x) {
+            return Err(SsssError::DuplicateShare(*x).into());
+        }
+    }
+
+    Ok(())
+}
+
+// the shares are points on x^t + Q(x), where Q has a degree of t - 1 and Q(0) is the secret, so
+// the secret is found by lagrange interpolation of the shares with x^t taken away
+fn combine_shares(shares: &[(u8, Vec<u8>)]) -> Vec<u8> {
+    let field = Field::new(shares[0].1.len());
+    let threshold = shares.len();
+
+    let mut secret = field.element(&[0]);
+    for (i, (x_i, y_i)) in shares.iter().enumerate() {
+        let x = field.element(&[*x_i]);
+
+        let mut numerator = field.element(&[1]);
+        let mut denominator = field.element(&[1]);
+        for (j, (x_j, _)) in shares.iter().enumerate() {
+            if i != j {
+                numerator = field.mul(&numerator, &field.element(&[*x_j]));
+                denominator = field.mul(&denominator, &field.element(&[x_i ^ x_j]));
+            }
+        }
+
+        let mut term = field.element(y_i);
+        xor_into(&mut term, &field.pow(&x, threshold));
+        term = field.mul(&term, &numerator);
+        term = field.mul(&term, &field.invert(&denominator));
+
+        xor_into(&mut secret, &term);
+        term.zeroize();
+    }
+
+    let mut bytes = field.to_bytes(&secret);
+    secret.zeroize();
+
+    if bytes.len() >= DIFFUSION_LENGTH {
+        diffuse(&mut bytes, Direction::Backwards);
+    }
+
+    bytes
+}
+
+/// GF(2^n) the way `ssss` implements it, elements are big endian numbers of n / 8 bytes, held as
+/// 64 bit limbs with the least significant first and room for bit n
+struct Field {
+    degree: usize,
+    polynomial: Vec<u64>,
+}
+
+impl Field {
+    fn new(secret_length: usize) -> Self {
+        let degree = secret_length * 8;
+        let [a, b, c] = IRREDUCIBLE[secret_length - 1];
+
+        let mut polynomial = vec![0; degree / 64 + 1];
+        for exponent in &[degree, a as usize, b as usize, c as usize, 0] {
+            polynomial[exponent / 64] |= 1 << (exponent % 64);
+        }
+
+        Self { degree, polynomial }
+    }
+
+    fn element(&self, bytes: &[u8]) -> Vec<u64> {
+        let mut element = vec![0; self.polynomial.len()];
+        for (index, byte) in bytes.iter().rev().enumerate() {
+            element[index / 8] |= (*byte as u64) << (index % 8 * 8);
+        }
+
+        element
+    }
+
+    fn to_bytes(&self, element: &[u64]) -> Vec<u8> {
+        (0..self.degree / 8)
+            .rev()
+            .map(|index| (element[index / 8] >> (index % 8 * 8)) as u8)
+            .collect()
+    }
+
+    fn mul(&self, x: &[u64], y: &[u64]) -> Vec<u64> {
+        let mut shifted = x.to_vec();
+        let mut product = vec![0; self.polynomial.len()];
+
+        for bit in 0..self.degree {
+            if bit > 0 {
+                shift_left(&mut shifted);
+                if is_set(&shifted, self.degree) {
+                    xor_into(&mut shifted, &self.polynomial);
+                }
+            }
+
+            if is_set(y, bit) {
+                xor_into(&mut product, &shifted);
+            }
+        }
+        shifted.zeroize();
+
+        product
+    }
+
+    fn pow(&self, x: &[u64], exponent: usize) -> Vec<u64> {
+        (0..exponent).fold(self.element(&[1]), |power, _| self.mul(&power, x))
+    }
+
+    // x^(2^n - 2), which is x^-1 as x^(2^n - 1) is 1
+    fn invert(&self, x: &[u64]) -> Vec<u64> {
+        let mut inverse = self.element(&[1]);
+        let mut square = x.to_vec();
+
+        for _ in 1..self.degree {
+            square = self.mul(&square, &square);
+            inverse = self.mul(&inverse, &square);
+        }
+        square.zeroize();
+
+        inverse
+    }
+}
+
+fn xor_into(element: &mut [u64], other: &[u64]) {
+    for (limb, other_limb) in element.iter_mut().zip(other) {
+        *limb ^= other_limb
+    }
+}
+
+fn is_set(element: &[u64], bit: usize) -> bool {
+    element[bit / 64] >> (bit % 64) & 1 == 1
+}
+
+fn shift_left(element: &mut [u64]) {
+    for index in (0..element.len()).rev() {
+        let carry = if index > 0 {
+            element[index - 1] >> 63
+        } else {
+            0
+        };
+        element[index] = element[index] << 1 | carry;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    Forwards,
+    Backwards,
+}
+
+/// Runs the diffusion layer over a secret of at least 8 bytes, forwards before splitting it and
+/// backwards after combining it. XTEA is run over 8 bytes starting at every other byte, wrapping
+/// around the end, 40 times over. The bytes are in the order `ssss` exports the secret in, 16 bit
+/// words with the least significant first, each word big endian.
+fn diffuse(secret: &mut [u8], direction: Direction) {
+    let mut words = to_word_order(secret);
+    let offsets = (0..DIFFUSION_ROUNDS * secret.len()).step_by(2);
+
+    match direction {
+        Direction::Forwards => offsets.for_each(|offset| run_xtea(&mut words, offset, encipher)),
+        Direction::Backwards => offsets
+            .rev()
+            .for_each(|offset| run_xtea(&mut words, offset, decipher)),
+    }
+
+    let mut diffused = from_word_order(&words);
+    secret.copy_from_slice(&diffused);
+    words.zeroize();
+    diffused.zeroize();
+}
+
+// an odd number of bytes leaves the most significant word with only a low byte, it's moved into
+// the high byte of that word so the bytes are contiguous
+fn to_word_order(secret: &[u8]) -> Vec<u8> {
+    let mut padded = vec![0; secret.len() % 2];
+    padded.extend_from_slice(secret);
+
+    let mut words = padded.rchunks(2).flatten().copied().collect::<Vec<u8>>();
+    padded.zeroize();
+
+    if secret.len() % 2 == 1 {
+        words[secret.len() - 1] = words[secret.len()];
+        words.truncate(secret.len());
+    }
+
+    words
+}
+
+fn from_word_order(words: &[u8]) -> Vec<u8> {
+    let mut padded = words.to_vec();
+    if words.len() % 2 == 1 {
+        padded.push(words[words.len() - 1]);
+        padded[words.len() - 1] = 0;
+    }
+
+    let secret = padded
+        .chunks(2)
+        .rev()
+        .flatten()
+        .skip(words.len() % 2)
+        .copied()
+        .collect();
+    padded.zeroize();
+
+    secret
+}
+
+fn run_xtea(words: &mut [u8], offset: usize, cipher: fn(&mut [u32; 2])) {
+    let length = words.len();
+    let byte = |index: usize| words[(offset + index) % length] as u32;
+
+    let mut block = [
+        byte(0) << 24 | byte(1) << 16 | byte(2) << 8 | byte(3),
+        byte(4) << 24 | byte(5) << 16 | byte(6) << 8 | byte(7),
+    ];
+    cipher(&mut block);
+
+    for (index, value) in block.iter().flat_map(|half| half.to_be_bytes()).enumerate() {
+        words[(offset + index) % length] = value;
+    }
+    block.zeroize();
+}
+
+// XTEA with a key of all zeros
+fn encipher(block: &mut [u32; 2]) {
+    let mut sum = 0u32;
+
+    for _ in 0..XTEA_CYCLES {
+        block[0] = block[0].wrapping_add(mix(block[1]) ^ sum);
+        sum = sum.wrapping_add(XTEA_DELTA);
+        block[1] = block[1].wrapping_add(mix(block[0]) ^ sum);
+    }
+}
+
+fn decipher(block: &mut [u32; 2]) {
+    let mut sum = XTEA_DELTA.wrapping_mul(XTEA_CYCLES);
+
+    for _ in 0..XTEA_CYCLES {
+        block[1] = block[1].wrapping_sub(mix(block[0]) ^ sum);
+        sum = sum.wrapping_sub(XTEA_DELTA);
+        block[0] = block[0].wrapping_sub(mix(block[1]) ^ sum);
+    }
+}
+
+fn mix(half: u32) -> u32 {
+    ((half << 4) ^ (half >> 5)).wrapping_add(half)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC_CODE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+
+    // shares 1 to 4 of the example on the ssss homepage, `ssss-split -t 3 -n 5` of the secret
+    // `my secret root password` (23 bytes, so it went through the diffusion layer). Any 3 of them
+    // give `ssss-combine -x -t 3` the secret as hex, 6d792073656372657420726f6f742070617373776f7264
+    const SSSS_SECRET: &[u8] = b"my secret root password";
+    const SSSS_SHARES: [&str; 4] = [
+        "1-1c41ef496eccfbeba439714085df8437236298da8dd824",
+        "2-fbc74a03a50e14ab406c225afb5f45c40ae11976d2b665",
+        "3-fa1c3a9c6df8af0779c36de6c33f6e36e989d0e0b91309",
+        "4-468de7d6eb36674c9cf008c8e8fc8c566537ad6301eb9e",
+    ];
+
+    // the random coefficients of the polynomial the ssss homepage example was split with, worked
+    // out from its shares
+    const SSSS_COEFFICIENTS: [&str; 2] = [
+        "af8806b30fd2cb1a0b6170e6ce901e5ce163ed10bb1b02",
+        "ae53762cc72470b632ce3f5af6f035ae020b2486d0be69",
+    ];
+
+    struct FakeRng(Vec<u8>);
+
+    impl RngCore for FakeRng {
+        fn next_u32(&mut self) -> u32 {
+            let mut bytes = [0; 4];
+            self.fill_bytes(&mut bytes);
+            u32::from_le_bytes(bytes)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let mut bytes = [0; 8];
+            self.fill_bytes(&mut bytes);
+            u64::from_le_bytes(bytes)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            let rest = self.0.split_off(dest.len());
+            dest.copy_from_slice(&self.0);
+            self.0 = rest;
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    fn strings(strs: &[&str]) -> Vec<String> {
+        strs.iter().map(ToString::to_string).collect()
+    }
+
+    fn degree(polynomial: &[u64]) -> Option<usize> {
+        polynomial
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, limb)| **limb != 0)
+            .map(|(index, limb)| index * 64 + 63 - limb.leading_zeros() as usize)
+    }
+
+    fn gcd(mut a: Vec<u64>, mut b: Vec<u64>) -> Vec<u64> {
+        while let Some(b_degree) = degree(&b) {
+            while let Some(a_degree) = degree(&a).filter(|a_degree| *a_degree >= b_degree) {
+                for bit in (0..=b_degree).filter(|bit| is_set(&b, *bit)) {
+                    let shifted = bit + a_degree - b_degree;
+                    a[shifted / 64] ^= 1 << (shifted % 64);
+                }
+            }
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        a
+    }
+
+    // Rabin's test, the polynomial f of degree n is irreducible if x^(2^n) = x mod f, and for every
+    // prime p dividing n, x^(2^(n/p)) - x and f have no common factor
+    fn is_irreducible(field: &Field) -> bool {
+        let n = field.degree;
+        let x = field.element(&[2]);
+        let one = field.element(&[1]);
+
+        let primes = (2..=n)
+            .filter(|p| n.is_multiple_of(*p) && (2..*p).all(|q| !p.is_multiple_of(q)))
+            .collect::<Vec<usize>>();
+
+        let mut power = x.clone();
+        for squarings in 1..=n {
+            power = field.mul(&power, &power);
+
+            if primes.iter().any(|p| squarings == n / p) {
+                let mut difference = power.clone();
+                xor_into(&mut difference, &x);
+
+                if gcd(field.polynomial.clone(), difference) != one {
+                    return false;
+                }
+            }
+        }
+
+        power == x
+    }
+
+    #[test]
+    fn uses_irreducible_polynomials() {
+        // the fields of secrets up to 32 bytes (every mnemonic) and the largest field
+        for secret_length in (1..=32).chain(std::iter::once(MAX_SECRET_LENGTH)) {
+            assert!(
+                is_irreducible(&Field::new(secret_length)),
+                "GF(2^{})",
+                secret_length * 8
+            );
+        }
+
+        // x^8 + x^3 + x^2 + x + 1 is (x^3 + x^2 + 1)(x^5 + x^4 + x^3 + x + 1)
+        let reducible = Field {
+            degree: 8,
+            polynomial: vec![0b1_0000_1111, 0],
+        };
+        assert!(!is_irreducible(&reducible));
+    }
+
+    #[test]
+    fn multiplies_and_inverts_in_gf256_like_aes() {
+        let field = Field::new(1);
+
+        // from the AES specification (FIPS 197, section 4.2)
+        assert_eq!(
+            field.to_bytes(&field.mul(&field.element(&[0x57]), &field.element(&[0x83]))),
+            vec![0xc1]
+        );
+
+        for byte in 1..=255 {
+            let x = field.element(&[byte]);
+            assert_eq!(field.mul(&x, &field.invert(&x)), field.element(&[1]));
+        }
+    }
+
+    #[test]
+    fn splits_with_a_polynomial_with_a_leading_coefficient_of_1() {
+        let shares = split_secret(&[0x2a], 2, 3).unwrap();
+        let ys = shares
+            .iter()
+            .map(|share| hex::decode(&share[2..]).unwrap()[0])
+            .collect::<Vec<u8>>();
+
+        // y = x^2 + ax + secret, x^2 is 1, 4 and 5 for x = 1, 2 and 3. 1 ^ 2 ^ 3 is 0, so the ax
+        // terms cancel out and the 3 shares without their x^2 add up to the secret
+        assert_eq!((ys[0] ^ 1) ^ (ys[1] ^ 4) ^ (ys[2] ^ 5), 0x2a);
+    }
+
+    #[test]
+    fn undoes_the_diffusion_layer() {
+        for length in &[8, 9, 16, 17, 33, 128] {
+            let secret = (0..*length).map(|byte| byte as u8).collect::<Vec<u8>>();
+
+            let words = to_word_order(&secret);
+            assert_eq!(words.len(), secret.len());
+            assert_eq!(from_word_order(&words), secret);
+
+            let mut diffused = secret.clone();
+            diffuse(&mut diffused, Direction::Forwards);
+            assert_ne!(diffused, secret);
+
+            diffuse(&mut diffused, Direction::Backwards);
+            assert_eq!(diffused, secret);
+        }
+
+        // 0x0102 and 0x0304 are written least significant word first
+        assert_eq!(to_word_order(&[1, 2, 3, 4]), vec![3, 4, 1, 2]);
+        assert_eq!(to_word_order(&[1, 2, 3]), vec![2, 3, 1]);
+
+        let mut block = [0x0123_4567, 0x89ab_cdef];
+        encipher(&mut block);
+        assert_ne!(block, [0x0123_4567, 0x89ab_cdef]);
+        decipher(&mut block);
+        assert_eq!(block, [0x0123_4567, 0x89ab_cdef]);
+    }
+
+    #[test]
+    fn recovers_shares_made_by_ssss_split() {
+        for left_out in 0..SSSS_SHARES.len() {
+            let shares = SSSS_SHARES
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| *index != left_out)
+                .map(|(_, share)| share.to_string())
+                .collect::<Vec<String>>();
+
+            assert_eq!(recover_secret(&shares, 3).unwrap(), SSSS_SECRET);
+        }
+
+        // `ssss-split -w` only puts the token in front of the share
+        let shares = SSSS_SHARES[1..]
+            .iter()
+            .map(|share| format!("root-{}", share))
+            .collect::<Vec<String>>();
+        assert_eq!(recover_secret(&shares, 3).unwrap(), SSSS_SECRET);
+
+        let secret_phrases =
+            ssss_shares_to_secret_phrases(&strings(&SSSS_SHARES[..3]), 3, 5).unwrap();
+        assert_eq!(
+            secret::recover_secret(secret_phrases[2..].to_vec()).unwrap(),
+            SSSS_SECRET
+        );
+    }
+
+    #[test]
+    fn splits_like_ssss_split() {
+        let mut rng = FakeRng(
+            SSSS_COEFFICIENTS
+                .iter()
+                .flat_map(|c| hex::decode(c).unwrap())
+                .collect(),
+        );
+
+        // with the same coefficients the shares are the ones ssss-split made, which ssss-combine
+        // turns back into the secret
+        assert_eq!(
+            split_secret_with(SSSS_SECRET, 3, 4, &mut rng).unwrap(),
+            strings(&SSSS_SHARES)
+        );
+    }
+
+    #[test]
+    fn splits_and_recovers_secrets() {
+        for length in &[1, 7, 8, 9, 16, 31, 32, 33, 64] {
+            let secret = (0..*length)
+                .map(|byte| (byte * 37 + 11) as u8)
+                .collect::<Vec<u8>>();
+
+            let shares = split_secret(&secret, 3, 5).unwrap();
+            assert_eq!(shares.len(), 5);
+            assert!(shares[4].starts_with("5-"));
+            assert_eq!(shares[0].len(), 2 + length * 2);
+
+            assert_eq!(recover_secret(&shares[2..], 3).unwrap(), secret);
+            assert_eq!(
+                recover_secret(
+                    &[shares[4].clone(), shares[0].clone(), shares[2].clone()],
+                    3
+                )
+                .unwrap(),
+                secret
+            );
+            assert_eq!(
+                recover_secret(&shares[..2], 3),
+                Err(crate::Error::NotEnoughShares {
+                    gave: 2,
+                    expected: 3
+                })
+            );
+
+            // the threshold is part of the polynomial, the wrong one doesn't recover the secret
+            assert_ne!(recover_secret(&shares[..2], 2).unwrap(), secret);
+        }
+    }
+
+    #[test]
+    fn reads_shares_with_tokens_and_padded_indexes() {
+        let shares = split_secret(b"correct horse battery staple", 2, 12).unwrap();
+        assert!(shares[0].starts_with("01-"));
+        assert!(shares[11].starts_with("12-"));
+
+        let shares = vec![
+            format!("backup-{}", shares[10]),
+            format!("  {}\n", shares[0].to_uppercase()),
+        ];
+        assert_eq!(
+            recover_secret(&shares, 2).unwrap(),
+            b"correct horse battery staple"
+        );
+    }
+
+    #[test]
+    fn converts_secret_phrases() {
+        let secret_phrases = secret::split_secret(b"hunter2", 2, 3).unwrap();
+
+        let ssss_shares = secret_phrases_to_ssss_shares(&secret_phrases[1..], 3, 5).unwrap();
+        assert_eq!(recover_secret(&ssss_shares[1..4], 3).unwrap(), b"hunter2");
+
+        let imported = ssss_shares_to_secret_phrases(&ssss_shares[..3], 3, 4).unwrap();
+        assert_eq!(imported.len(), 4);
+        assert_eq!(
+            secret::recover_secret(imported[1..].to_vec()).unwrap(),
+            b"hunter2"
+        );
+        assert!(secret::recover_secret(imported[..2].to_vec()).is_err());
+    }
+
+    #[test]
+    fn converts_split_phrases() {
+        let split_phrases = crate::get_split_phrases(MNEMONIC_CODE.to_string()).unwrap();
+        let ssss_shares = split_phrases_to_ssss_shares(&split_phrases[2..], 2, 3).unwrap();
+
+        let entropy = recover_secret(&ssss_shares[1..], 2).unwrap();
+        assert_eq!(
+            Mnemonic::from_entropy(&entropy).unwrap().to_string(),
+            MNEMONIC_CODE
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_shares() {
+        assert_eq!(parse_share(0, "0a1b"), Err(SsssError::InvalidShare(0)));
+        assert_eq!(parse_share(1, "1-xyz"), Err(SsssError::InvalidShare(1)));
+        assert_eq!(parse_share(1, "1-abc"), Err(SsssError::InvalidShare(1)));
+        assert_eq!(parse_share(2, "256-ab"), Err(SsssError::InvalidShare(2)));
+        assert_eq!(parse_share(0, "0-ab"), Err(SsssError::ZeroIndex(0)));
+        assert_eq!(
+            parse_share(0, &format!("1-{}", "ab".repeat(129))),
+            Err(SsssError::TooLong(0, 258))
+        );
+        assert_eq!(parse_share(0, "3-0A1b"), Ok((3, vec![0x0a, 0x1b])));
+
+        let shares = |shares: &[&str]| shares.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            recover_secret(&shares(&["1-ab", "2-abcd"]), 2),
+            Err(SsssError::MismatchedLengths.into())
+        );
+        assert_eq!(
+            recover_secret(&shares(&["1-ab", "01-cd"]), 2),
+            Err(SsssError::DuplicateShare(1).into())
+        );
+        assert!(recover_secret(&shares(&["1-ab", "2-cd"]), 1).is_err());
+
+        assert_eq!(
+            split_secret(&[0; 129], 2, 3),
+            Err(SsssError::InvalidSecretLength(129).into())
+        );
+        assert!(split_secret(b"hunter2", 4, 3).is_err());
+    }
+}
@@ -1,12 +1,19 @@
+use bip39::{Language, Mnemonic};
 use thiserror::Error;
 
 use crate::wordlist::{English, Wordlist};
 
+/// The valid lengths of mnemonic codes
+pub const MNEMONIC_LENGTHS: [usize; 3] = [12, 18, 24];
+
+/// The valid lengths of split phrases, for 12, 18 and 24 word mnemonic codes
+pub const SPLIT_PHRASE_LENGTHS: [usize; 3] = [16, 22, 28];
+
 #[derive(Debug, Error, PartialEq, Clone)]
 pub enum Error {
     #[error("this mnemonic length is invalid, expected {expected:?}, found: {given:?}\nmnemonic: {mnemonic:?}")]
     MnemonicLength {
-        expected: usize,
+        expected: Vec<usize>,
         given: usize,
         mnemonic: String,
     },
@@ -28,7 +35,7 @@ pub enum Error {
     },
 
     #[error("found invalid split phrase lengths, the following phrases weren't long enough: {invalid_phrases:?}\n\
-    they were expected to all be 28 words long. Instead they were of lengths: {invalid_phrase_lengths:?}")]
+    they were expected to all be 28 words long (16 or 22 words for 12 or 18 word mnemonics). Instead they were of lengths: {invalid_phrase_lengths:?}")]
     PhraseLength {
         invalid_phrase_lengths: Vec<usize>,
         invalid_phrases: Vec<String>,
@@ -38,6 +45,15 @@ pub enum Error {
     #[error("invalid words in split phrases: {0:?}")]
     InvalidSplitPhraseWords(Vec<(usize, Error)>),
 
+    #[error("this mnemonic has an invalid checksum, one or more of the words are wrong or out of order\nmnemonic: {mnemonic:?}")]
+    Checksum { mnemonic: String },
+
+    #[error("invalid checksum in split phrase number(s): {}, one or more of the words in these phrases are wrong or out of order", phrase_numbers(.invalid_phrases))]
+    SplitPhraseChecksum {
+        invalid_phrases: Vec<usize>,
+        all_phrases: String,
+    },
+
     #[error("mismatched set(s), expected: {expected:?}, found: {given:?}")]
     MismatchedSet {
         expected: String,
@@ -45,12 +61,18 @@ pub enum Error {
     },
 }
 
+/// When given all but the last word of a mnemonic code, returns every last word that gives the
+/// mnemonic code a valid checksum. Returns `None` if the words can't be the start of a mnemonic code.
+pub fn valid_last_words(words: &[&str]) -> Option<Vec<&'static str>> {
+    Mnemonic::valid_last_words(Language::English, words).ok()
+}
+
 pub fn validate_mnemonic_code(mnemonic: &str) -> Result<(), Error> {
     let mnemonic_vec: Vec<&str> = mnemonic.split(' ').collect();
 
-    if mnemonic_vec.len() != 24 {
+    if !MNEMONIC_LENGTHS.contains(&mnemonic_vec.len()) {
         return Err(Error::MnemonicLength {
-            expected: 24,
+            expected: MNEMONIC_LENGTHS.to_vec(),
             given: mnemonic_vec.len(),
             mnemonic: mnemonic.to_string(),
         });
@@ -58,6 +80,12 @@ pub fn validate_mnemonic_code(mnemonic: &str) -> Result<(), Error> {
 
     validate_all_correct_words(&mnemonic_vec)?;
 
+    if !is_valid_checksum(&mnemonic_vec) {
+        return Err(Error::Checksum {
+            mnemonic: mnemonic.to_string(),
+        });
+    }
+
     Ok(())
 }
 
@@ -77,6 +105,7 @@ pub fn validate_split_phrases(split_phrases: Vec<String>) -> Result<(), Error> {
 
     validate_lengths_of_phrases(&split_phrases_vec)?;
     validate_words_in_phrases(&split_phrases_vec)?;
+    validate_checksums_of_phrases(&split_phrases_vec)?;
     validate_part_of_same_set(&split_phrases_vec)?;
 
     Ok(())
@@ -108,8 +137,15 @@ fn validate_lengths_of_phrases(split_phrases: &[Vec<&str>]) -> Result<(), Error>
     let mut invalid_phrase_lengths = vec![];
     let mut invalid_phrases = vec![];
 
+    // all the split phrases in a set are the same length
+    let expected_length = split_phrases
+        .iter()
+        .map(Vec::len)
+        .find(|len| SPLIT_PHRASE_LENGTHS.contains(len))
+        .unwrap_or(28);
+
     for phrases in split_phrases {
-        if phrases.len() != 28 {
+        if phrases.len() != expected_length {
             invalid_phrases.push(phrases.join(" "));
             invalid_phrase_lengths.push(phrases.len());
         }
@@ -121,7 +157,6 @@ fn validate_lengths_of_phrases(split_phrases: &[Vec<&str>]) -> Result<(), Error>
             invalid_phrases,
             all_phrases: split_phrases
                 .iter()
-                .cloned()
                 .map(|phrases| phrases.join(" "))
                 .collect::<Vec<String>>()
                 .join("\n"),
@@ -147,6 +182,43 @@ fn validate_words_in_phrases(split_phrases: &[Vec<&str>]) -> Result<(), Error> {
     Ok(())
 }
 
+// the last 24 words of every split phrase are a standalone BIP39 mnemonic
+// so each one can be checked on its own, before trying to recover anything
+fn validate_checksums_of_phrases(split_phrases: &[Vec<&str>]) -> Result<(), Error> {
+    let invalid_phrases: Vec<usize> = split_phrases
+        .iter()
+        .enumerate()
+        .filter(|(_index, phrase)| !is_valid_checksum(&phrase[4..]))
+        .map(|(index, _phrase)| index)
+        .collect();
+
+    if !invalid_phrases.is_empty() {
+        return Err(Error::SplitPhraseChecksum {
+            invalid_phrases,
+            all_phrases: split_phrases
+                .iter()
+                .map(|phrases| phrases.join(" "))
+                .collect::<Vec<String>>()
+                .join("\n"),
+        });
+    }
+
+    Ok(())
+}
+
+fn is_valid_checksum(words: &[&str]) -> bool {
+    Mnemonic::parse_in(Language::English, words.join(" ")).is_ok()
+}
+
+// formats phrase indexes as the 1 based numbers shown to the user
+pub(crate) fn phrase_numbers(indexes: &[usize]) -> String {
+    indexes
+        .iter()
+        .map(|index| (index + 1).to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
 fn validate_part_of_same_set(split_phrases: &[Vec<&str>]) -> Result<(), Error> {
     let mut set_id = Vec::with_capacity(3);
     let mut mismatched_sets = vec![];
@@ -182,7 +254,7 @@ mod tests {
         assert_eq!(
             error,
             Error::MnemonicLength {
-                expected: 24,
+                expected: vec![12, 18, 24],
                 given: 4,
                 mnemonic: "this is a fail".to_string()
             }
@@ -190,10 +262,19 @@ mod tests {
 
         assert_eq!(
             error.to_string(),
-            "this mnemonic length is invalid, expected 24, found: 4\nmnemonic: \"this is a fail\""
+            "this mnemonic length is invalid, expected [12, 18, 24], found: 4\nmnemonic: \"this is a fail\""
         )
     }
 
+    #[test]
+    fn accepts_12_and_18_word_mnemonics() {
+        let mnemonic_12 = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let mnemonic_18 = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon agent";
+
+        assert_eq!(validate_mnemonic_code(mnemonic_12), Ok(()));
+        assert_eq!(validate_mnemonic_code(mnemonic_18), Ok(()));
+    }
+
     #[test]
     fn produces_error_on_wrong_words() {
         let mnemonic = "abandon abandon abandon abandon ford abandon abandon abandon abandon abandan abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon f150 art".to_string();
@@ -253,9 +334,67 @@ mod tests {
         )
     }
 
+    #[test]
+    fn produces_error_on_wrong_checksum() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+        let error = validate_mnemonic_code(mnemonic).unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::Checksum {
+                mnemonic: mnemonic.to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn produces_error_with_phrase_number_on_wrong_checksum() {
+        let phrases = vec![
+            "embody fog drop ability sword volume hat detail blue pride yard benefit coach primary now pledge head panel hour congress curtain plug over ordinary debris release tent coin".to_string(),
+            "embody fog drop about embrace visa adapt winner wine dash fabric snack drip auction deputy visit shift animal various bread country lecture assist marriage merit goat glove gravity".to_string(),
+            "embody fog drop able network accident hedgehog sibling toilet outdoor quick subway hurdle picture property false quit notable panther crucial already supply mother beef recycle spell rich enhance".to_string(),
+        ];
+
+        let error = validate_split_phrases(phrases.clone()).unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::SplitPhraseChecksum {
+                invalid_phrases: vec![1],
+                all_phrases: phrases.join("\n"),
+            }
+        );
+
+        assert!(error
+            .to_string()
+            .starts_with("invalid checksum in split phrase number(s): 2,"));
+    }
+
+    #[test]
+    fn produces_error_when_phrases_are_different_lengths() {
+        let phrases: Vec<Vec<&str>> = [
+            "embody fog drop ability sword volume hat detail blue pride yard benefit coach primary now pledge",
+            "embody fog drop ability sword volume hat detail blue pride yard benefit coach primary now pledge",
+            "embody fog drop ability sword volume hat detail blue pride yard benefit coach primary now pledge head panel hour congress curtain plug",
+        ]
+        .iter()
+        .map(|phrase| phrase.split(' ').collect())
+        .collect();
+
+        let error = validate_lengths_of_phrases(&phrases).unwrap_err();
+
+        assert!(matches!(
+            error,
+            Error::PhraseLength {
+                invalid_phrase_lengths,
+                ..
+            } if invalid_phrase_lengths == vec![22]
+        ))
+    }
+
     #[test]
     fn test_validate_part_of_same_set() {
-        let phrases: Vec<Vec<&str>> = vec![
+        let phrases: Vec<Vec<&str>> = [
             "hello hello hello some other random stuff",
             "hello hello hello more random stuff",
             "hello bad hello even more random stuff",
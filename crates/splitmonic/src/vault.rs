@@ -0,0 +1,341 @@
+//! Contains converters between splitmonic's shares and the shares HashiCorp Vault uses for its
+//! unseal and recovery keys, so secrets can be moved between the two without being recovered.
+//!
+//! Vault splits secrets with shamir secret sharing over the same GF(256) field splitmonic uses (the
+//! AES field), so a share only needs to be re-arranged. Vault appends the x coordinate of the share
+//! as the last byte, splitmonic puts it first:
+//!
+//! | Vault:      | y bytes | x (1 byte) |
+//! | splitmonic: | x (1 byte) | y bytes |
+//!
+//! Vault shares don't record the threshold, it has to be given when importing them.
+//!
+//! `ssss-split` shares can't be converted this way, see [crate::ssss] for converting those.
+
+use crate::{decode_split_shares, encoding::Words, secret};
+use std::collections::HashSet;
+use thiserror::Error;
+use zeroize::Zeroize;
+
+/// How Vault shares are written, Vault prints its keys as base64 and can also show them as hex
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VaultEncoding {
+    Base64,
+    Hex,
+}
+
+impl std::str::FromStr for VaultEncoding {
+    type Err = String;
+
+    fn from_str(encoding: &str) -> Result<Self, Self::Err> {
+        match encoding {
+            "base64" => Ok(Self::Base64),
+            "hex" => Ok(Self::Hex),
+            _ => Err(format!("unknown Vault share encoding: {}", encoding)),
+        }
+    }
+}
+
+impl std::fmt::Display for VaultEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Base64 => write!(f, "base64"),
+            Self::Hex => write!(f, "hex"),
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum VaultError {
+    #[error("Vault share number {} is not valid {}", .0 + 1, .1)]
+    InvalidEncoding(usize, VaultEncoding),
+
+    #[error("Vault share number {} is too short, it must have at least 1 byte of secret and its x coordinate", .0 + 1)]
+    TooShort(usize),
+
+    #[error("Vault share number {} has an x coordinate of 0, which is never a valid share", .0 + 1)]
+    ZeroCoordinate(usize),
+
+    #[error("all the shares must be the same length")]
+    MismatchedLengths,
+
+    #[error("more than one share has the x coordinate {0}")]
+    DuplicateShare(u8),
+}
+
+/// Returns the share (with the x coordinate as the first byte) in Vault's layout
+pub fn to_vault_share(share: &[u8], encoding: VaultEncoding) -> String {
+    let mut vault_share = Vec::with_capacity(share.len());
+    vault_share.extend_from_slice(&share[1..]);
+    vault_share.push(share[0]);
+
+    let encoded = match encoding {
+        VaultEncoding::Base64 => base64::encode(&vault_share),
+        VaultEncoding::Hex => hex::encode(&vault_share),
+    };
+    vault_share.zeroize();
+
+    encoded
+}
+
+/// Decodes a Vault share written in `encoding` and returns it with the x coordinate as the first
+/// byte. The encoding has to be given, some base64 shares are also valid hex.
+///
+/// `index` is the position of the share in its set, used in errors
+pub fn from_vault_share(
+    index: usize,
+    vault_share: &str,
+    encoding: VaultEncoding,
+) -> Result<Vec<u8>, VaultError> {
+    let vault_share = vault_share.trim();
+
+    let decoded = match encoding {
+        VaultEncoding::Base64 => base64::decode(vault_share).ok(),
+        VaultEncoding::Hex => hex::decode(vault_share).ok(),
+    };
+    let mut bytes = decoded.ok_or(VaultError::InvalidEncoding(index, encoding))?;
+
+    if bytes.len() < 2 {
+        bytes.zeroize();
+        return Err(VaultError::TooShort(index));
+    }
+
+    let x = bytes.pop().unwrap_or_default();
+    if x == 0 {
+        bytes.zeroize();
+        return Err(VaultError::ZeroCoordinate(index));
+    }
+
+    bytes.insert(0, x);
+    Ok(bytes)
+}
+
+/// Converts secret phrases from the same set into Vault shares, in the same order
+pub fn secret_phrases_to_vault_shares(
+    secret_phrases: &[String],
+    encoding: VaultEncoding,
+) -> Result<Vec<String>, crate::Error> {
+    let (_threshold, mut shares) = secret::decode_secret_phrases(secret_phrases)?;
+
+    let vault_shares = shares
+        .iter()
+        .map(|share| to_vault_share(share, encoding))
+        .collect();
+    shares.zeroize();
+
+    Ok(vault_shares)
+}
+
+/// Converts split phrases from the same set into Vault shares, in the same order. The shares are of
+/// the entropy of the mnemonic code, and any 3 of them recover it.
+pub fn split_phrases_to_vault_shares(
+    split_phrases: &[String],
+    encoding: VaultEncoding,
+) -> Result<Vec<String>, crate::Error> {
+    let split_shares = decode_split_shares(split_phrases, &Words)?;
+
+    Ok(split_shares
+        .iter()
+        .map(|split_share| to_vault_share(&split_share.share, encoding))
+        .collect())
+}
+
+/// Converts Vault shares from the same set into secret phrases, which `threshold` of can recover the
+/// secret. The secret phrases get a new set id, so every share from a set should be converted at the
+/// same time.
+pub fn vault_shares_to_secret_phrases(
+    vault_shares: &[String],
+    threshold: u8,
+    encoding: VaultEncoding,
+) -> Result<Vec<String>, crate::Error> {
+    let mut shares = vault_shares
+        .iter()
+        .enumerate()
+        .map(|(index, vault_share)| from_vault_share(index, vault_share, encoding))
+        .collect::<Result<Vec<Vec<u8>>, VaultError>>()?;
+
+    let secret_phrases = check_shares(&shares, threshold).and_then(|_| {
+        let set_id = secret::random_set_id();
+
+        shares
+            .iter()
+            .map(|share| secret::share_to_secret_phrase(&set_id, threshold, share))
+            .collect()
+    });
+    shares.zeroize();
+
+    secret_phrases
+}
+
+fn check_shares(shares: &[Vec<u8>], threshold: u8) -> Result<(), crate::Error> {
+    // the shares don't need to reach the threshold, but the threshold must be possible
+    if threshold < 2 {
+        return Err(crate::Error::InvalidThreshold {
+            threshold,
+            number_of_shares: u8::MAX,
+        });
+    }
+
+    let secret_length = match shares.first() {
+        Some(share) => share.len() - 1,
+        None => return Ok(()),
+    };
+    if secret_length > u16::MAX as usize {
+        return Err(crate::Error::InvalidSecretLength(secret_length));
+    }
+
+    let mut xs = HashSet::with_capacity(shares.len());
+    for share in shares {
+        if share.len() != secret_length + 1 {
+            return Err(VaultError::MismatchedLengths.into());
+        }
+
+        if !xs.insert(share[0]) {
+            return Err(VaultError::DuplicateShare(share[0]).into());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shamir::SecretData;
+
+    const MNEMONIC_CODE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+
+    // shares of `correct horse battery staple`, 3 of 5, made with shamir-vault 1.3.0 (a port of
+    // Vault's shamir package) and rand 0.8.5, base64 encoded like Vault prints them:
+    //
+    //   let mut rng = rand::rngs::StdRng::seed_from_u64(48);
+    //   shamir_vault::split(b"correct horse battery staple", 5, 3, &mut rng)
+    const VAULT_SHARES: [&str; 5] = [
+        "cazn+dJ9K80QmujkWwqwjvkx7edpa5dlfKYjVAw=",
+        "KFxEg8FOtjtOJNfzwE2zAVJVoal19iScwdys2oI=",
+        "iH/s7xMPyt4vXvJTdVM39X6qjJg0fCwTF2Cp8N4=",
+        "Op/RCHZQ6dY20U1k/mdh7t8QKTxlvcCN3Arj644=",
+        "uf6oUJwsDzDmaQBy+Y0DUqgISliPXTcLDTNvuKw=",
+    ];
+
+    fn strings(strs: &[&str]) -> Vec<String> {
+        strs.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn imports_vault_shares() {
+        let secret_phrases =
+            vault_shares_to_secret_phrases(&strings(&VAULT_SHARES), 3, VaultEncoding::Base64)
+                .unwrap();
+        assert_eq!(secret_phrases.len(), 5);
+
+        assert_eq!(
+            secret::recover_secret(secret_phrases[2..].to_vec()).unwrap(),
+            b"correct horse battery staple"
+        );
+        assert!(secret::recover_secret(secret_phrases[..2].to_vec()).is_err());
+    }
+
+    #[test]
+    fn reads_vault_shares_as_hex() {
+        let share = from_vault_share(0, VAULT_SHARES[0], VaultEncoding::Base64).unwrap();
+        let hex_share = to_vault_share(&share, VaultEncoding::Hex);
+
+        assert_eq!(
+            hex_share,
+            "71ace7f9d27d2bcd109ae8e45b0ab08ef931ede7696b97657ca623540c"
+        );
+        assert_eq!(
+            from_vault_share(0, &hex_share, VaultEncoding::Hex).unwrap(),
+            share
+        );
+        assert_eq!(share[0], 0x0c);
+    }
+
+    #[test]
+    fn reads_vault_shares_in_the_encoding_given() {
+        // valid as both hex and base64, but different shares
+        let vault_share = "abcd0102";
+
+        assert_eq!(
+            from_vault_share(0, vault_share, VaultEncoding::Hex).unwrap(),
+            vec![0x02, 0xab, 0xcd, 0x01]
+        );
+        assert_eq!(
+            from_vault_share(0, vault_share, VaultEncoding::Base64).unwrap(),
+            vec![0x36, 0x69, 0xb7, 0x1d, 0xd3, 0x5d]
+        );
+    }
+
+    #[test]
+    fn exports_and_imports_secret_phrases() {
+        let secret_phrases = secret::split_secret(b"hunter2", 2, 3).unwrap();
+        let vault_shares =
+            secret_phrases_to_vault_shares(&secret_phrases, VaultEncoding::Base64).unwrap();
+
+        let mut shares = vault_shares
+            .iter()
+            .enumerate()
+            .map(|(index, vault_share)| {
+                from_vault_share(index, vault_share, VaultEncoding::Base64).unwrap()
+            })
+            .collect::<Vec<Vec<u8>>>();
+        assert_eq!(shares[1][0], 2);
+
+        shares.truncate(2);
+        assert_eq!(SecretData::recover_secret(2, shares).unwrap(), b"hunter2");
+
+        let imported =
+            vault_shares_to_secret_phrases(&vault_shares[1..], 2, VaultEncoding::Base64).unwrap();
+        assert_eq!(secret::recover_secret(imported).unwrap(), b"hunter2");
+    }
+
+    #[test]
+    fn exports_split_phrases() {
+        let split_phrases = crate::get_split_phrases(MNEMONIC_CODE.to_string()).unwrap();
+        let vault_shares =
+            split_phrases_to_vault_shares(&split_phrases[1..4], VaultEncoding::Hex).unwrap();
+
+        let imported =
+            vault_shares_to_secret_phrases(&vault_shares, 3, VaultEncoding::Hex).unwrap();
+        let entropy = secret::recover_secret(imported).unwrap();
+
+        assert_eq!(
+            bip39::Mnemonic::from_entropy(&entropy).unwrap().to_string(),
+            MNEMONIC_CODE
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_vault_shares() {
+        use VaultEncoding::{Base64, Hex};
+
+        assert_eq!(
+            from_vault_share(1, "not a share!", Base64),
+            Err(VaultError::InvalidEncoding(1, Base64))
+        );
+        assert_eq!(
+            from_vault_share(0, "abc", Hex),
+            Err(VaultError::InvalidEncoding(0, Hex))
+        );
+        assert_eq!(
+            from_vault_share(2, VAULT_SHARES[2], Hex),
+            Err(VaultError::InvalidEncoding(2, Hex))
+        );
+        assert_eq!(from_vault_share(0, "01", Hex), Err(VaultError::TooShort(0)));
+        assert_eq!(
+            from_vault_share(0, "ff00", Hex),
+            Err(VaultError::ZeroCoordinate(0))
+        );
+
+        assert_eq!(
+            vault_shares_to_secret_phrases(&strings(&["ff01", "ffee02"]), 2, Hex),
+            Err(VaultError::MismatchedLengths.into())
+        );
+        assert_eq!(
+            vault_shares_to_secret_phrases(&strings(&["ff01", "ee01"]), 2, Hex),
+            Err(VaultError::DuplicateShare(1).into())
+        );
+        assert!(vault_shares_to_secret_phrases(&strings(&VAULT_SHARES), 1, Base64).is_err());
+    }
+}
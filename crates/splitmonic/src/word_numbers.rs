@@ -0,0 +1,159 @@
+//! Contains word numbers, for steel backup plates and stamping kits that record each word of a
+//! mnemonic or split phrase as its number in the BIP39 word list instead of the word itself.
+//!
+//! - decimal: the word number, 1 to 2048 (`abandon` is 1, `zoo` is 2048)
+//! - padded: the word number as 4 digits, 0001 to 2048
+//! - binary: the 11 bits of the word's index, 00000000000 to 11111111111, for punched dot
+//!   patterns. These are the bits the mnemonic is made of, so they start from 0, not 1.
+//!
+//! When reading word numbers, all 3 forms are accepted, an 11 digit number made of 0s and 1s is
+//! always binary, anything else is a decimal word number.
+
+use crate::wordlist::{English, Wordlist};
+use thiserror::Error;
+
+const BINARY_LENGTH: usize = 11;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum WordNumberError {
+    #[error("`{0}` is not a word number, word numbers are between 1 and 2048 or 11 binary digits")]
+    InvalidWordNumber(String),
+}
+
+/// How word numbers are written
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WordNumbers {
+    Decimal,
+    Padded,
+    Binary,
+}
+
+impl std::str::FromStr for WordNumbers {
+    type Err = String;
+
+    fn from_str(word_numbers: &str) -> Result<Self, Self::Err> {
+        match word_numbers {
+            "decimal" => Ok(Self::Decimal),
+            "padded" => Ok(Self::Padded),
+            "binary" => Ok(Self::Binary),
+            _ => Err(format!("unknown word number format: {}", word_numbers)),
+        }
+    }
+}
+
+/// Returns the word number of a BIP39 word
+pub fn to_word_number(word: &str, word_numbers: WordNumbers) -> Result<String, crate::Error> {
+    let index = English::get_index(word)?;
+
+    Ok(match word_numbers {
+        WordNumbers::Decimal => (index + 1).to_string(),
+        WordNumbers::Padded => format!("{:04}", index + 1),
+        WordNumbers::Binary => format!("{:011b}", index),
+    })
+}
+
+/// Returns the BIP39 word of a word number, written in any of the 3 forms
+pub fn from_word_number(word_number: &str) -> Result<&'static str, crate::Error> {
+    let invalid = || WordNumberError::InvalidWordNumber(word_number.to_string());
+
+    let index = if word_number.len() == BINARY_LENGTH
+        && word_number.chars().all(|char| char == '0' || char == '1')
+    {
+        usize::from_str_radix(word_number, 2).map_err(|_| invalid())?
+    } else {
+        match word_number.parse::<usize>() {
+            Ok(number) if (1..=2048).contains(&number) => number - 1,
+            _ => return Err(invalid().into()),
+        }
+    };
+
+    Ok(English::get_word(index)?)
+}
+
+/// Returns the word numbers of every word in the phrase, separated by spaces
+pub fn phrase_to_word_numbers(
+    phrase: &str,
+    word_numbers: WordNumbers,
+) -> Result<String, crate::Error> {
+    Ok(phrase
+        .split_whitespace()
+        .map(|word| to_word_number(word, word_numbers))
+        .collect::<Result<Vec<String>, _>>()?
+        .join(" "))
+}
+
+/// Returns the phrase the word numbers, separated by whitespace, stand for
+pub fn word_numbers_to_phrase(word_numbers: &str) -> Result<String, crate::Error> {
+    Ok(word_numbers
+        .split_whitespace()
+        .map(from_word_number)
+        .collect::<Result<Vec<&str>, _>>()?
+        .join(" "))
+}
+
+/// True when the phrase is made up of only word numbers, rather than words
+pub fn are_word_numbers(phrase: &str) -> bool {
+    let mut numbers = phrase.split_whitespace().peekable();
+
+    numbers.peek().is_some()
+        && numbers.all(|number| number.chars().all(|char| char.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC_CODE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn writes_word_numbers() {
+        assert_eq!(
+            to_word_number("abandon", WordNumbers::Decimal).unwrap(),
+            "1"
+        );
+        assert_eq!(
+            to_word_number("about", WordNumbers::Padded).unwrap(),
+            "0004"
+        );
+        assert_eq!(
+            to_word_number("about", WordNumbers::Binary).unwrap(),
+            "00000000011"
+        );
+        assert_eq!(to_word_number("zoo", WordNumbers::Decimal).unwrap(), "2048");
+        assert_eq!(
+            to_word_number("zoo", WordNumbers::Binary).unwrap(),
+            "11111111111"
+        );
+    }
+
+    #[test]
+    fn reads_every_form() {
+        for word_numbers in &[
+            WordNumbers::Decimal,
+            WordNumbers::Padded,
+            WordNumbers::Binary,
+        ] {
+            let numbers = phrase_to_word_numbers(MNEMONIC_CODE, *word_numbers).unwrap();
+
+            assert!(are_word_numbers(&numbers));
+            assert_eq!(word_numbers_to_phrase(&numbers).unwrap(), MNEMONIC_CODE);
+        }
+
+        // 11 digit numbers are binary, even when they could be decimal
+        assert_eq!(from_word_number("00000000100").unwrap(), "above");
+        assert_eq!(from_word_number("0100").unwrap(), "arrest");
+    }
+
+    #[test]
+    fn rejects_invalid_word_numbers() {
+        for word_number in &["0", "2049", "0000", "11111111112", "abandon"] {
+            assert_eq!(
+                from_word_number(word_number),
+                Err(WordNumberError::InvalidWordNumber(word_number.to_string()).into())
+            );
+        }
+
+        assert!(!are_word_numbers(MNEMONIC_CODE));
+        assert!(!are_word_numbers(""));
+    }
+}
@@ -34,7 +34,7 @@ pub trait Wordlist {
             .words
             .get(&index)
             .ok_or(WordlistError::InvalidIndex(index))
-            .map(|word| *word)
+            .copied()
     }
 
     /// Returns the index of a given word from the word list.
@@ -43,7 +43,7 @@ pub trait Wordlist {
             .indexes
             .get(word)
             .ok_or_else(|| WordlistError::InvalidWord(word.into()))
-            .map(|usize| *usize)
+            .copied()
     }
 
     fn contains_word(word: &str) -> bool {
@@ -61,7 +61,6 @@ pub trait Wordlist {
         let mut words = Self::wordlist()
             .words
             .values()
-            .into_iter()
             .filter(|word| word.starts_with(start))
             .cloned()
             .collect::<Vec<&'static str>>();
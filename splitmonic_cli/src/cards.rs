@@ -0,0 +1,419 @@
+//! Printable cards for split phrases, so they don't have to be copied out by hand. Each split
+//! phrase gets a card with its numbered words in a grid, its set id, share number, threshold,
+//! recovery instructions and a checksum of its words. Cards are written as PDF, SVG and HTML
+//! (the SVG with print styles), everything is created offline.
+
+use crate::output;
+use eyre::Result;
+use splitmonic::{inspect, share_file};
+use std::path::{Path, PathBuf};
+
+/// A5 landscape, in points
+const WIDTH: f32 = 595.0;
+const HEIGHT: f32 = 420.0;
+
+const MARGIN: f32 = 40.0;
+const COLUMNS: usize = 4;
+const ROW_HEIGHT: f32 = 30.0;
+const GRID_TOP: f32 = 100.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+/// What a card is drawn with, coordinates are in points from the top left corner
+#[derive(Debug, Clone, PartialEq)]
+enum Element {
+    Text {
+        x: f32,
+        y: f32,
+        size: f32,
+        font: Font,
+        text: String,
+    },
+    Rect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    },
+}
+
+/// The layout of one split phrase card
+#[derive(Debug, Clone, PartialEq)]
+pub struct Card {
+    title: String,
+    elements: Vec<Element>,
+}
+
+fn text(x: f32, y: f32, size: f32, font: Font, text: String) -> Element {
+    Element::Text {
+        x,
+        y,
+        size,
+        font,
+        text,
+    }
+}
+
+impl Card {
+    pub fn new(split_phrase: &str, fingerprint: &str) -> Result<Self> {
+        let info = inspect::inspect_split_phrase(split_phrase)?;
+        let words = split_phrase.split_whitespace().collect::<Vec<&str>>();
+        let rows = words.len().div_ceil(COLUMNS);
+        let column_width = (WIDTH - 2.0 * MARGIN) / COLUMNS as f32;
+
+        let share = format!("Share {} of {}", info.share_index, info.number_of_shares);
+
+        let mut elements = vec![
+            Element::Rect {
+                x: 20.0,
+                y: 20.0,
+                width: WIDTH - 40.0,
+                height: HEIGHT - 40.0,
+            },
+            text(
+                MARGIN,
+                55.0,
+                16.0,
+                Font::Bold,
+                "splitmonic split phrase".to_string(),
+            ),
+            text(
+                WIDTH - MARGIN - 110.0,
+                55.0,
+                16.0,
+                Font::Bold,
+                share.clone(),
+            ),
+            text(
+                MARGIN,
+                78.0,
+                10.0,
+                Font::Regular,
+                format!(
+                    "Set id: {}    Any {} of {} split phrases recover the mnemonic",
+                    info.set_id, info.threshold, info.number_of_shares
+                ),
+            ),
+        ];
+
+        // words are numbered down the columns, like they are written on paper
+        for (index, word) in words.iter().enumerate() {
+            let x = MARGIN + (index / rows) as f32 * column_width;
+            let y = GRID_TOP + (index % rows) as f32 * ROW_HEIGHT;
+
+            elements.push(Element::Rect {
+                x,
+                y,
+                width: column_width - 6.0,
+                height: ROW_HEIGHT - 6.0,
+            });
+            elements.push(text(
+                x + 6.0,
+                y + 16.0,
+                12.0,
+                Font::Regular,
+                format!("{:>2}. {}", index + 1, word),
+            ));
+        }
+
+        let instructions_top = GRID_TOP + rows as f32 * ROW_HEIGHT + 16.0;
+        elements.extend(vec![
+            text(
+                MARGIN,
+                instructions_top,
+                10.0,
+                Font::Bold,
+                format!(
+                    "Checksum: {}    Mnemonic fingerprint: {}",
+                    share_file::words_checksum(&words),
+                    fingerprint
+                ),
+            ),
+            text(
+                MARGIN,
+                instructions_top + 16.0,
+                9.0,
+                Font::Regular,
+                format!(
+                    "To recover, run `splitmonic combine` with any {} split phrases from set \"{}\".",
+                    info.threshold, info.set_id
+                ),
+            ),
+            text(
+                MARGIN,
+                instructions_top + 30.0,
+                9.0,
+                Font::Regular,
+                format!(
+                    "The recovered mnemonic fingerprint must be {}. Keep this card secret, never photograph it.",
+                    fingerprint
+                ),
+            ),
+        ]);
+
+        Ok(Self {
+            title: format!("splitmonic {} ({})", share.to_lowercase(), info.set_id),
+            elements,
+        })
+    }
+
+    pub fn to_svg(&self) -> String {
+        let mut svg = vec![
+            format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="210mm" height="148mm" viewBox="0 0 {} {}">"#,
+                WIDTH, HEIGHT
+            ),
+            format!("<title>{}</title>", escape_xml(&self.title)),
+            format!(
+                r##"<rect x="0" y="0" width="{}" height="{}" fill="#ffffff"/>"##,
+                WIDTH, HEIGHT
+            ),
+        ];
+
+        for element in &self.elements {
+            svg.push(match element {
+                Element::Text {
+                    x,
+                    y,
+                    size,
+                    font,
+                    text,
+                } => format!(
+                    r#"<text x="{}" y="{}" font-family="Helvetica, Arial, sans-serif" font-size="{}" font-weight="{}" xml:space="preserve">{}</text>"#,
+                    x,
+                    y,
+                    size,
+                    if *font == Font::Bold { "bold" } else { "normal" },
+                    escape_xml(text)
+                ),
+                Element::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                } => format!(
+                    r##"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="#999999" stroke-width="0.5"/>"##,
+                    x, y, width, height
+                ),
+            });
+        }
+
+        svg.push("</svg>".to_string());
+        svg.join("\n")
+    }
+
+    pub fn to_html(&self) -> String {
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{}</title>
+<style>
+  @page {{ size: A5 landscape; margin: 0; }}
+  body {{ margin: 0; }}
+  svg {{ display: block; width: 210mm; height: 148mm; }}
+</style>
+</head>
+<body>
+{}
+</body>
+</html>
+"#,
+            escape_xml(&self.title),
+            self.to_svg()
+        )
+    }
+
+    /// A one page PDF, using the standard Helvetica fonts every PDF reader has built in
+    pub fn to_pdf(&self) -> Vec<u8> {
+        let mut content = String::new();
+        for element in &self.elements {
+            match element {
+                Element::Text {
+                    x,
+                    y,
+                    size,
+                    font,
+                    text,
+                } => content.push_str(&format!(
+                    "BT /{} {} Tf {} {} Td ({}) Tj ET\n",
+                    if *font == Font::Bold { "F2" } else { "F1" },
+                    size,
+                    x,
+                    HEIGHT - y,
+                    escape_pdf(text)
+                )),
+                Element::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                } => content.push_str(&format!(
+                    "0.6 G 0.5 w {} {} {} {} re S\n",
+                    x,
+                    HEIGHT - y - height,
+                    width,
+                    height
+                )),
+            }
+        }
+
+        let objects = [
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 4 0 R /F2 5 0 R >> >> /Contents 6 0 R >>",
+                WIDTH, HEIGHT
+            ),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold >>".to_string(),
+            format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                content.len(),
+                content
+            ),
+            format!("<< /Title ({}) /Producer (splitmonic) >>", escape_pdf(&self.title)),
+        ];
+
+        let mut pdf = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.push_str(&format!("{} 0 obj\n{}\nendobj\n", index + 1, object));
+        }
+
+        let xref = pdf.len();
+        pdf.push_str(&format!(
+            "xref\n0 {}\n0000000000 65535 f \n",
+            objects.len() + 1
+        ));
+        for offset in offsets {
+            pdf.push_str(&format!("{:010} 00000 n \n", offset));
+        }
+        pdf.push_str(&format!(
+            "trailer\n<< /Size {} /Root 1 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            objects.len(),
+            xref
+        ));
+
+        pdf.into_bytes()
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn escape_pdf(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('(', "\\(")
+        .replace(')', "\\)")
+}
+
+/// Writes a PDF, SVG and HTML card for each split phrase to `dir`, returns the paths written to.
+/// Nothing is written if any of the files already exist.
+pub fn write_cards(
+    split_phrases: &[String],
+    fingerprint: &str,
+    dir: &Path,
+) -> Result<Vec<PathBuf>> {
+    let total = split_phrases.len();
+
+    let cards = split_phrases
+        .iter()
+        .enumerate()
+        .map(|(index, split_phrase)| {
+            let name = format!("card_{}_of_{}", index + 1, total);
+            Ok((name, Card::new(split_phrase, fingerprint)?))
+        })
+        .collect::<Result<Vec<(String, Card)>>>()?;
+
+    let files = cards
+        .iter()
+        .flat_map(|(name, card)| {
+            vec![
+                (dir.join(format!("{}.pdf", name)), card.to_pdf()),
+                (
+                    dir.join(format!("{}.svg", name)),
+                    card.to_svg().into_bytes(),
+                ),
+                (
+                    dir.join(format!("{}.html", name)),
+                    card.to_html().into_bytes(),
+                ),
+            ]
+        })
+        .collect::<Vec<(PathBuf, Vec<u8>)>>();
+
+    if let Some((existing, _)) = files.iter().find(|(path, _)| path.exists()) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!(
+                "Refusing to overwrite existing file: {}",
+                existing.display()
+            ),
+        )
+        .into());
+    }
+
+    for (path, contents) in &files {
+        output::write_new_file(path, contents)?;
+    }
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPLIT_PHRASE: &str = "embody fog drop about embrace visa adapt winner wine dash fabric snack drip auction deputy visit shift animal various bread country lecture assist marriage merit goat gravity glove";
+
+    #[test]
+    fn lays_out_the_words_down_the_columns() {
+        let card = Card::new(SPLIT_PHRASE, "5436d724").unwrap();
+        let svg = card.to_svg();
+
+        assert!(svg.contains("Share 3 of 5"));
+        assert!(svg.contains("Set id: embody fog drop"));
+        assert!(svg.contains("Mnemonic fingerprint: 5436d724"));
+
+        // 28 words in 4 columns of 7, so word 8 starts the second column
+        let word_8 = card
+            .elements
+            .iter()
+            .find(|element| matches!(element, Element::Text { text, .. } if text == " 8. winner"));
+        assert!(matches!(
+            word_8,
+            Some(Element::Text { y, .. }) if *y == GRID_TOP + 16.0
+        ));
+    }
+
+    #[test]
+    fn creates_a_pdf_with_a_valid_cross_reference_table() {
+        let pdf = Card::new(SPLIT_PHRASE, "5436d724").unwrap().to_pdf();
+        let pdf = String::from_utf8(pdf).unwrap();
+
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.contains("(28. glove) Tj"));
+
+        let startxref = pdf.rsplit("startxref\n").next().unwrap();
+        let xref: usize = startxref.lines().next().unwrap().parse().unwrap();
+        assert!(pdf[xref..].starts_with("xref\n0 8\n"));
+
+        // every object is where the cross reference table says it is
+        for (index, entry) in pdf[xref..].lines().skip(3).take(7).enumerate() {
+            let offset: usize = entry[0..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj", index + 1)));
+        }
+    }
+}
@@ -0,0 +1,525 @@
+mod view;
+
+use crate::{
+    ui::message::{Error, Message},
+    ui::util::stateful_list::StatefulList,
+    ui::InputMode,
+    Term,
+};
+use crossbeam_channel::{Receiver, Sender};
+use eyre::Result;
+use splitmonic::wordlist::english::English;
+use splitmonic::wordlist::Wordlist;
+use zeroize::Zeroize;
+
+use crossterm::{
+    event::{KeyCode, KeyEvent, KeyModifiers},
+    execute, terminal,
+};
+
+pub enum Effect {
+    ReceivedMessage(Message),
+    ReceivedMnemonic(String, String),
+}
+
+impl Effect {
+    fn error<T: Into<Error>>(error: T) -> Self {
+        Self::ReceivedMessage(Message::error(error.into()))
+    }
+
+    fn success<T: Into<String>>(message: T) -> Self {
+        Self::ReceivedMessage(Message::success(message.into()))
+    }
+
+    fn mnemonic(mnemonic: String, fingerprint: String) -> Self {
+        Self::ReceivedMnemonic(mnemonic, fingerprint)
+    }
+}
+
+pub enum Event {
+    Input(KeyEvent),
+    Effect(Effect),
+    Tick,
+}
+
+pub enum Screen {
+    WordInput(InputMode),
+    List,
+    FileInput,
+    Recovered { revealed: bool },
+}
+
+/// The live validation status of a split phrase, while it is being entered
+#[derive(Debug, PartialEq)]
+pub enum SplitPhraseStatus {
+    Empty,
+    WrongLength { given: usize, expected: usize },
+    MismatchedSet,
+    DuplicateShare,
+    InvalidChecksum,
+    Valid,
+}
+
+pub struct CombineApp {
+    tx: Sender<Event>,
+    rx: Receiver<Event>,
+
+    pub message: Message,
+
+    pub autocomplete: &'static str,
+    pub input: String,
+    pub file_path: String,
+
+    pub screen: Screen,
+    pub current: usize,
+    pub split_phrases: [StatefulList<String>; 3],
+    /// 28 words for 24 word mnemonics, 16 and 22 words for 12 and 18 word mnemonics
+    pub split_phrase_length: usize,
+    pub should_quit: bool,
+
+    pub recovered: Option<String>,
+    pub fingerprint: Option<String>,
+}
+
+impl CombineApp {
+    pub fn new(tx: Sender<Event>, rx: Receiver<Event>) -> Self {
+        Self {
+            tx,
+            rx,
+            message: Message::None,
+            autocomplete: English::get_word(0).unwrap(),
+            input: String::new(),
+            file_path: String::new(),
+            screen: Screen::WordInput(InputMode::Normal),
+            current: 0,
+            split_phrases: empty_split_phrases(),
+            split_phrase_length: 28,
+            should_quit: false,
+            recovered: None,
+            fingerprint: None,
+        }
+    }
+
+    pub fn start_event_loop(&mut self, mut terminal: Term) -> Result<()> {
+        loop {
+            terminal.draw(|f| view::draw(self, f))?;
+
+            match self.rx.recv()? {
+                Event::Input(event) => match self.screen {
+                    Screen::WordInput(InputMode::Normal) => self.update_input_in_normal(event),
+                    Screen::WordInput(InputMode::Inserting) => {
+                        self.update_input_in_editing(event, None)
+                    }
+                    Screen::WordInput(InputMode::Editing(edit)) => {
+                        self.update_input_in_editing(event, edit)
+                    }
+                    Screen::List => self.update_in_list(event),
+                    Screen::FileInput => self.update_in_file_input(event),
+                    Screen::Recovered { revealed } => self.update_in_recovered(event, revealed),
+                },
+                Event::Effect(Effect::ReceivedMnemonic(mnemonic, fingerprint)) => {
+                    self.recovered = Some(mnemonic);
+                    self.fingerprint = Some(fingerprint);
+                    self.screen = Screen::Recovered { revealed: false };
+                }
+                Event::Effect(Effect::ReceivedMessage(msg)) => self.message = msg,
+                Event::Tick => {
+                    match &self.message {
+                        Message::None => {}
+                        _any_other => self.message = Message::None,
+                    };
+                }
+            }
+
+            if self.should_quit {
+                self.clear_recovered();
+                terminal::disable_raw_mode()?;
+                execute!(terminal.backend_mut(), terminal::LeaveAlternateScreen,)?;
+                terminal.show_cursor()?;
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn update_input_in_editing(&mut self, key_event: KeyEvent, edit: Option<usize>) {
+        match key_event.code {
+            KeyCode::Char(char) => {
+                self.input.push(char);
+
+                match English::starting_with(&self.input).as_slice() {
+                    [] => {
+                        self.autocomplete = "";
+                        self.input.pop();
+                    }
+                    [only_one] => {
+                        self.autocomplete = "";
+                        self.add_word_to_split_phrase(only_one.to_string(), edit);
+                        self.input = "".to_string();
+                    }
+                    [head, ..] => self.autocomplete = head,
+                }
+            }
+            KeyCode::Esc => self.screen = Screen::WordInput(InputMode::Normal),
+            KeyCode::Backspace => {
+                self.input.pop();
+
+                match English::starting_with(&self.input).as_slice() {
+                    [] => self.autocomplete = "",
+                    [head, ..] => self.autocomplete = head,
+                }
+            }
+            KeyCode::Right => self.input = self.autocomplete.to_string(),
+            KeyCode::Down => self.select_list(self.current),
+            KeyCode::Tab => {
+                if let Some(word) = English::next_starting_with(&self.input, self.autocomplete) {
+                    self.autocomplete = word;
+                }
+            }
+            KeyCode::Enter => {
+                self.input = self.input.trim().to_string();
+                self.add_word_to_split_phrase(self.autocomplete.to_string(), edit);
+                self.input = "".to_string();
+            }
+            _ => {}
+        }
+    }
+
+    fn update_input_in_normal(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('q') => {
+                self.should_quit = true;
+            }
+            KeyCode::Char('i') => self.screen = Screen::WordInput(InputMode::Inserting),
+            KeyCode::Char('o') => self.screen = Screen::FileInput,
+            KeyCode::Char('w') => {
+                self.split_phrase_length = match self.split_phrase_length {
+                    28 => 16,
+                    16 => 22,
+                    _ => 28,
+                }
+            }
+            KeyCode::Esc => self.screen = Screen::WordInput(InputMode::Normal),
+            KeyCode::Down | KeyCode::Tab => self.select_list(self.current),
+            KeyCode::Left => self.current = previous_index(self.current),
+            KeyCode::Right => self.current = next_index(self.current),
+            KeyCode::Enter if self.all_split_phrases_valid() => self.recover_mnemonic(),
+            _ => {}
+        }
+    }
+
+    fn update_in_list(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('i') => {
+                self.split_phrases[self.current].unselect();
+                self.screen = Screen::WordInput(InputMode::Inserting)
+            }
+            KeyCode::Char('e') => {
+                let current = self.split_phrases[self.current].selected();
+                self.split_phrases[self.current].unselect();
+                self.screen = Screen::WordInput(InputMode::Editing(current))
+            }
+            KeyCode::Esc | KeyCode::Tab => {
+                self.split_phrases[self.current].unselect();
+                self.screen = Screen::WordInput(InputMode::Normal)
+            }
+            KeyCode::Up if key_event.modifiers.contains(KeyModifiers::ALT) => {
+                self.split_phrases[self.current].move_up();
+            }
+            KeyCode::Down if key_event.modifiers.contains(KeyModifiers::ALT) => {
+                self.split_phrases[self.current].move_down();
+            }
+            KeyCode::Up => {
+                if self.split_phrases[self.current].items.is_empty() {
+                    self.split_phrases[self.current].unselect();
+                    self.screen = Screen::WordInput(InputMode::Normal)
+                } else {
+                    self.split_phrases[self.current].previous()
+                }
+            }
+            KeyCode::Down => self.split_phrases[self.current].next(),
+            KeyCode::Left => self.select_list(previous_index(self.current)),
+            KeyCode::Right => self.select_list(next_index(self.current)),
+            KeyCode::Char('d') => self.split_phrases[self.current].delete_selected(),
+            KeyCode::Enter if self.all_split_phrases_valid() => self.recover_mnemonic(),
+            _ => {}
+        }
+    }
+
+    fn update_in_file_input(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Esc => self.screen = Screen::WordInput(InputMode::Normal),
+            KeyCode::Char(char) => self.file_path.push(char),
+            KeyCode::Backspace => {
+                self.file_path.pop();
+            }
+            KeyCode::Enter => match crate::read_and_get_phrases_from_file(&self.file_path) {
+                Ok(words) => {
+                    // a file holds a whole split phrase, so it sets the length of split phrases
+                    if [16, 22, 28].contains(&words.len()) {
+                        self.split_phrase_length = words.len();
+                    }
+
+                    self.split_phrases[self.current] = StatefulList::with_items(words);
+                    self.file_path = String::new();
+                    self.screen = Screen::WordInput(InputMode::Normal);
+
+                    self.send_status_of_split_phrase(self.current);
+                    self.select_next_empty_split_phrase();
+                }
+                Err(error) => self
+                    .tx
+                    .send(Event::Effect(Effect::error(error)))
+                    .expect("should always send"),
+            },
+            _ => {}
+        }
+    }
+
+    fn update_in_recovered(&mut self, key_event: KeyEvent, revealed: bool) {
+        match key_event.code {
+            KeyCode::Char('q') => self.should_quit = true,
+            KeyCode::Char('r') => {
+                self.screen = Screen::Recovered {
+                    revealed: !revealed,
+                }
+            }
+            KeyCode::Esc => {
+                self.clear_recovered();
+                self.screen = Screen::WordInput(InputMode::Normal)
+            }
+            _ => {}
+        }
+    }
+
+    fn clear_recovered(&mut self) {
+        if let Some(recovered) = self.recovered.as_mut() {
+            recovered.zeroize();
+        }
+
+        self.recovered = None;
+        self.fingerprint = None;
+    }
+
+    fn select_list(&mut self, index: usize) {
+        self.split_phrases[self.current].unselect();
+        self.current = index;
+        self.split_phrases[self.current].select();
+        self.screen = Screen::List;
+    }
+
+    fn select_next_empty_split_phrase(&mut self) {
+        if let Some(index) = (0..3).find(|index| self.split_phrases[*index].len() == 0) {
+            self.current = index
+        }
+    }
+
+    fn add_word_to_split_phrase(&mut self, word: String, place: Option<usize>) {
+        // if the word is not in set of BIP39 words return early
+        if !English::contains_word(&word) {
+            return;
+        }
+
+        let split_phrase_length = self.split_phrase_length;
+        let split_phrase = &mut self.split_phrases[self.current];
+
+        match (place, split_phrase.len()) {
+            (None, len) if len >= split_phrase_length => {
+                split_phrase.pop();
+                split_phrase.push(word);
+            }
+            (None, _len) => split_phrase.push(word),
+            (Some(index), _len) if index < split_phrase.len() => {
+                split_phrase.items[index] = word;
+                self.screen = Screen::WordInput(InputMode::Inserting)
+            }
+            (Some(_index), _len) => split_phrase.push(word),
+        }
+
+        if self.split_phrases[self.current].len() == self.split_phrase_length {
+            self.send_status_of_split_phrase(self.current);
+            self.select_next_empty_split_phrase();
+        }
+    }
+
+    fn send_status_of_split_phrase(&self, index: usize) {
+        let effect = match self.split_phrase_status(index) {
+            SplitPhraseStatus::Valid => {
+                Effect::success(format!("split phrase {} is valid", index + 1))
+            }
+            SplitPhraseStatus::Empty => return,
+            status => Effect::error(eyre::eyre!(
+                "split phrase {} is not valid: {}",
+                index + 1,
+                status.description()
+            )),
+        };
+
+        self.tx
+            .send(Event::Effect(effect))
+            .expect("should always send")
+    }
+
+    fn recover_mnemonic(&mut self) {
+        let split_phrases = self
+            .split_phrases
+            .iter()
+            .map(|split_phrase| split_phrase.items.join(" "))
+            .collect::<Vec<String>>();
+
+        let recovered = splitmonic::validation::validate_split_phrases(split_phrases.clone())
+            .map_err(Error::from)
+            .and_then(|_| Ok(splitmonic::recover_mnemonic_code(split_phrases)?))
+            .and_then(|mnemonic| {
+                let fingerprint = splitmonic::fingerprint::get_fingerprint(&mnemonic)?;
+                Ok((mnemonic, fingerprint))
+            });
+
+        let effect = match recovered {
+            Ok((mnemonic, fingerprint)) => Effect::mnemonic(mnemonic, fingerprint),
+            Err(error) => Effect::error(error),
+        };
+
+        self.tx
+            .send(Event::Effect(effect))
+            .expect("should always send")
+    }
+
+    pub fn all_split_phrases_valid(&self) -> bool {
+        (0..3).all(|index| self.split_phrase_status(index) == SplitPhraseStatus::Valid)
+    }
+
+    pub fn split_phrase_status(&self, index: usize) -> SplitPhraseStatus {
+        let words = &self.split_phrases[index].items;
+
+        // the first split phrase with a set id, is the one the others are compared against
+        let first_with_set_id = self
+            .split_phrases
+            .iter()
+            .position(|split_phrase| split_phrase.len() >= 3);
+
+        let is_duplicate_share = |words: &[String]| {
+            self.split_phrases[0..index]
+                .iter()
+                .any(|split_phrase| split_phrase.len() >= 4 && split_phrase.items[3] == words[3])
+        };
+
+        match words.len() {
+            0 => SplitPhraseStatus::Empty,
+            len if len >= 3
+                && first_with_set_id
+                    .map(|first| self.split_phrases[first].items[0..3] != words[0..3])
+                    .unwrap_or(false) =>
+            {
+                SplitPhraseStatus::MismatchedSet
+            }
+            len if len >= 4 && is_duplicate_share(words) => SplitPhraseStatus::DuplicateShare,
+            len if len != self.split_phrase_length => SplitPhraseStatus::WrongLength {
+                given: len,
+                expected: self.split_phrase_length,
+            },
+            _ => match splitmonic::inspect::inspect_split_phrase(&words.join(" ")) {
+                Ok(info) if info.is_valid() => SplitPhraseStatus::Valid,
+                _ => SplitPhraseStatus::InvalidChecksum,
+            },
+        }
+    }
+}
+
+impl SplitPhraseStatus {
+    pub fn description(&self) -> String {
+        match self {
+            Self::Empty => "empty".to_string(),
+            Self::WrongLength { given, expected } => format!("{}/{} words", given, expected),
+            Self::MismatchedSet => "from a different set".to_string(),
+            Self::DuplicateShare => "same share entered twice".to_string(),
+            Self::InvalidChecksum => "invalid checksum".to_string(),
+            Self::Valid => "valid".to_string(),
+        }
+    }
+}
+
+fn next_index(index: usize) -> usize {
+    (index + 1) % 3
+}
+
+fn previous_index(index: usize) -> usize {
+    (index + 2) % 3
+}
+
+fn empty_split_phrases() -> [StatefulList<String>; 3] {
+    [
+        StatefulList::with_capacity(28),
+        StatefulList::with_capacity(28),
+        StatefulList::with_capacity(28),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp_dir::TempDir;
+    use crossbeam_channel::unbounded;
+
+    fn words(phrase: &str) -> Vec<String> {
+        phrase.split(' ').map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn validates_split_phrases_while_they_are_entered() {
+        let (tx, rx) = unbounded();
+        let mut app = CombineApp::new(tx, rx);
+
+        app.split_phrases[0] = StatefulList::with_items(words("embody fog drop ability sword volume hat detail blue pride yard benefit coach primary now pledge head panel hour congress curtain plug over ordinary debris release tent coin"));
+        app.split_phrases[1] = StatefulList::with_items(words("embody fog drop ability sword"));
+        app.split_phrases[2] = StatefulList::with_items(words("embody bad drop"));
+
+        assert_eq!(app.split_phrase_status(0), SplitPhraseStatus::Valid);
+        assert_eq!(
+            app.split_phrase_status(1),
+            SplitPhraseStatus::DuplicateShare
+        );
+        assert_eq!(app.split_phrase_status(2), SplitPhraseStatus::MismatchedSet);
+
+        app.split_phrases[1] = StatefulList::with_items(words("embody fog drop about embrace visa adapt winner wine dash fabric snack drip auction deputy visit shift animal various bread country lecture assist marriage merit goat glove gravity"));
+        app.split_phrases[2] = StatefulList::with_items(words("embody fog drop able"));
+
+        assert_eq!(
+            app.split_phrase_status(1),
+            SplitPhraseStatus::InvalidChecksum
+        );
+        assert_eq!(
+            app.split_phrase_status(2),
+            SplitPhraseStatus::WrongLength {
+                given: 4,
+                expected: 28
+            }
+        );
+        assert!(!app.all_split_phrases_valid());
+    }
+
+    #[test]
+    fn takes_the_split_phrase_length_from_loaded_files() {
+        let (tx, rx) = unbounded();
+        let mut app = CombineApp::new(tx, rx);
+
+        let split_phrases = splitmonic::get_split_phrases(
+            "legal winner thank year wave sausage worth useful legal winner thank yellow"
+                .to_string(),
+        )
+        .unwrap();
+
+        let dir = TempDir::new("combine_app");
+        let path = dir.join("split_phrase.txt");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&path, &split_phrases[0]).unwrap();
+
+        app.screen = Screen::FileInput;
+        app.file_path = path.to_string_lossy().to_string();
+        app.update_in_file_input(KeyEvent::from(KeyCode::Enter));
+
+        assert_eq!(app.split_phrase_length, 16);
+        assert_eq!(app.split_phrase_status(0), SplitPhraseStatus::Valid);
+    }
+}
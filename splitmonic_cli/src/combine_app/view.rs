@@ -0,0 +1,345 @@
+use crate::{
+    combine_app::{CombineApp, Screen, SplitPhraseStatus},
+    ui::message::messages_area,
+    ui::InputMode,
+    Backend,
+};
+
+use tui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+pub fn draw(app: &mut CombineApp, frame: &mut Frame<Backend>) {
+    let help_box_size = match &app.screen {
+        Screen::WordInput(InputMode::Normal) => 2,
+        Screen::List => 3,
+        _ => 1,
+    };
+
+    let input_box_size = 3;
+
+    // setup layout
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints(
+            [
+                Constraint::Length(help_box_size + 1),
+                Constraint::Length(input_box_size),
+                Constraint::Min(10),
+                Constraint::Length(3),
+                Constraint::Length(3),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+
+    // render blocks
+    frame.render_widget(help_message_block(app), chunks[0]);
+    frame.render_widget(input_block(app), chunks[1]);
+
+    // cursor handling
+    match app.screen {
+        Screen::WordInput(InputMode::Inserting | InputMode::Editing(_)) => {
+            // Make the cursor visible and ask tui-rs to put it at the specified coordinates after rendering
+            frame.set_cursor(
+                // Put cursor past the end of the input text
+                chunks[1].x + app.input.width() as u16 + 1,
+                // Move one line down, from the border to the input line
+                chunks[1].y + 1,
+            )
+        }
+        Screen::FileInput => frame.set_cursor(
+            chunks[3].x + app.file_path.width() as u16 + 1,
+            chunks[3].y + 1,
+        ),
+        _ => {}
+    }
+
+    match app.screen {
+        Screen::Recovered { revealed } => {
+            frame.render_widget(recovered_block(app, revealed), chunks[2])
+        }
+        _ => render_split_phrase_blocks(app, frame, chunks[2]),
+    }
+
+    frame.render_widget(file_area(app), chunks[3]);
+    frame.render_widget(messages_area(&app.message), chunks[4])
+}
+
+fn bold(text: &str) -> Span<'_> {
+    Span::styled(text, Style::default().add_modifier(Modifier::BOLD))
+}
+
+fn help_message_block(app: &CombineApp) -> Paragraph<'_> {
+    let (mut text, style) = match app.screen {
+        Screen::WordInput(InputMode::Normal) => (
+            {
+                let mut texts = Text::from(Spans::from(vec![
+                    Span::raw("Press "),
+                    bold("q "),
+                    Span::raw("to exit, "),
+                    bold("i "),
+                    Span::raw("to start entering words, "),
+                    bold("o "),
+                    Span::raw("to load the split phrase from a file, "),
+                    bold("↓ "),
+                    Span::raw("or "),
+                    bold("<TAB> "),
+                    Span::raw("to access the word list"),
+                ]));
+
+                texts.extend(Text::from(Spans::from(vec![
+                    bold("      ← → "),
+                    Span::raw("to switch split phrase, "),
+                    bold("w "),
+                    Span::raw(format!(
+                        "to change the split phrase length ({} words)",
+                        app.split_phrase_length
+                    )),
+                ])));
+
+                if app.all_split_phrases_valid() {
+                    texts.extend(Text::from(Spans::from(vec![
+                        bold("      <ENTER> "),
+                        Span::raw("to recover your mnemonic"),
+                    ])));
+                }
+
+                texts
+            },
+            Style::default(),
+        ),
+
+        Screen::WordInput(InputMode::Inserting | InputMode::Editing(_)) => (
+            Text::from(Spans::from(vec![
+                Span::raw("Press "),
+                bold("Esc "),
+                Span::raw("to stop editing, "),
+                bold("Enter"),
+                Span::raw(" to add the word, "),
+                bold("↓ "),
+                Span::raw("to access the word list, "),
+                bold("<TAB> "),
+                Span::raw("to see the next autocomplete word"),
+            ])),
+            Style::default(),
+        ),
+
+        Screen::List => (
+            {
+                let mut texts = Text::from(Spans::from(vec![
+                    Span::raw("Press "),
+                    bold("<TAB> "),
+                    Span::raw("to go to normal mode, "),
+                    bold("i "),
+                    Span::raw("to add new words, "),
+                    bold("← → "),
+                    Span::raw("to switch split phrase"),
+                ]));
+
+                texts.extend(Text::from(Spans::from(vec![
+                    bold("      <ALT> + ↓ "),
+                    Span::raw("to move word down, "),
+                    bold("<ALT> + ↑ "),
+                    Span::raw("to move word up, "),
+                ])));
+
+                texts.extend(Text::from(Spans::from(vec![
+                    bold("      d "),
+                    Span::raw("to delete word, "),
+                    bold("e "),
+                    Span::raw("to edit word "),
+                ])));
+
+                if app.all_split_phrases_valid() {
+                    texts.extend(Text::from(Spans::from(vec![
+                        bold("      <ENTER> "),
+                        Span::raw("to recover your mnemonic"),
+                    ])));
+                }
+
+                texts
+            },
+            Style::default(),
+        ),
+
+        Screen::FileInput => (
+            Text::from(Spans::from(vec![
+                Span::raw("Press "),
+                bold("<ENTER> "),
+                Span::raw("to load split phrase "),
+                Span::raw((app.current + 1).to_string()),
+                Span::raw(" from the file below, "),
+                bold("Esc "),
+                Span::raw("to cancel"),
+            ])),
+            Style::default(),
+        ),
+
+        Screen::Recovered { revealed } => (
+            Text::from(Spans::from(vec![
+                Span::raw("Press "),
+                bold("r "),
+                Span::raw(if revealed {
+                    "to hide your mnemonic, "
+                } else {
+                    "to reveal your mnemonic, "
+                }),
+                bold("Esc "),
+                Span::raw("to go back, "),
+                bold("q "),
+                Span::raw("to exit"),
+            ])),
+            Style::default().add_modifier(Modifier::RAPID_BLINK),
+        ),
+    };
+
+    text.patch_style(style);
+    Paragraph::new(text)
+}
+
+fn input_block(app: &CombineApp) -> Paragraph<'_> {
+    let input_text = match app.screen {
+        Screen::WordInput(InputMode::Inserting | InputMode::Editing(_)) => {
+            let autocomplete = if app.autocomplete.len() >= app.input.len() {
+                &app.autocomplete[app.input.len()..]
+            } else {
+                app.autocomplete
+            };
+
+            vec![Spans::from(vec![
+                Span::raw(&app.input),
+                Span::styled(autocomplete, Style::default().fg(Color::DarkGray)),
+            ])]
+        }
+        _ => vec![Spans::from(Span::raw(""))],
+    };
+
+    let title = format!("Input (split phrase {})", app.current + 1);
+
+    Paragraph::new(input_text)
+        .style(match app.screen {
+            Screen::WordInput(InputMode::Inserting) => Style::default().fg(Color::Yellow),
+            Screen::WordInput(InputMode::Editing(_)) => Style::default().fg(Color::Yellow),
+            _ => Style::default(),
+        })
+        .block(Block::default().borders(Borders::ALL).title(title))
+}
+
+fn split_phrase_block(app: &CombineApp, index: usize) -> List<'static> {
+    let status = app.split_phrase_status(index);
+    let title = format!("{} of 3 ({})", index + 1, status.description());
+
+    let border = match status {
+        SplitPhraseStatus::Valid => Style::default().fg(Color::Green),
+        SplitPhraseStatus::Empty | SplitPhraseStatus::WrongLength { .. } => {
+            Style::default().fg(Color::DarkGray)
+        }
+        _ => Style::default().fg(Color::Red),
+    };
+
+    let border = match app.screen {
+        Screen::List if app.current == index => border.fg(Color::Yellow),
+        _ if app.current == index => border.add_modifier(Modifier::BOLD),
+        _ => border,
+    };
+
+    let words: Vec<ListItem> = app.split_phrases[index]
+        .items
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let content = vec![Spans::from(Span::raw(format!("{}: {}", i + 1, m)))];
+            ListItem::new(content)
+        })
+        .collect();
+
+    // Create a List from all list items and highlight the currently selected one
+    List::new(words)
+        .style(Style::default())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_style(border),
+        )
+        .highlight_style(
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::White),
+        )
+        .highlight_symbol("> ")
+}
+
+fn render_split_phrase_blocks(app: &mut CombineApp, frame: &mut Frame<Backend>, area: Rect) {
+    let sections = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+                Constraint::Percentage(34),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    for (index, section) in sections.iter().enumerate() {
+        let block = split_phrase_block(app, index);
+        frame.render_stateful_widget(block, *section, &mut app.split_phrases[index].state)
+    }
+}
+
+fn recovered_block(app: &CombineApp, revealed: bool) -> Paragraph<'_> {
+    let fingerprint = app.fingerprint.as_deref().unwrap_or("");
+
+    let mut text = Text::from(Spans::from(vec![
+        Span::raw("Mnemonic fingerprint: "),
+        bold(fingerprint),
+    ]));
+    text.extend(Text::raw(""));
+
+    match (&app.recovered, revealed) {
+        (Some(mnemonic), true) => {
+            for (index, word) in mnemonic.split(' ').enumerate() {
+                text.extend(Text::raw(format!("{}: {}", index + 1, word)))
+            }
+        }
+        _ => text.extend(Text::from(Spans::from(vec![
+            Span::raw("Your mnemonic was recovered successfully, press "),
+            bold("r "),
+            Span::raw("to reveal it, make sure no one can see your screen"),
+        ]))),
+    }
+
+    Paragraph::new(text).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Recovered mnemonic")
+            .border_style(Style::default().fg(Color::Green)),
+    )
+}
+
+fn file_area(app: &CombineApp) -> Paragraph<'_> {
+    let style = match app.screen {
+        Screen::FileInput => Style::default().fg(Color::Yellow),
+        _ => Style::default().fg(Color::DarkGray),
+    };
+
+    let input_text = vec![Spans::from(vec![Span::raw(&app.file_path)])];
+
+    Paragraph::new(input_text)
+        .style(style.add_modifier(Modifier::RAPID_BLINK))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Load from file")
+                .border_style(style),
+        )
+}
@@ -0,0 +1,181 @@
+//! Exit codes and error reporting for the CLI commands, so scripts can tell why a command failed
+//! without parsing the error message
+
+use crate::input::UnaskableError;
+use crate::output::OutputFormat;
+use crate::qr::QrImageError;
+use splitmonic::{
+    codex32::Codex32Error, seed_xor::SeedXorError, shamir::ShamirError, share_file::ShareFileError,
+    sskr::SskrError, validation,
+};
+
+/// The exit codes splitmonic uses, these are stable and won't change between versions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExitCode {
+    /// any error that doesn't fit one of the other exit codes
+    Other = 1,
+
+    /// an invalid mnemonic, split phrase or secret phrase (wrong words, checksum, length...)
+    InvalidInput = 2,
+
+    /// the split phrases are from different sets
+    MismatchedSet = 3,
+
+    /// fewer split phrases were given than are needed to recover the mnemonic
+    NotEnoughShares = 4,
+
+    /// a file or the terminal couldn't be read from or written to
+    Io = 5,
+}
+
+impl ExitCode {
+    /// The name of the exit code in JSON output
+    pub fn kind(self) -> &'static str {
+        match self {
+            Self::Other => "other",
+            Self::InvalidInput => "invalid_input",
+            Self::MismatchedSet => "mismatched_set",
+            Self::NotEnoughShares => "not_enough_shares",
+            Self::Io => "io",
+        }
+    }
+
+    fn from_error(error: &eyre::Report) -> Self {
+        error
+            .chain()
+            .find_map(|error| {
+                if let Some(error) = error.downcast_ref::<splitmonic::Error>() {
+                    Some(Self::from_splitmonic_error(error))
+                } else if let Some(error) = error.downcast_ref::<validation::Error>() {
+                    Some(Self::from_validation_error(error))
+                } else if error.is::<UnaskableError>() {
+                    Some(Self::InvalidInput)
+                } else if let Some(error) = error.downcast_ref::<QrImageError>() {
+                    Some(match error {
+                        QrImageError::Unreadable { .. } => Self::Io,
+                        _ => Self::InvalidInput,
+                    })
+                } else if error.is::<std::io::Error>() {
+                    Some(Self::Io)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(Self::Other)
+    }
+
+    fn from_splitmonic_error(error: &splitmonic::Error) -> Self {
+        use splitmonic::Error;
+
+        match error {
+            Error::NotEnoughShares { .. } => Self::NotEnoughShares,
+            Error::MismatchedSet(..) => Self::MismatchedSet,
+            Error::Shamir(ShamirError::NotEnoughShares { .. }) => Self::NotEnoughShares,
+            Error::Sskr(SskrError::NotEnoughShares(_)) => Self::NotEnoughShares,
+            Error::Sskr(SskrError::MismatchedShares) => Self::MismatchedSet,
+            Error::Codex32(Codex32Error::NotEnoughShares { .. }) => Self::NotEnoughShares,
+            Error::Codex32(Codex32Error::MismatchedShares) => Self::MismatchedSet,
+            Error::SeedXor(SeedXorError::NotEnoughParts(_)) => Self::NotEnoughShares,
+            Error::ShareFile(ShareFileError::Unreadable { .. }) => Self::Io,
+            Error::ShareToPhrase | Error::Fingerprint | Error::FailedVerification(_) => Self::Other,
+            _ => Self::InvalidInput,
+        }
+    }
+
+    fn from_validation_error(error: &validation::Error) -> Self {
+        use validation::Error;
+
+        match error {
+            Error::MismatchedSet { .. } => Self::MismatchedSet,
+            Error::PhrasesLengthThreshold {
+                expected, given, ..
+            } if given < expected => Self::NotEnoughShares,
+            _ => Self::InvalidInput,
+        }
+    }
+}
+
+/// A failed command, what was being done when it failed and why
+#[derive(Debug)]
+pub struct Failure {
+    context: &'static str,
+    error: eyre::Report,
+}
+
+impl Failure {
+    pub fn new<E: Into<eyre::Report>>(context: &'static str, error: E) -> Self {
+        Self {
+            context,
+            error: error.into(),
+        }
+    }
+
+    pub fn exit_code(&self) -> ExitCode {
+        ExitCode::from_error(&self.error)
+    }
+
+    /// Prints the error, to stderr for text output or as JSON to stdout, and returns the exit
+    /// code to exit with
+    pub fn report(&self, format: OutputFormat) -> i32 {
+        let exit_code = self.exit_code();
+
+        match format {
+            OutputFormat::Text => eprintln!("{}: {}", self.context, self.error),
+            OutputFormat::Json => println!(
+                "{}",
+                serde_json::json!({
+                    "error": {
+                        "kind": exit_code.kind(),
+                        "exit_code": exit_code as i32,
+                        "message": format!("{}: {}", self.context, self.error),
+                    }
+                })
+            ),
+        }
+
+        exit_code as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_exit_code_from_the_error() {
+        let not_enough = splitmonic::Error::NotEnoughShares {
+            gave: 2,
+            expected: 3,
+        };
+        assert_eq!(
+            Failure::new("combining", not_enough).exit_code(),
+            ExitCode::NotEnoughShares
+        );
+
+        let mismatched = validation::Error::MismatchedSet {
+            expected: "gun dismiss area".to_string(),
+            given: vec![(2, "embody fog drop".to_string())],
+        };
+        assert_eq!(
+            Failure::new("combining", mismatched).exit_code(),
+            ExitCode::MismatchedSet
+        );
+
+        let unreadable = splitmonic::share_file::read_share_file("/splitmonic/missing.txt");
+        assert_eq!(
+            Failure::new("combining", unreadable.unwrap_err()).exit_code(),
+            ExitCode::Io
+        );
+
+        let checksum = validation::validate_mnemonic_code("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon");
+        assert_eq!(
+            Failure::new("splitting", checksum.unwrap_err()).exit_code(),
+            ExitCode::InvalidInput
+        );
+
+        assert_eq!(
+            Failure::new("splitting", eyre::eyre!("something else")).exit_code(),
+            ExitCode::Other
+        );
+    }
+}
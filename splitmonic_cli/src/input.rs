@@ -0,0 +1,221 @@
+//! Reading secrets (mnemonics and split phrases) without putting them on the command line, where
+//! they would end up in the shell history and in the process list
+
+use eyre::{Context, Result};
+use splitmonic::{
+    validation::{MNEMONIC_LENGTHS, SPLIT_PHRASE_LENGTHS},
+    wordlist::{English, Wordlist},
+};
+use std::io::Read;
+
+/// Warns that the secret was passed as an argument, `secret` is the name of the argument, which has
+/// `--<secret>-stdin` and `--<secret>-fd` options to read it from instead
+pub fn warn_secret_in_arguments(secret: &str) {
+    eprintln!("Warning: secrets passed as arguments end up in your shell history and can be seen by other users in the process list.");
+    eprintln!(
+        "Use --{0}-stdin or --{0}-fd to read them from stdin or a file descriptor instead.\n",
+        secret
+    );
+}
+
+pub fn read_stdin() -> Result<String> {
+    let mut contents = String::new();
+    std::io::stdin()
+        .read_to_string(&mut contents)
+        .wrap_err("Unable to read from stdin")?;
+
+    Ok(contents)
+}
+
+#[cfg(unix)]
+pub fn read_fd(fd: i32) -> Result<String> {
+    use std::{fs::File, os::unix::io::FromRawFd};
+
+    // stdin, stdout and stderr are still used by splitmonic, and would be closed with the file
+    if fd <= 2 {
+        return Err(eyre::eyre!(
+            "Invalid file descriptor: {}, use a file descriptor above 2, or the -stdin option to read from stdin",
+            fd
+        ));
+    }
+
+    // Safety: fcntl with F_GETFD only reads the flags of the file descriptor, it fails if the file
+    // descriptor isn't open
+    if unsafe { libc::fcntl(fd, libc::F_GETFD) } == -1 {
+        return Err(std::io::Error::last_os_error())
+            .wrap_err_with(|| format!("File descriptor {} is not open", fd));
+    }
+
+    // Safety: the file descriptor is open and is given to us by the user to read the secret from,
+    // it isn't stdin, stdout or stderr and isn't used anywhere else in splitmonic, so the file can
+    // own and close it
+    let mut file = unsafe { File::from_raw_fd(fd) };
+
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)
+        .wrap_err_with(|| format!("Unable to read from file descriptor: {}", fd))?;
+
+    Ok(contents)
+}
+
+#[cfg(not(unix))]
+pub fn read_fd(_fd: i32) -> Result<String> {
+    Err(eyre::eyre!(
+        "Reading from a file descriptor is only supported on unix"
+    ))
+}
+
+/// A question that can't be asked because the output is JSON or there's no terminal to ask it on,
+/// the answer has to be given as an option instead
+#[derive(Debug, thiserror::Error)]
+#[error("{question} can't be asked {reason}, use {option} instead")]
+pub struct UnaskableError {
+    pub question: &'static str,
+    pub reason: &'static str,
+    pub option: &'static str,
+}
+
+/// Whether there's a terminal to ask questions on, stdin may be where the secrets were read from so
+/// questions are always asked on the terminal itself
+#[cfg(unix)]
+pub fn has_terminal() -> bool {
+    std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .is_ok()
+}
+
+#[cfg(not(unix))]
+pub fn has_terminal() -> bool {
+    use std::io::IsTerminal;
+
+    std::io::stdin().is_terminal()
+}
+
+/// Asks a yes or no question on the terminal, stdin may be where the secrets were read from so the
+/// answer is always read from the terminal
+pub fn confirm(question: &str) -> Result<bool> {
+    let answer = read_line_from_tty(&format!("{} [y/N]: ", question)).wrap_err(
+        "Unable to read from the terminal, use --expect-fingerprint when there's no terminal",
+    )?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+#[cfg(unix)]
+fn read_line_from_tty(prompt: &str) -> std::io::Result<String> {
+    use std::io::{BufRead, BufReader, Write};
+
+    let mut tty = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")?;
+    tty.write_all(prompt.as_bytes())?;
+    tty.flush()?;
+
+    let mut line = String::new();
+    BufReader::new(tty).read_line(&mut line)?;
+    Ok(line)
+}
+
+#[cfg(not(unix))]
+fn read_line_from_tty(prompt: &str) -> std::io::Result<String> {
+    eprint!("{}", prompt);
+
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    Ok(line)
+}
+
+/// Splits the text read from stdin or a file descriptor into split phrases, one per line
+pub fn split_phrases_from_text(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<&str>>().join(" "))
+        .filter(|phrase| !phrase.is_empty())
+        .collect()
+}
+
+/// Prompts for the words of a mnemonic one at a time without showing them, each word is checked
+/// against the word list as it's entered
+pub fn prompt_mnemonic_code() -> Result<String> {
+    eprintln!("Enter the words of your mnemonic, the words won't be shown as you type");
+    eprintln!(
+        "Mnemonics are {:?} words long, press enter on an empty word to finish a shorter mnemonic",
+        MNEMONIC_LENGTHS
+    );
+
+    let max_length = MNEMONIC_LENGTHS[MNEMONIC_LENGTHS.len() - 1];
+    let words = prompt_words("Word", max_length, Some(&MNEMONIC_LENGTHS))?;
+    Ok(words.join(" "))
+}
+
+/// Prompts for split phrases, `threshold` of them, one word at a time without showing them
+pub fn prompt_split_phrases(threshold: usize) -> Result<Vec<String>> {
+    eprintln!(
+        "Enter {} of your split phrases, the words won't be shown as you type",
+        threshold
+    );
+    eprintln!(
+        "Split phrases are {:?} words long, press enter on an empty word to finish a shorter split phrase",
+        SPLIT_PHRASE_LENGTHS
+    );
+
+    let max_length = SPLIT_PHRASE_LENGTHS[SPLIT_PHRASE_LENGTHS.len() - 1];
+
+    (1..=threshold)
+        .map(|number| {
+            let label = format!("Split phrase {}, word", number);
+            prompt_words(&label, max_length, Some(&SPLIT_PHRASE_LENGTHS))
+                .map(|words| words.join(" "))
+        })
+        .collect()
+}
+
+/// Prompts until `count` valid words are entered, an empty word finishes early if the number of
+/// words entered so far is one of the `finish_at` lengths
+fn prompt_words(label: &str, count: usize, finish_at: Option<&[usize]>) -> Result<Vec<String>> {
+    let mut words = Vec::with_capacity(count);
+
+    while words.len() < count {
+        let prompt = format!("{} {} of {}: ", label, words.len() + 1, count);
+        let input = rpassword::read_password_from_tty(Some(&prompt))
+            .wrap_err("Unable to read from the terminal")?;
+        let input = input.trim().to_lowercase();
+
+        if input.is_empty() {
+            match finish_at {
+                Some(lengths) if lengths.contains(&words.len()) => break,
+                _ => continue,
+            }
+        }
+
+        match English::starting_with(&input).as_slice() {
+            // accept the exact word, or a prefix of only one word (the first 4 letters are enough)
+            [word, ..] if *word == input => words.push(word.to_string()),
+            [only_one] => words.push(only_one.to_string()),
+            [] => eprintln!("  not a valid word, enter word {} again", words.len() + 1),
+            _ => eprintln!(
+                "  more than one word starts with that, enter word {} again",
+                words.len() + 1
+            ),
+        }
+    }
+
+    Ok(words)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_file_descriptors_it_cant_own() {
+        for fd in [-1, 0, 1, 2] {
+            assert!(read_fd(fd).is_err(), "{}", fd);
+        }
+
+        let error = read_fd(i32::MAX).unwrap_err();
+        assert!(error.to_string().contains("is not open"));
+    }
+}
@@ -1,15 +1,40 @@
+mod cards;
+mod combine_app;
+mod exit;
+mod input;
+mod output;
+mod qr;
 mod split_app;
+#[cfg(test)]
+mod temp_dir;
 mod ui;
 
+use crate::combine_app::CombineApp;
+use crate::exit::Failure;
+use crate::output::OutputFormat;
 use crate::split_app::SplitApp;
-use crossbeam_channel::unbounded;
+use crossbeam_channel::{unbounded, Sender};
 use crossterm::{
-    event::{self, Event as CEvent},
+    event::{self, Event as CEvent, KeyEvent},
     execute, terminal,
 };
 use eyre::{Context, Result};
+use splitmonic::{
+    codex32,
+    encoding::Encoding,
+    generate::{EntropySource, ExtraEntropy},
+    inspect::SplitPhraseInfo,
+    missing_words::Known,
+    qr::SeedQrFormat,
+    sskr::{GroupSpec, Share},
+    ssss,
+    vault::{self, VaultEncoding},
+    word_numbers::{self, WordNumbers},
+    Scheme,
+};
 use std::{
     io::{self, Stdout},
+    path::PathBuf,
     thread,
     time::{Duration, Instant},
 };
@@ -23,7 +48,7 @@ use structopt::StructOpt;
 
 /// Split your BIP39 mnemonic phrase using shamir secret sharing
 #[derive(StructOpt, Debug)]
-#[structopt(name = "splitmonic", global_settings = &[AppSettings::ColoredHelp, AppSettings::ArgRequiredElseHelp])]
+#[structopt(name = "splitmonic", global_settings = &[AppSettings::ColoredHelp], setting = AppSettings::ArgRequiredElseHelp)]
 enum Splitmonic {
     #[structopt(
         name = "split",
@@ -36,11 +61,180 @@ enum Splitmonic {
         #[structopt(
             short,
             long,
-            help = "your mnemonic",
-            required_unless = "interactive",
-            conflicts_with = "interactive"
+            help = "your mnemonic, leave out to be prompted for it without it being shown",
+            conflicts_with_all = &["interactive", "mnemonic-stdin", "mnemonic-fd"]
         )]
         mnemonic: Option<String>,
+
+        #[structopt(
+            long,
+            help = "read your mnemonic from stdin",
+            conflicts_with_all = &["interactive", "mnemonic-fd"]
+        )]
+        mnemonic_stdin: bool,
+
+        #[structopt(
+            long,
+            help = "read your mnemonic from this file descriptor",
+            conflicts_with = "interactive"
+        )]
+        mnemonic_fd: Option<i32>,
+
+        #[structopt(
+            long,
+            help = "skip recovering your mnemonic from every combination of 3 split phrases after splitting"
+        )]
+        no_verify: bool,
+
+        #[structopt(
+            short,
+            long,
+            help = "write the split phrases to files instead of showing them, give 1 directory for all of them or 5 directories (one for each split phrase)",
+            conflicts_with = "interactive",
+            parse(from_os_str)
+        )]
+        output_dir: Option<Vec<PathBuf>>,
+
+        #[structopt(
+            long,
+            default_value = output::DEFAULT_FILE_NAME_TEMPLATE,
+            help = "file name for each split phrase, {n} is the split phrase number, {total} the number of split phrases and {fingerprint} the mnemonic fingerprint, used with --output-dir"
+        )]
+        file_name: String,
+
+        #[structopt(
+            long,
+            help = "write a printable card (PDF, SVG and HTML) for each split phrase to this directory",
+            conflicts_with = "interactive",
+            parse(from_os_str)
+        )]
+        print_cards: Option<PathBuf>,
+
+        #[structopt(
+            long,
+            possible_values = &["standard", "compact"],
+            help = "also show the 24 words after the set and share id of each split phrase as a SeedQR grid to copy by hand, standard or compact"
+        )]
+        seed_qr: Option<SeedQrFormat>,
+
+        #[structopt(
+            long,
+            default_value = "splitmonic",
+            possible_values = &["splitmonic", "sskr", "codex32", "xor"],
+            help = "split into splitmonic split phrases, SSKR shares shown as Bytewords and ur:sskr URs, codex32 (BIP-93) shares or Seed XOR parts"
+        )]
+        scheme: Scheme,
+
+        #[structopt(
+            long,
+            default_value = "words",
+            possible_values = &["words", "indexes", "padded-indexes", "binary", "hex", "base32", "base58", "bytewords"],
+            help = "how to write the split phrases: BIP39 words, word numbers (1 to 2048), 4 digit word numbers (0001 to 2048), 11 bit binary word indexes, hex, base32, base58 or Bytewords, used with --scheme splitmonic"
+        )]
+        encoding: Encoding,
+
+        #[structopt(
+            long,
+            help = "the SSKR groups as threshold-of-count, ex: 2-of-3,3-of-5, used with --scheme sskr (default: 3-of-5)",
+            use_delimiter = true
+        )]
+        groups: Option<Vec<GroupSpec>>,
+
+        #[structopt(
+            long,
+            help = "how many SSKR groups are needed to recover your mnemonic, used with --scheme sskr (default: 1)"
+        )]
+        group_threshold: Option<u8>,
+
+        #[structopt(
+            long,
+            help = "how many codex32 shares are needed to recover your mnemonic, used with --scheme codex32 (default: 3)"
+        )]
+        threshold: Option<u8>,
+
+        #[structopt(
+            long,
+            help = "how many codex32 shares to create, used with --scheme codex32 (default: 5)"
+        )]
+        shares: Option<usize>,
+
+        #[structopt(
+            long,
+            help = "the 4 character codex32 identifier, used with --scheme codex32 (default: random)"
+        )]
+        identifier: Option<String>,
+
+        #[structopt(
+            long,
+            help = "how many Seed XOR parts to create, all of them are needed to recover your mnemonic, used with --scheme xor (default: 3)"
+        )]
+        parts: Option<usize>,
+
+        #[structopt(
+            long,
+            default_value = "text",
+            possible_values = &["text", "json"],
+            help = "print the result as text or as json"
+        )]
+        format: OutputFormat,
+    },
+    #[structopt(
+        name = "generate",
+        about = "Generate a new mnemonic and split it into split phrases, without ever showing it"
+    )]
+    Generate {
+        #[structopt(short, long, help = "use the interactive TUI")]
+        interactive: bool,
+
+        #[structopt(
+            short,
+            long,
+            default_value = "24",
+            possible_values = &["12", "18", "24"],
+            help = "number of words in the generated mnemonic"
+        )]
+        words: usize,
+
+        #[structopt(
+            long,
+            help = "d6 dice rolls to mix in with the generated randomness, ex: 3,1,6,6,2,4",
+            conflicts_with_all = &["coin-flips", "from-rolls"]
+        )]
+        dice: Option<String>,
+
+        #[structopt(
+            long,
+            help = "coin flips (h/t) to mix in with the generated randomness, ex: hhtht",
+            conflicts_with = "from-rolls"
+        )]
+        coin_flips: Option<String>,
+
+        #[structopt(
+            long,
+            help = "create the mnemonic only from these rolls, without any generated randomness, so it can be checked by hand"
+        )]
+        from_rolls: Option<String>,
+
+        #[structopt(
+            long,
+            default_value = "d6",
+            possible_values = &["coin", "d6", "d20", "cards"],
+            help = "what the rolls given to --from-rolls are"
+        )]
+        source: EntropySource,
+
+        #[structopt(
+            long,
+            help = "also print the generated mnemonic",
+            conflicts_with = "interactive"
+        )]
+        show_mnemonic: bool,
+
+        #[structopt(
+            long,
+            help = "skip recovering your mnemonic from every combination of 3 split phrases after splitting"
+        )]
+        no_verify: bool,
     },
     #[structopt(
         name = "combine",
@@ -51,168 +245,1768 @@ enum Splitmonic {
         interactive: bool,
 
         #[structopt(
-            short="s",
+            short = "s",
             long,
-            help = "3 of 5 split phrases",
-            required_unless_one = &["split-phrases-1", "split-phrases-2", "split-phrases-3", "interactive", "split-phrase-files"],
+            help = "3 of 5 split phrases (or as many SSKR shares, codex32 shares or Seed XOR parts as are needed), leave out to be prompted for them without them being shown",
             conflicts_with = "interactive",
             use_delimiter = true,
-            min_values = 3,
-            max_values = 3
+            min_values = 1
         )]
         all_split_phrases: Option<Vec<String>>,
 
-        #[structopt(short="f", long, 
-        help = "list of files containing your split phrases",             
-        required_unless_one = &["split-phrases-1", "split-phrases-2", "split-phrases-3", "interactive", "all-split-phrases"],
-        conflicts_with = "interactive",
-        use_delimiter = true,
-        min_values = 1,
-        max_values = 3
+        #[structopt(
+            short = "f",
+            long,
+            help = "list of files containing your split phrases",
+            conflicts_with = "interactive",
+            use_delimiter = true,
+            min_values = 1
         )]
         split_phrase_files: Option<Vec<String>>,
 
+        #[structopt(
+            long = "qr-image",
+            help = "list of PNG or JPEG photos or screenshots of your split phrase QR codes",
+            conflicts_with = "interactive",
+            use_delimiter = true,
+            min_values = 1,
+            parse(from_os_str)
+        )]
+        qr_images: Option<Vec<PathBuf>>,
+
         #[structopt(
             short = "1",
             visible_alias = "sp1",
             long,
-            help = "first split phrase",
+            help = "first split phrase, its words (or tokens, with another --encoding)",
             conflicts_with = "interactive",
             use_delimiter = true,
-            min_values = 28,
-            max_values = 28
+            min_values = 1
         )]
         split_phrases_1: Option<Vec<String>>,
 
-        #[structopt(
-            short = "2",
-            visible_alias = "sp2",
-            long,
-            help = "second split phrase",
-            conflicts_with = "interactive",
-            use_delimiter = true,
-            min_values = 28,
-            max_values = 28
-        )]
-        split_phrases_2: Option<Vec<String>>,
+        #[structopt(
+            short = "2",
+            visible_alias = "sp2",
+            long,
+            help = "second split phrase, its words (or tokens, with another --encoding)",
+            conflicts_with = "interactive",
+            use_delimiter = true,
+            min_values = 1
+        )]
+        split_phrases_2: Option<Vec<String>>,
+
+        #[structopt(
+            short = "3",
+            visible_alias = "sp3",
+            long,
+            help = "third split phrase, its words (or tokens, with another --encoding)",
+            conflicts_with = "interactive",
+            use_delimiter = true,
+            min_values = 1
+        )]
+        split_phrases_3: Option<Vec<String>>,
+
+        #[structopt(
+            long,
+            help = "read your split phrases from stdin, one per line",
+            conflicts_with_all = &["interactive", "split-phrases-fd", "all-split-phrases", "split-phrase-files", "qr-images"]
+        )]
+        split_phrases_stdin: bool,
+
+        #[structopt(
+            long,
+            help = "read your split phrases from this file descriptor, one per line",
+            conflicts_with_all = &["interactive", "all-split-phrases", "split-phrase-files", "qr-images"]
+        )]
+        split_phrases_fd: Option<i32>,
+
+        #[structopt(
+            long,
+            possible_values = &["standard", "compact"],
+            help = "also show the recovered mnemonic as a SeedQR grid to copy by hand, standard or compact"
+        )]
+        seed_qr: Option<SeedQrFormat>,
+
+        #[structopt(
+            long,
+            default_value = "words",
+            possible_values = &["words", "indexes", "padded-indexes", "binary", "hex", "base32", "base58", "bytewords"],
+            help = "how the split phrases are written, the encoding they were split with"
+        )]
+        encoding: Encoding,
+
+        #[structopt(
+            long,
+            help = "the fingerprint shown when your mnemonic was split with Seed XOR, checked instead of asking you to confirm it"
+        )]
+        expect_fingerprint: Option<String>,
+
+        #[structopt(
+            long,
+            possible_values = &["decimal", "padded", "binary"],
+            help = "also show the word numbers of the recovered mnemonic, for steel plates and stamping kits: decimal (1 to 2048), padded (0001 to 2048) or binary (11 bits)"
+        )]
+        word_numbers: Option<WordNumbers>,
+
+        #[structopt(
+            long,
+            default_value = "text",
+            possible_values = &["text", "json"],
+            help = "print the result as text or as json"
+        )]
+        format: OutputFormat,
+    },
+    #[structopt(
+        name = "inspect",
+        about = "Show which set and share a split phrase belongs to, without recovering anything"
+    )]
+    Inspect {
+        #[structopt(
+            short = "s",
+            long,
+            help = "the split phrase to inspect",
+            required_unless = "file",
+            conflicts_with = "file",
+            use_delimiter = true
+        )]
+        split_phrase: Option<Vec<String>>,
+
+        #[structopt(short, long, help = "file containing the split phrase to inspect")]
+        file: Option<String>,
+
+        #[structopt(
+            long,
+            default_value = "text",
+            possible_values = &["text", "json"],
+            help = "print the result as text or as json"
+        )]
+        format: OutputFormat,
+    },
+    #[structopt(
+        name = "recover-words",
+        about = "Recover a mnemonic with one or two missing words"
+    )]
+    RecoverWords {
+        #[structopt(
+            short,
+            long,
+            help = "your mnemonic, with a ? or _ in place of each missing word",
+            required_unless_one = &["mnemonic-stdin", "mnemonic-fd"],
+            conflicts_with_all = &["mnemonic-stdin", "mnemonic-fd"]
+        )]
+        mnemonic: Option<String>,
+
+        #[structopt(
+            long,
+            help = "read your mnemonic, with a ? or _ in place of each missing word, from stdin",
+            conflicts_with = "mnemonic-fd"
+        )]
+        mnemonic_stdin: bool,
+
+        #[structopt(
+            long,
+            help = "read your mnemonic, with a ? or _ in place of each missing word, from this file descriptor"
+        )]
+        mnemonic_fd: Option<i32>,
+
+        #[structopt(
+            short,
+            long,
+            help = "the fingerprint of your mnemonic, to find the one correct mnemonic",
+            conflicts_with = "address"
+        )]
+        fingerprint: Option<String>,
+
+        #[structopt(
+            short,
+            long,
+            help = "one of the first 20 receive addresses of your first account, to find the one correct mnemonic"
+        )]
+        address: Option<String>,
+    },
+    #[structopt(
+        name = "split-secret",
+        about = "Split any secret (password, private key, etc...) into multiple secret phrases"
+    )]
+    SplitSecret {
+        #[structopt(
+            short,
+            long,
+            help = "your secret, as text",
+            required_unless_one = &["hex", "file", "secret-stdin", "secret-fd", "hex-stdin", "hex-fd"],
+            conflicts_with_all = &["hex", "file", "secret-stdin", "secret-fd", "hex-stdin", "hex-fd"]
+        )]
+        secret: Option<String>,
+
+        #[structopt(
+            long,
+            help = "your secret, as hex",
+            conflicts_with_all = &["file", "secret-stdin", "secret-fd", "hex-stdin", "hex-fd"]
+        )]
+        hex: Option<String>,
+
+        #[structopt(
+            long,
+            help = "read your secret, as text, from stdin",
+            conflicts_with_all = &["file", "secret-fd", "hex-stdin", "hex-fd"]
+        )]
+        secret_stdin: bool,
+
+        #[structopt(
+            long,
+            help = "read your secret, as text, from this file descriptor",
+            conflicts_with_all = &["file", "hex-stdin", "hex-fd"]
+        )]
+        secret_fd: Option<i32>,
+
+        #[structopt(
+            long,
+            help = "read your secret, as hex, from stdin",
+            conflicts_with_all = &["file", "hex-fd"]
+        )]
+        hex_stdin: bool,
+
+        #[structopt(
+            long,
+            help = "read your secret, as hex, from this file descriptor",
+            conflicts_with = "file"
+        )]
+        hex_fd: Option<i32>,
+
+        #[structopt(short, long, help = "file containing your secret")]
+        file: Option<String>,
+
+        #[structopt(
+            short,
+            long,
+            default_value = "3",
+            help = "number of secret phrases needed to recover your secret"
+        )]
+        threshold: u8,
+
+        #[structopt(
+            short = "n",
+            long,
+            default_value = "5",
+            help = "number of secret phrases to create"
+        )]
+        shares: u8,
+    },
+    #[structopt(
+        name = "combine-secret",
+        about = "Combine your secret phrases into your original secret"
+    )]
+    CombineSecret {
+        #[structopt(
+            short = "s",
+            long,
+            help = "your secret phrases",
+            required_unless_one = &["secret-phrase-files", "secret-phrases-stdin", "secret-phrases-fd"],
+            conflicts_with_all = &["secret-phrases-stdin", "secret-phrases-fd"],
+            use_delimiter = true
+        )]
+        secret_phrases: Option<Vec<String>>,
+
+        #[structopt(
+            long,
+            help = "read your secret phrases from stdin, one per line",
+            conflicts_with = "secret-phrases-fd"
+        )]
+        secret_phrases_stdin: bool,
+
+        #[structopt(
+            long,
+            help = "read your secret phrases from this file descriptor, one per line"
+        )]
+        secret_phrases_fd: Option<i32>,
+
+        #[structopt(
+            short = "f",
+            long,
+            help = "list of files containing your secret phrases",
+            use_delimiter = true
+        )]
+        secret_phrase_files: Option<Vec<String>>,
+
+        #[structopt(long, help = "print the recovered secret as hex")]
+        hex: bool,
+    },
+    #[structopt(
+        name = "export-vault",
+        about = "Convert your secret phrases or split phrases into HashiCorp Vault shares"
+    )]
+    ExportVault {
+        #[structopt(
+            short = "s",
+            long,
+            help = "your secret phrases",
+            required_unless_one = &["split-phrases", "secret-phrases-stdin", "secret-phrases-fd", "split-phrases-stdin", "split-phrases-fd"],
+            conflicts_with_all = &["split-phrases", "secret-phrases-stdin", "secret-phrases-fd", "split-phrases-stdin", "split-phrases-fd"],
+            use_delimiter = true
+        )]
+        secret_phrases: Option<Vec<String>>,
+
+        #[structopt(
+            long,
+            help = "read your secret phrases from stdin, one per line",
+            conflicts_with_all = &["split-phrases", "secret-phrases-fd", "split-phrases-stdin", "split-phrases-fd"]
+        )]
+        secret_phrases_stdin: bool,
+
+        #[structopt(
+            long,
+            help = "read your secret phrases from this file descriptor, one per line",
+            conflicts_with_all = &["split-phrases", "split-phrases-stdin", "split-phrases-fd"]
+        )]
+        secret_phrases_fd: Option<i32>,
+
+        #[structopt(
+            short = "p",
+            long,
+            help = "your split phrases",
+            conflicts_with_all = &["split-phrases-stdin", "split-phrases-fd"],
+            use_delimiter = true
+        )]
+        split_phrases: Option<Vec<String>>,
+
+        #[structopt(
+            long,
+            help = "read your split phrases from stdin, one per line",
+            conflicts_with = "split-phrases-fd"
+        )]
+        split_phrases_stdin: bool,
+
+        #[structopt(
+            long,
+            help = "read your split phrases from this file descriptor, one per line"
+        )]
+        split_phrases_fd: Option<i32>,
+
+        #[structopt(long, help = "print the Vault shares as hex instead of base64")]
+        hex: bool,
+    },
+    #[structopt(
+        name = "import-vault",
+        about = "Convert HashiCorp Vault shares (unseal keys) into secret phrases"
+    )]
+    ImportVault {
+        #[structopt(
+            short = "k",
+            long,
+            help = "your Vault shares, as base64 (or hex, with --hex)",
+            required_unless_one = &["vault-shares-stdin", "vault-shares-fd"],
+            conflicts_with_all = &["vault-shares-stdin", "vault-shares-fd"],
+            use_delimiter = true
+        )]
+        vault_shares: Option<Vec<String>>,
+
+        #[structopt(
+            long,
+            help = "read your Vault shares from stdin, one per line",
+            conflicts_with = "vault-shares-fd"
+        )]
+        vault_shares_stdin: bool,
+
+        #[structopt(
+            long,
+            help = "read your Vault shares from this file descriptor, one per line"
+        )]
+        vault_shares_fd: Option<i32>,
+
+        #[structopt(
+            short,
+            long,
+            help = "number of shares needed to recover the secret, Vault's key threshold"
+        )]
+        threshold: u8,
+
+        #[structopt(long, help = "read the Vault shares as hex instead of base64")]
+        hex: bool,
+    },
+    #[structopt(
+        name = "export-ssss",
+        about = "Convert your secret phrases or split phrases into ssss shares, for ssss-combine -x"
+    )]
+    ExportSsss {
+        #[structopt(
+            short = "s",
+            long,
+            help = "your secret phrases, as many as are needed to recover the secret",
+            required_unless_one = &["split-phrases", "secret-phrases-stdin", "secret-phrases-fd", "split-phrases-stdin", "split-phrases-fd"],
+            conflicts_with_all = &["split-phrases", "secret-phrases-stdin", "secret-phrases-fd", "split-phrases-stdin", "split-phrases-fd"],
+            use_delimiter = true
+        )]
+        secret_phrases: Option<Vec<String>>,
+
+        #[structopt(
+            long,
+            help = "read your secret phrases from stdin, one per line",
+            conflicts_with_all = &["split-phrases", "secret-phrases-fd", "split-phrases-stdin", "split-phrases-fd"]
+        )]
+        secret_phrases_stdin: bool,
+
+        #[structopt(
+            long,
+            help = "read your secret phrases from this file descriptor, one per line",
+            conflicts_with_all = &["split-phrases", "split-phrases-stdin", "split-phrases-fd"]
+        )]
+        secret_phrases_fd: Option<i32>,
+
+        #[structopt(
+            short = "p",
+            long,
+            help = "3 of your split phrases, the ssss shares are of your mnemonic's entropy",
+            conflicts_with_all = &["split-phrases-stdin", "split-phrases-fd"],
+            use_delimiter = true
+        )]
+        split_phrases: Option<Vec<String>>,
+
+        #[structopt(
+            long,
+            help = "read your split phrases from stdin, one per line",
+            conflicts_with = "split-phrases-fd"
+        )]
+        split_phrases_stdin: bool,
+
+        #[structopt(
+            long,
+            help = "read your split phrases from this file descriptor, one per line"
+        )]
+        split_phrases_fd: Option<i32>,
+
+        #[structopt(
+            short,
+            long,
+            default_value = "3",
+            help = "number of ssss shares needed to recover the secret"
+        )]
+        threshold: u8,
+
+        #[structopt(
+            short = "n",
+            long,
+            default_value = "5",
+            help = "number of ssss shares to create"
+        )]
+        shares: u8,
+    },
+    #[structopt(
+        name = "import-ssss",
+        about = "Convert ssss shares into secret phrases"
+    )]
+    ImportSsss {
+        #[structopt(
+            short = "k",
+            long,
+            help = "your ssss shares, at least the threshold of them",
+            required_unless_one = &["ssss-shares-stdin", "ssss-shares-fd"],
+            conflicts_with_all = &["ssss-shares-stdin", "ssss-shares-fd"],
+            use_delimiter = true
+        )]
+        ssss_shares: Option<Vec<String>>,
+
+        #[structopt(
+            long,
+            help = "read your ssss shares from stdin, one per line",
+            conflicts_with = "ssss-shares-fd"
+        )]
+        ssss_shares_stdin: bool,
+
+        #[structopt(
+            long,
+            help = "read your ssss shares from this file descriptor, one per line"
+        )]
+        ssss_shares_fd: Option<i32>,
+
+        #[structopt(
+            short,
+            long,
+            help = "number of shares needed to recover the secret, the threshold given to ssss-split"
+        )]
+        threshold: u8,
+
+        #[structopt(
+            short = "n",
+            long,
+            default_value = "5",
+            help = "number of secret phrases to create"
+        )]
+        shares: u8,
+    },
+}
+
+impl Splitmonic {
+    fn output_format(&self) -> OutputFormat {
+        match self {
+            Self::Split { format, .. }
+            | Self::Combine { format, .. }
+            | Self::Inspect { format, .. } => *format,
+            _ => OutputFormat::Text,
+        }
+    }
+
+    /// The options given to `split` that its scheme and encoding don't use, each with what it's
+    /// used with instead
+    fn unused_split_options(&self) -> Vec<(&'static str, &'static str)> {
+        let mut unused = vec![];
+
+        if let Self::Split {
+            no_verify,
+            output_dir,
+            file_name,
+            print_cards,
+            seed_qr,
+            scheme,
+            encoding,
+            groups,
+            group_threshold,
+            threshold,
+            shares,
+            identifier,
+            parts,
+            ..
+        } = self
+        {
+            let splitmonic = *scheme == Scheme::Splitmonic;
+            let words = splitmonic && *encoding == Encoding::Words;
+            let mut check = |option, given: bool, used: bool, used_with| {
+                if given && !used {
+                    unused.push((option, used_with));
+                }
+            };
+
+            check("--no-verify", *no_verify, splitmonic, "--scheme splitmonic");
+            check(
+                "--encoding",
+                *encoding != Encoding::Words,
+                splitmonic,
+                "--scheme splitmonic",
+            );
+
+            let with_words = "split phrases written as words";
+            check("--output-dir", output_dir.is_some(), words, with_words);
+            check(
+                "--file-name",
+                file_name != output::DEFAULT_FILE_NAME_TEMPLATE,
+                words,
+                with_words,
+            );
+            check("--print-cards", print_cards.is_some(), words, with_words);
+            check("--seed-qr", seed_qr.is_some(), words, with_words);
+
+            let sskr = *scheme == Scheme::Sskr;
+            check("--groups", groups.is_some(), sskr, "--scheme sskr");
+            check(
+                "--group-threshold",
+                group_threshold.is_some(),
+                sskr,
+                "--scheme sskr",
+            );
+
+            let codex32 = *scheme == Scheme::Codex32;
+            check(
+                "--threshold",
+                threshold.is_some(),
+                codex32,
+                "--scheme codex32",
+            );
+            check("--shares", shares.is_some(), codex32, "--scheme codex32");
+            check(
+                "--identifier",
+                identifier.is_some(),
+                codex32,
+                "--scheme codex32",
+            );
+
+            check(
+                "--parts",
+                parts.is_some(),
+                *scheme == Scheme::Xor,
+                "--scheme xor",
+            );
+        }
+
+        unused
+    }
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let opt = Splitmonic::from_args();
+    let format = opt.output_format();
+    let unused_split_options = opt.unused_split_options();
+
+    let result = match opt {
+        Splitmonic::Split {
+            interactive: true, ..
+        } => return setup_split_tui(),
+
+        Splitmonic::Split { .. } if !unused_split_options.is_empty() => {
+            let unused = unused_split_options
+                .iter()
+                .map(|(option, used_with)| format!("{} is only used with {}", option, used_with))
+                .collect::<Vec<String>>();
+
+            Err(Failure::new(
+                "Error splitting mnemonic",
+                eyre::eyre!("{}", unused.join(", ")),
+            ))
+        }
+
+        Splitmonic::Split {
+            interactive: false,
+            scheme: Scheme::Sskr,
+            mnemonic,
+            mnemonic_stdin,
+            mnemonic_fd,
+            groups,
+            group_threshold,
+            ..
+        } => get_mnemonic_code_from_split_cli(mnemonic, mnemonic_stdin, mnemonic_fd)
+            .and_then(|mnemonic| get_sskr_shares(mnemonic, group_threshold, groups))
+            .map(|(shares, fingerprint)| print_sskr_shares(&shares, &fingerprint, format))
+            .map_err(|error| Failure::new("Error splitting mnemonic into SSKR shares", error)),
+
+        Splitmonic::Split {
+            interactive: false,
+            scheme: Scheme::Codex32,
+            mnemonic,
+            mnemonic_stdin,
+            mnemonic_fd,
+            threshold,
+            shares,
+            identifier,
+            ..
+        } => get_mnemonic_code_from_split_cli(mnemonic, mnemonic_stdin, mnemonic_fd)
+            .and_then(|mnemonic| {
+                get_codex32_shares(mnemonic, threshold, shares, identifier.as_deref())
+            })
+            .map(|(shares, fingerprint)| print_codex32_shares(&shares, &fingerprint, format))
+            .map_err(|error| Failure::new("Error splitting mnemonic into codex32 shares", error)),
+
+        Splitmonic::Split {
+            interactive: false,
+            scheme: Scheme::Xor,
+            mnemonic,
+            mnemonic_stdin,
+            mnemonic_fd,
+            parts,
+            ..
+        } => get_mnemonic_code_from_split_cli(mnemonic, mnemonic_stdin, mnemonic_fd)
+            .and_then(|mnemonic| get_seed_xor_parts(mnemonic, parts))
+            .map(|(parts, fingerprint)| print_seed_xor_parts(&parts, &fingerprint, format))
+            .map_err(|error| Failure::new("Error splitting mnemonic into Seed XOR parts", error)),
+
+        Splitmonic::Split {
+            interactive: false,
+            mnemonic,
+            mnemonic_stdin,
+            mnemonic_fd,
+            no_verify,
+            output_dir,
+            file_name,
+            print_cards,
+            seed_qr,
+            encoding,
+            format,
+            ..
+        } => get_mnemonic_code_from_split_cli(mnemonic, mnemonic_stdin, mnemonic_fd)
+            .and_then(|mnemonic| get_split_phrases(mnemonic, !no_verify, encoding))
+            .map_err(|error| Failure::new("Error splitting mnemonic into split phrases", error))
+            .and_then(|(split_phrases, fingerprint)| {
+                let paths = match output_dir {
+                    Some(output_dirs) => Some(
+                        output::write_split_phrases(
+                            &split_phrases,
+                            &output_dirs,
+                            &file_name,
+                            &fingerprint,
+                        )
+                        .map_err(|error| Failure::new("Error writing split phrases", error))?,
+                    ),
+                    None => None,
+                };
+
+                let cards = match print_cards {
+                    Some(dir) => Some(
+                        cards::write_cards(&split_phrases, &fingerprint, &dir)
+                            .map_err(|error| Failure::new("Error writing cards", error))?,
+                    ),
+                    None => None,
+                };
+
+                let seed_qrs = match seed_qr {
+                    Some(seed_qr_format) => Some(
+                        split_phrases
+                            .iter()
+                            .map(|split_phrase| {
+                                let body = splitmonic::qr::split_phrase_body(split_phrase)?;
+                                qr::SeedQr::new(&body, seed_qr_format)
+                            })
+                            .collect::<Result<Vec<qr::SeedQr>>>()
+                            .map_err(|error| Failure::new("Error creating SeedQRs", error))?,
+                    ),
+                    None => None,
+                };
+
+                print_split_phrases(&split_phrases, &fingerprint, paths, cards, seed_qrs, format);
+                Ok(())
+            }),
+
+        splitmonic @ Splitmonic::Generate { .. } => generate_from_cli(splitmonic)
+            .map_err(|error| Failure::new("Error generating mnemonic", error)),
+
+        Splitmonic::Combine {
+            interactive: true, ..
+        } => return setup_combine_tui(),
+
+        splitmonic @ Splitmonic::Combine {
+            interactive: false,
+            seed_qr,
+            word_numbers,
+            ..
+        } => get_mnemonic_code_from_combine_cli(splitmonic)
+            .and_then(|mnemonic_code| {
+                let seed_qr = match seed_qr {
+                    Some(seed_qr_format) => Some(qr::SeedQr::new(&mnemonic_code, seed_qr_format)?),
+                    None => None,
+                };
+
+                print_recovered_mnemonic(&mnemonic_code, seed_qr, word_numbers, format);
+                Ok(())
+            })
+            .map_err(|error| Failure::new("Error combining split phrases", error)),
+
+        Splitmonic::RecoverWords {
+            mnemonic,
+            mnemonic_stdin,
+            mnemonic_fd,
+            fingerprint,
+            address,
+        } => {
+            let known = match (fingerprint, address) {
+                (Some(fingerprint), _) => Some(Known::Fingerprint(fingerprint)),
+                (_, Some(address)) => Some(Known::Address(address)),
+                _ => None,
+            };
+
+            get_secret_from_cli(mnemonic, mnemonic_stdin, mnemonic_fd, "mnemonic")
+                .and_then(|mnemonic| {
+                    let words = splitmonic::missing_words::parse_partial_mnemonic_code(&mnemonic);
+                    Ok(splitmonic::missing_words::find_missing_words(
+                        &words,
+                        known.as_ref(),
+                    )?)
+                })
+                .and_then(|mnemonics| print_recovered_mnemonics(&mnemonics, known.is_some()))
+                .map_err(|error| Failure::new("Error recovering missing words", error))
+        }
+
+        splitmonic @ Splitmonic::SplitSecret { .. } => {
+            get_secret_phrases_from_split_secret_cli(splitmonic)
+                .map(|secret_phrases| print_phrases("Secret Phrase", &secret_phrases))
+                .map_err(|error| Failure::new("Error splitting secret into secret phrases", error))
+        }
+
+        Splitmonic::CombineSecret {
+            secret_phrases,
+            secret_phrases_stdin,
+            secret_phrases_fd,
+            secret_phrase_files,
+            hex,
+        } => get_phrases_from_cli(
+            secret_phrases,
+            secret_phrases_stdin,
+            secret_phrases_fd,
+            "secret-phrases",
+        )
+        .and_then(|secret_phrases| {
+            get_secret_from_combine_secret_cli(secret_phrases, secret_phrase_files)
+        })
+        .map(|secret| {
+            println!("\nSuccessfully recovered your secret:\n");
+            match String::from_utf8(secret) {
+                Ok(text) if !hex => println!("{}", text),
+                Ok(text) => println!("{}", hex::encode(text)),
+                Err(error) => println!("{}", hex::encode(error.into_bytes())),
+            }
+        })
+        .map_err(|error| Failure::new("Error combining secret phrases", error)),
+
+        Splitmonic::ExportVault {
+            secret_phrases,
+            secret_phrases_stdin,
+            secret_phrases_fd,
+            split_phrases,
+            split_phrases_stdin,
+            split_phrases_fd,
+            hex,
+        } => get_phrases_from_cli(
+            secret_phrases,
+            secret_phrases_stdin,
+            secret_phrases_fd,
+            "secret-phrases",
+        )
+        .and_then(|secret_phrases| {
+            let split_phrases = get_phrases_from_cli(
+                split_phrases,
+                split_phrases_stdin,
+                split_phrases_fd,
+                "split-phrases",
+            )?;
+            get_vault_shares_from_export_vault_cli(secret_phrases, split_phrases, hex)
+        })
+        .map(|vault_shares| print_vault_shares(&vault_shares))
+        .map_err(|error| Failure::new("Error exporting Vault shares", error)),
+
+        Splitmonic::ImportVault {
+            vault_shares,
+            vault_shares_stdin,
+            vault_shares_fd,
+            threshold,
+            hex,
+        } => get_phrases_from_cli(
+            vault_shares,
+            vault_shares_stdin,
+            vault_shares_fd,
+            "vault-shares",
+        )
+        .and_then(|vault_shares| {
+            Ok(vault::vault_shares_to_secret_phrases(
+                &vault_shares.unwrap_or_default(),
+                threshold,
+                vault_encoding(hex),
+            )?)
+        })
+        .map(|secret_phrases| print_phrases("Secret Phrase", &secret_phrases))
+        .map_err(|error| Failure::new("Error importing Vault shares", error)),
+
+        Splitmonic::ExportSsss {
+            secret_phrases,
+            secret_phrases_stdin,
+            secret_phrases_fd,
+            split_phrases,
+            split_phrases_stdin,
+            split_phrases_fd,
+            threshold,
+            shares,
+        } => get_phrases_from_cli(
+            secret_phrases,
+            secret_phrases_stdin,
+            secret_phrases_fd,
+            "secret-phrases",
+        )
+        .and_then(|secret_phrases| {
+            let split_phrases = get_phrases_from_cli(
+                split_phrases,
+                split_phrases_stdin,
+                split_phrases_fd,
+                "split-phrases",
+            )?;
+            get_ssss_shares_from_export_ssss_cli(secret_phrases, split_phrases, threshold, shares)
+        })
+        .map(|ssss_shares| print_ssss_shares(&ssss_shares, threshold))
+        .map_err(|error| Failure::new("Error exporting ssss shares", error)),
+
+        Splitmonic::ImportSsss {
+            ssss_shares,
+            ssss_shares_stdin,
+            ssss_shares_fd,
+            threshold,
+            shares,
+        } => get_phrases_from_cli(
+            ssss_shares,
+            ssss_shares_stdin,
+            ssss_shares_fd,
+            "ssss-shares",
+        )
+        .and_then(|ssss_shares| {
+            Ok(ssss::ssss_shares_to_secret_phrases(
+                &ssss_shares.unwrap_or_default(),
+                threshold,
+                shares,
+            )?)
+        })
+        .map(|secret_phrases| print_phrases("Secret Phrase", &secret_phrases))
+        .map_err(|error| Failure::new("Error importing ssss shares", error)),
+
+        splitmonic @ Splitmonic::Inspect { .. } => {
+            get_split_phrase_info_from_inspect_cli(splitmonic)
+                .map(|info| print_split_phrase_info(&info, format))
+                .map_err(|error| Failure::new("Error inspecting split phrase", error))
+        }
+    };
+
+    if let Err(failure) = result {
+        std::process::exit(failure.report(format));
+    }
+
+    Ok(())
+}
+
+fn print_recovered_mnemonics(mnemonics: &[String], narrowed: bool) -> Result<()> {
+    match mnemonics {
+        [] if narrowed => return Err(eyre::eyre!("no mnemonic with a valid checksum matched, check the known words, their order and the fingerprint or address")),
+        [] => return Err(eyre::eyre!("no mnemonic with a valid checksum was found, check the known words and their order")),
+        [mnemonic] => {
+            println!("\nFound your mnemonic:\n");
+            for (index, word) in mnemonic.split(' ').enumerate() {
+                println!("{}: {}", index + 1, word)
+            }
+
+            if let Ok(fingerprint) = splitmonic::fingerprint::get_fingerprint(mnemonic) {
+                println!();
+                print_fingerprint(&fingerprint);
+            }
+        }
+        mnemonics => {
+            println!(
+                "\nFound {} mnemonics with a valid checksum, use --fingerprint or --address to find the correct one:\n",
+                mnemonics.len()
+            );
+            for mnemonic in mnemonics {
+                println!("{}", mnemonic)
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_split_phrases(
+    split_phrases: &[String],
+    fingerprint: &str,
+    files: Option<Vec<PathBuf>>,
+    cards: Option<Vec<PathBuf>>,
+    seed_qrs: Option<Vec<qr::SeedQr>>,
+    format: OutputFormat,
+) {
+    // the split phrases are only shown when they weren't written anywhere
+    let show_split_phrases = files.is_none() && cards.is_none();
+
+    match format {
+        OutputFormat::Json => {
+            let mut json = serde_json::json!({ "fingerprint": fingerprint });
+
+            if let Some(files) = files {
+                json["files"] = serde_json::json!(files);
+            }
+
+            if let Some(cards) = cards {
+                json["cards"] = serde_json::json!(cards);
+            }
+
+            if show_split_phrases {
+                json["split_phrases"] = split_phrases
+                    .iter()
+                    .enumerate()
+                    .map(|(index, split_phrase)| {
+                        serde_json::json!({
+                            "share": index + 1,
+                            "phrase": split_phrase,
+                            "words": split_phrase.split(' ').collect::<Vec<&str>>(),
+                        })
+                    })
+                    .collect();
+                json["threshold"] = splitmonic::THRESHOLD.into();
+                json["number_of_shares"] = splitmonic::NUMBER_OF_SHARES.into();
+            }
+
+            if let Some(seed_qrs) = &seed_qrs {
+                json["seed_qrs"] = seed_qrs.iter().map(qr::SeedQr::to_json).collect();
+            }
+
+            println!("{}", json)
+        }
+
+        OutputFormat::Text => {
+            for path in files.iter().chain(cards.iter()).flatten() {
+                println!("Wrote {}", path.display())
+            }
+
+            if show_split_phrases {
+                print_phrases("Split Phrase", split_phrases)
+            }
+
+            for (index, seed_qr) in seed_qrs.iter().flatten().enumerate() {
+                let title = format!(
+                    "{} of split phrase {}, words 5 to {}",
+                    seed_qr.name(),
+                    index + 1,
+                    split_phrases[index].split(' ').count()
+                );
+                print_seed_qr(&title, seed_qr);
+            }
+
+            print_split_fingerprint(fingerprint);
+        }
+    }
+}
+
+fn print_sskr_shares(shares: &[Vec<Share>], fingerprint: &str, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => {
+            let sskr_shares = shares
+                .iter()
+                .flatten()
+                .map(|share| {
+                    serde_json::json!({
+                        "group": share.group_index + 1,
+                        "group_threshold": share.group_threshold,
+                        "group_count": share.group_count,
+                        "member": share.member_index + 1,
+                        "member_threshold": share.member_threshold,
+                        "bytewords": share.to_bytewords(),
+                        "ur": share.to_ur(),
+                    })
+                })
+                .collect::<Vec<serde_json::Value>>();
+
+            println!(
+                "{}",
+                serde_json::json!({ "fingerprint": fingerprint, "sskr_shares": sskr_shares })
+            )
+        }
+
+        OutputFormat::Text => {
+            for share in shares.iter().flatten() {
+                let title = format!(
+                    " SSKR Share {}.{} ",
+                    share.group_index + 1,
+                    share.member_index + 1
+                );
+
+                println!("\n######################################################");
+                println!("{:#^54}", title);
+                println!("######################################################");
+                println!("{}\n", share.describe());
+                println!("{}\n", share.to_bytewords());
+                println!("{}\n", share.to_ur());
+            }
+
+            print_split_fingerprint(fingerprint);
+        }
+    }
+}
+
+fn print_codex32_shares(shares: &[codex32::Share], fingerprint: &str, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => {
+            let codex32_shares = shares
+                .iter()
+                .map(|share| {
+                    serde_json::json!({
+                        "index": share.index.to_string(),
+                        "threshold": share.threshold,
+                        "identifier": share.identifier,
+                        "share": share.to_string(),
+                    })
+                })
+                .collect::<Vec<serde_json::Value>>();
+
+            println!(
+                "{}",
+                serde_json::json!({ "fingerprint": fingerprint, "codex32_shares": codex32_shares })
+            )
+        }
+
+        OutputFormat::Text => {
+            for share in shares {
+                let title = format!(" codex32 Share {} ", share.index.to_ascii_uppercase());
+
+                println!("\n######################################################");
+                println!("{:#^54}", title);
+                println!("######################################################");
+                println!(
+                    "identifier {}, {} of {} shares needed\n",
+                    share.identifier,
+                    share.threshold,
+                    shares.len()
+                );
+                println!("{}\n", share.to_string().to_uppercase());
+            }
+
+            print_split_fingerprint(fingerprint);
+        }
+    }
+}
+
+fn print_seed_xor_parts(parts: &[String], fingerprint: &str, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => {
+            let xor_parts = parts
+                .iter()
+                .enumerate()
+                .map(|(index, part)| {
+                    serde_json::json!({
+                        "part": index + 1,
+                        "mnemonic": part,
+                        "words": part.split(' ').collect::<Vec<&str>>(),
+                    })
+                })
+                .collect::<Vec<serde_json::Value>>();
+
+            println!(
+                "{}",
+                serde_json::json!({ "fingerprint": fingerprint, "xor_parts": xor_parts })
+            )
+        }
+
+        OutputFormat::Text => {
+            print_phrases("Seed XOR Part", parts);
+
+            println!("All {} parts are needed to recover your mnemonic, each part is also a valid mnemonic on its own", parts.len());
+            print_split_fingerprint(fingerprint);
+        }
+    }
+}
+
+fn print_recovered_mnemonic(
+    mnemonic_code: &str,
+    seed_qr: Option<qr::SeedQr>,
+    word_numbers: Option<WordNumbers>,
+    format: OutputFormat,
+) {
+    let fingerprint = splitmonic::fingerprint::get_fingerprint(mnemonic_code).ok();
+    let numbers = word_numbers.map(|word_numbers| {
+        mnemonic_code
+            .split(' ')
+            .map(|word| word_numbers::to_word_number(word, word_numbers).unwrap_or_default())
+            .collect::<Vec<String>>()
+    });
+
+    match format {
+        OutputFormat::Json => {
+            let mut json = serde_json::json!({
+                "mnemonic": mnemonic_code,
+                "words": mnemonic_code.split(' ').collect::<Vec<&str>>(),
+                "fingerprint": fingerprint,
+            });
+
+            if let Some(numbers) = &numbers {
+                json["word_numbers"] = serde_json::json!(numbers);
+            }
+
+            if let Some(seed_qr) = &seed_qr {
+                json["seed_qr"] = seed_qr.to_json();
+            }
+
+            println!("{}", json)
+        }
+
+        OutputFormat::Text => {
+            println!("\nSuccessfully recovered your mnemonic code:\n");
+            for (index, word) in mnemonic_code.split(' ').enumerate() {
+                match &numbers {
+                    Some(numbers) => println!("{}: {:<8} {}", index + 1, word, numbers[index]),
+                    None => println!("{}: {}", index + 1, word),
+                }
+            }
+
+            if let Some(fingerprint) = &fingerprint {
+                println!();
+                print_fingerprint(fingerprint);
+            }
+
+            if let Some(seed_qr) = &seed_qr {
+                print_seed_qr(&format!("{} of your mnemonic", seed_qr.name()), seed_qr);
+            }
+        }
+    }
+}
+
+fn print_seed_qr(title: &str, seed_qr: &qr::SeedQr) {
+    let size = seed_qr.qr.width();
+    println!("\n{} ({}x{}):\n", title, size, size);
+
+    for line in qr::to_grid(&seed_qr.qr) {
+        println!("{}", line)
+    }
+
+    println!("\n{}", seed_qr.payload_text());
+}
+
+fn print_vault_shares(vault_shares: &[String]) {
+    println!();
+    for (index, vault_share) in vault_shares.iter().enumerate() {
+        println!("Vault Share {}: {}", index + 1, vault_share);
+    }
+    println!();
+}
+
+// one share per line, the way ssss-combine reads them
+fn print_ssss_shares(ssss_shares: &[String], threshold: u8) {
+    println!();
+    for ssss_share in ssss_shares {
+        println!("{}", ssss_share);
+    }
+    println!();
+    println!(
+        "Any {} of these shares recover your secret with: ssss-combine -x -t {}",
+        threshold, threshold
+    );
+}
+
+fn print_fingerprint(fingerprint: &str) {
+    println!("Mnemonic fingerprint: {}", fingerprint);
+}
+
+/// Prints the fingerprint of the mnemonic that was split, to compare with the one `combine` shows
+fn print_split_fingerprint(fingerprint: &str) {
+    print_fingerprint(fingerprint);
+    println!("Keep this fingerprint, `combine` will show the same fingerprint when your mnemonic is recovered correctly");
+}
+
+fn print_phrases(name: &str, phrases: &[String]) {
+    for (index, phrase) in phrases.iter().enumerate() {
+        let title = format!(" {} {} of {} ", name, index + 1, phrases.len());
+
+        println!("\n######################################################");
+        println!("{:#^54}", title);
+        println!("######################################################");
+
+        phrase
+            .split(' ')
+            .enumerate()
+            .for_each(|(index, word)| println!("{}: {}", index + 1, word));
+
+        println!();
+    }
+}
+
+fn generate_from_cli(splitmonic: Splitmonic) -> Result<()> {
+    match splitmonic {
+        Splitmonic::Generate {
+            interactive,
+            words,
+            dice,
+            coin_flips,
+            from_rolls,
+            source,
+            show_mnemonic,
+            no_verify,
+        } => {
+            let extra_entropy = match (dice, coin_flips) {
+                (Some(rolls), _) => Some(ExtraEntropy::DiceRolls(rolls)),
+                (_, Some(flips)) => Some(ExtraEntropy::CoinFlips(flips)),
+                _ => None,
+            };
+
+            let mnemonic = match from_rolls {
+                Some(rolls) => splitmonic::generate::mnemonic_code_from_rolls(
+                    words, source, &rolls,
+                )
+                .map_err(|error| {
+                    eyre::eyre!(
+                        "{} ({} words need {} kept rolls, about {} rolls in total)",
+                        error,
+                        words,
+                        source.rolls_needed(words).unwrap_or_default(),
+                        source.expected_rolls(words).unwrap_or_default()
+                    )
+                })?,
+                None => {
+                    splitmonic::generate::generate_mnemonic_code(words, extra_entropy.as_ref())?
+                }
+            };
+
+            if interactive {
+                return setup_generate_tui(mnemonic);
+            }
+
+            let fingerprint = splitmonic::fingerprint::get_fingerprint(&mnemonic)?;
+            let split_phrases = splitmonic::get_encoded_split_shares(
+                mnemonic.clone(),
+                &splitmonic::encoding::Words,
+                !no_verify,
+            )?;
+
+            if show_mnemonic {
+                println!("\nGenerated mnemonic:\n");
+                for (index, word) in mnemonic.split(' ').enumerate() {
+                    println!("{}: {}", index + 1, word)
+                }
+            }
+
+            print_phrases("Split Phrase", &split_phrases);
+
+            print_split_fingerprint(&fingerprint);
+
+            Ok(())
+        }
+
+        // any other combinations are impossible
+        _ => Err(eyre::eyre!("unreachable")),
+    }
+}
+
+fn get_secret_phrases_from_split_secret_cli(splitmonic: Splitmonic) -> Result<Vec<String>> {
+    match splitmonic {
+        Splitmonic::SplitSecret {
+            secret,
+            hex,
+            secret_stdin,
+            secret_fd,
+            hex_stdin,
+            hex_fd,
+            file,
+            threshold,
+            shares,
+        } => {
+            let secret = if let Some(file) = file {
+                std::fs::read(&file).wrap_err_with(|| format!("Unable to read file: {}", file))?
+            } else if secret.is_some() || secret_stdin || secret_fd.is_some() {
+                let secret = get_secret_from_cli(secret, secret_stdin, secret_fd, "secret")?;
+                // a secret read from stdin or a file descriptor usually ends with a newline
+                secret
+                    .strip_suffix('\n')
+                    .map(|secret| secret.strip_suffix('\r').unwrap_or(secret))
+                    .unwrap_or(&secret)
+                    .as_bytes()
+                    .to_vec()
+            } else {
+                let hex = get_secret_from_cli(hex, hex_stdin, hex_fd, "hex")?;
+                hex::decode(hex.trim()).wrap_err("Invalid hex secret")?
+            };
+
+            Ok(splitmonic::secret::split_secret(
+                &secret, threshold, shares,
+            )?)
+        }
+
+        // any other combinations are impossible
+        _ => Err(eyre::eyre!("unreachable")),
+    }
+}
+
+/// Returns the secret given as an argument (with a warning), or read from stdin or a file
+/// descriptor. `secret` is the name of the argument, used to suggest its `-stdin` and `-fd` options
+fn get_secret_from_cli(
+    argument: Option<String>,
+    stdin: bool,
+    fd: Option<i32>,
+    secret: &str,
+) -> Result<String> {
+    match (argument, stdin, fd) {
+        (Some(argument), _, _) => {
+            input::warn_secret_in_arguments(secret);
+            Ok(argument)
+        }
+        (_, true, _) => input::read_stdin(),
+        (_, _, Some(fd)) => input::read_fd(fd),
+        // any other combinations are impossible
+        _ => Err(eyre::eyre!("unreachable")),
+    }
+}
+
+/// Returns the phrases (or shares) given as arguments (with a warning), or read from stdin or a
+/// file descriptor one per line, `None` when none of them were given
+fn get_phrases_from_cli(
+    arguments: Option<Vec<String>>,
+    stdin: bool,
+    fd: Option<i32>,
+    secret: &str,
+) -> Result<Option<Vec<String>>> {
+    match (arguments, stdin, fd) {
+        (Some(arguments), _, _) => {
+            input::warn_secret_in_arguments(secret);
+            Ok(Some(
+                arguments
+                    .iter()
+                    .map(|argument| argument.trim().to_string())
+                    .collect(),
+            ))
+        }
+        (_, true, _) => Ok(Some(input::split_phrases_from_text(&input::read_stdin()?))),
+        (_, _, Some(fd)) => Ok(Some(input::split_phrases_from_text(&input::read_fd(fd)?))),
+        _ => Ok(None),
+    }
+}
+
+fn get_secret_from_combine_secret_cli(
+    secret_phrases: Option<Vec<String>>,
+    secret_phrase_files: Option<Vec<String>>,
+) -> Result<Vec<u8>> {
+    let mut phrases = secret_phrases
+        .unwrap_or_default()
+        .iter()
+        .map(|phrase| phrase.trim().to_string())
+        .collect::<Vec<String>>();
+
+    for file in secret_phrase_files.unwrap_or_default() {
+        phrases.push(clean_and_combine_phrase(&read_and_get_phrases_from_file(
+            &file,
+        )?))
+    }
+
+    Ok(splitmonic::secret::recover_secret(phrases)?)
+}
+
+fn get_vault_shares_from_export_vault_cli(
+    secret_phrases: Option<Vec<String>>,
+    split_phrases: Option<Vec<String>>,
+    hex: bool,
+) -> Result<Vec<String>> {
+    let encoding = vault_encoding(hex);
+
+    match (secret_phrases, split_phrases) {
+        (Some(secret_phrases), _) => Ok(vault::secret_phrases_to_vault_shares(
+            &secret_phrases,
+            encoding,
+        )?),
+        (_, Some(split_phrases)) => Ok(vault::split_phrases_to_vault_shares(
+            &split_phrases,
+            encoding,
+        )?),
+        // any other combinations are impossible
+        _ => Err(eyre::eyre!("unreachable")),
+    }
+}
+
+fn get_ssss_shares_from_export_ssss_cli(
+    secret_phrases: Option<Vec<String>>,
+    split_phrases: Option<Vec<String>>,
+    threshold: u8,
+    shares: u8,
+) -> Result<Vec<String>> {
+    match (secret_phrases, split_phrases) {
+        (Some(secret_phrases), _) => Ok(ssss::secret_phrases_to_ssss_shares(
+            &secret_phrases,
+            threshold,
+            shares,
+        )?),
+        (_, Some(split_phrases)) => Ok(ssss::split_phrases_to_ssss_shares(
+            &split_phrases,
+            threshold,
+            shares,
+        )?),
+        // any other combinations are impossible
+        _ => Err(eyre::eyre!("unreachable")),
+    }
+}
+
+fn vault_encoding(hex: bool) -> VaultEncoding {
+    if hex {
+        VaultEncoding::Hex
+    } else {
+        VaultEncoding::Base64
+    }
+}
+
+fn get_split_phrase_info_from_inspect_cli(splitmonic: Splitmonic) -> Result<SplitPhraseInfo> {
+    let split_phrase = match splitmonic {
+        // the header of versioned share files has its own threshold and format version
+        Splitmonic::Inspect {
+            file: Some(file), ..
+        } => return Ok(splitmonic::inspect::inspect_share_file(&file)?),
+
+        Splitmonic::Inspect {
+            split_phrase: Some(split_phrase),
+            ..
+        } => clean_and_combine_phrase(&split_phrase),
+
+        // any other combinations are impossible
+        _ => return Err(eyre::eyre!("unreachable")),
+    };
+
+    // split phrases stamped as word numbers are read as words
+    let split_phrase = if word_numbers::are_word_numbers(&split_phrase) {
+        word_numbers::word_numbers_to_phrase(&split_phrase)?
+    } else {
+        split_phrase
+    };
+
+    Ok(splitmonic::inspect::inspect_split_phrase(&split_phrase)?)
+}
+
+fn print_split_phrase_info(info: &SplitPhraseInfo, format: OutputFormat) {
+    if format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "set_id": info.set_id,
+                "share_index": info.share_index,
+                "number_of_shares": info.number_of_shares,
+                "threshold": info.threshold,
+                "word_count": info.word_count,
+                "valid_checksum": info.valid_checksum,
+                "valid": info.is_valid(),
+                "format_version": info.format_version,
+            })
+        );
+        return;
+    }
+
+    let checksum = if info.valid_checksum {
+        "valid"
+    } else {
+        "invalid"
+    };
+
+    println!("Set id: {}", info.set_id);
+    println!(
+        "Split phrase: {} of {}",
+        info.share_index, info.number_of_shares
+    );
+    println!(
+        "Threshold: {} of {} split phrases needed to recover",
+        info.threshold, info.number_of_shares
+    );
+    println!(
+        "Word count: {} (expected one of {:?})",
+        info.word_count,
+        splitmonic::validation::SPLIT_PHRASE_LENGTHS
+    );
+    println!("Checksum: {}", checksum);
+    println!("Format version: {}", info.format_version);
+}
+
+fn get_mnemonic_code_from_split_cli(
+    mnemonic: Option<String>,
+    mnemonic_stdin: bool,
+    mnemonic_fd: Option<i32>,
+) -> Result<String> {
+    let mnemonic = match (mnemonic, mnemonic_stdin, mnemonic_fd) {
+        (Some(mnemonic), _, _) => {
+            input::warn_secret_in_arguments("mnemonic");
+            mnemonic
+        }
+        (_, true, _) => input::read_stdin()?,
+        (_, _, Some(fd)) => input::read_fd(fd)?,
+        _ => input::prompt_mnemonic_code()?,
+    };
+
+    Ok(clean_and_combine_phrase(
+        &mnemonic
+            .split_whitespace()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>(),
+    ))
+}
+
+fn get_split_phrases(
+    mnemonic: String,
+    verify: bool,
+    encoding: Encoding,
+) -> Result<(Vec<String>, String)> {
+    splitmonic::validation::validate_mnemonic_code(&mnemonic)?;
+    let fingerprint = splitmonic::fingerprint::get_fingerprint(&mnemonic)?;
+
+    let split_phrases = splitmonic::get_encoded_split_shares(mnemonic, encoding.encoder(), verify)?;
+
+    Ok((split_phrases, fingerprint))
+}
+
+fn get_sskr_shares(
+    mnemonic: String,
+    group_threshold: Option<u8>,
+    groups: Option<Vec<GroupSpec>>,
+) -> Result<(Vec<Vec<Share>>, String)> {
+    splitmonic::validation::validate_mnemonic_code(&mnemonic)?;
+    let fingerprint = splitmonic::fingerprint::get_fingerprint(&mnemonic)?;
+
+    let groups = groups.unwrap_or_else(|| {
+        vec![GroupSpec {
+            threshold: splitmonic::THRESHOLD,
+            count: splitmonic::NUMBER_OF_SHARES,
+        }]
+    });
+
+    let shares =
+        splitmonic::sskr::split_mnemonic_code(&mnemonic, group_threshold.unwrap_or(1), &groups)?;
+
+    Ok((shares, fingerprint))
+}
+
+fn get_codex32_shares(
+    mnemonic: String,
+    threshold: Option<u8>,
+    shares: Option<usize>,
+    identifier: Option<&str>,
+) -> Result<(Vec<codex32::Share>, String)> {
+    splitmonic::validation::validate_mnemonic_code(&mnemonic)?;
+    let fingerprint = splitmonic::fingerprint::get_fingerprint(&mnemonic)?;
+
+    let shares = splitmonic::codex32::split_mnemonic_code(
+        &mnemonic,
+        threshold.unwrap_or(splitmonic::THRESHOLD),
+        shares.unwrap_or(splitmonic::NUMBER_OF_SHARES as usize),
+        identifier,
+    )?;
 
-        #[structopt(
-            short = "3",
-            visible_alias = "sp3",
-            long,
-            help = "third split phrase",
-            conflicts_with = "interactive",
-            use_delimiter = true,
-            min_values = 28,
-            max_values = 28
-        )]
-        split_phrases_3: Option<Vec<String>>,
-    },
+    Ok((shares, fingerprint))
 }
 
-fn main() -> Result<()> {
-    color_eyre::install()?;
+fn get_seed_xor_parts(mnemonic: String, parts: Option<usize>) -> Result<(Vec<String>, String)> {
+    splitmonic::validation::validate_mnemonic_code(&mnemonic)?;
+    let fingerprint = splitmonic::fingerprint::get_fingerprint(&mnemonic)?;
 
-    let opt = Splitmonic::from_args();
+    let parts = splitmonic::seed_xor::split_mnemonic_code(
+        &mnemonic,
+        parts.unwrap_or(splitmonic::THRESHOLD as usize),
+    )?;
 
-    match opt {
-        Splitmonic::Split {
-            interactive: true, ..
-        } => setup_split_tui(),
+    Ok((parts, fingerprint))
+}
 
-        Splitmonic::Split {
-            interactive: false,
-            mnemonic: Some(mnemonic),
+fn get_mnemonic_code_from_combine_cli(splitmonic: Splitmonic) -> Result<String> {
+    let format = splitmonic.output_format();
+    let (encoding, expect_fingerprint) = match &splitmonic {
+        Splitmonic::Combine {
+            encoding,
+            expect_fingerprint,
             ..
-        } => {
-            match get_split_phrases(mnemonic) {
-                Ok(split_phrases) => {
-                    for (index, phrase) in split_phrases.iter().enumerate() {
-                        println!("\n######################################################");
-                        println!(
-                            "############## Split Phrase {} of 5 ###################",
-                            index + 1
-                        );
-                        println!("######################################################");
-
-                        phrase
-                            .split(' ')
-                            .enumerate()
-                            .for_each(|(index, word)| println!("{}: {}", index + 1, word));
-
-                        println!();
-                    }
-                }
-                Err(error) => eprintln!("Error splitting mnemonic into split phrases: {}", error),
-            }
+        } => (*encoding, expect_fingerprint.clone()),
+        _ => (Encoding::Words, None),
+    };
 
-            Ok(())
-        }
+    let split_phrases = get_split_phrases_from_combine_cli(splitmonic)?;
 
-        Splitmonic::Combine {
-            interactive: true, ..
-        } => Ok(()),
+    if encoding != Encoding::Words {
+        return Ok(splitmonic::recover_mnemonic_code_from(
+            split_phrases,
+            encoding.encoder(),
+        )?);
+    }
 
-        splitmonic @ Splitmonic::Combine {
-            interactive: false, ..
-        } => {
-            let mnemonic_code = get_mnemonic_code_from_combine_cli(splitmonic);
-
-            match mnemonic_code {
-                Ok(mnemonic_code) => {
-                    println!("\nSuccessfully recovered your mnemonic code:\n");
-                    for (index, word) in mnemonic_code.split(' ').enumerate() {
-                        println!("{}: {}", index + 1, word)
-                    }
-                }
-                Err(error) => {
-                    eprintln!("Error combining split phrases: {}", error)
-                }
+    // split phrases and Seed XOR parts stamped as word numbers are read as words
+    let split_phrases = split_phrases
+        .into_iter()
+        .map(|phrase| {
+            if word_numbers::are_word_numbers(&phrase) {
+                word_numbers::word_numbers_to_phrase(&phrase)
+            } else {
+                Ok(phrase)
             }
+        })
+        .collect::<Result<Vec<String>, _>>()?;
 
-            Ok(())
+    // SSKR and codex32 shares are recognised by their first share and Seed XOR parts by all being
+    // mnemonics, anything else is read as split phrases
+    if let Some(first) = split_phrases.first() {
+        if Share::parse(first).is_ok() {
+            return Ok(splitmonic::sskr::recover_mnemonic_code(&split_phrases)?);
         }
 
-        // any other combinations are impossible
-        _ => Err(eyre::eyre!("unreachable")),
+        if first.to_lowercase().starts_with("ms1") {
+            return Ok(splitmonic::codex32::recover_mnemonic_code(&split_phrases)?);
+        }
     }
+
+    if splitmonic::seed_xor::are_parts(&split_phrases) {
+        let mnemonic_code = splitmonic::seed_xor::recover_mnemonic_code(&split_phrases)?;
+        check_seed_xor_fingerprint(
+            &mnemonic_code,
+            split_phrases.len(),
+            expect_fingerprint,
+            format,
+        )?;
+        return Ok(mnemonic_code);
+    }
+
+    splitmonic::validation::validate_split_phrases(split_phrases.clone())?;
+
+    Ok(splitmonic::recover_mnemonic_code(split_phrases)?)
 }
 
-fn get_split_phrases(mnemonic: String) -> Result<Vec<String>> {
-    splitmonic::validation::validate_mnemonic_code(&mnemonic)?;
-    Ok(splitmonic::get_split_phrases(mnemonic)?)
+/// Any number of Seed XOR parts combine into a valid mnemonic, so a missing part can't be detected
+/// from the parts alone. The recovered mnemonic's fingerprint has to match the one shown when it was
+/// split, either the `--expect-fingerprint` given or confirmed by the user. The user is only asked
+/// with text output on a terminal, JSON output is read by scripts that can't answer
+fn check_seed_xor_fingerprint(
+    mnemonic_code: &str,
+    parts: usize,
+    expect_fingerprint: Option<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let fingerprint = splitmonic::fingerprint::get_fingerprint(mnemonic_code)?;
+
+    eprintln!(
+        "Combined {} Seed XOR parts, every part is needed, without all of them the result is still a valid mnemonic but not yours",
+        parts
+    );
+    eprintln!("Recovered mnemonic fingerprint: {}", fingerprint);
+
+    match expect_fingerprint {
+        Some(expected) if expected.trim().eq_ignore_ascii_case(&fingerprint) => Ok(()),
+
+        Some(expected) => Err(eyre::eyre!(
+            "the recovered mnemonic's fingerprint {} doesn't match the expected fingerprint {}, check that you gave every Seed XOR part",
+            fingerprint,
+            expected.trim()
+        )),
+
+        None => {
+            let reason = match format {
+                OutputFormat::Json => Some("with JSON output"),
+                OutputFormat::Text if !input::has_terminal() => Some("without a terminal"),
+                OutputFormat::Text => None,
+            };
+
+            if let Some(reason) = reason {
+                return Err(input::UnaskableError {
+                    question: "Whether the recovered mnemonic's fingerprint is the one shown when it was split",
+                    reason,
+                    option: "--expect-fingerprint",
+                }
+                .into());
+            }
+
+            if input::confirm("Is this the fingerprint shown when your mnemonic was split?")? {
+                Ok(())
+            } else {
+                Err(eyre::eyre!("the recovered mnemonic's fingerprint wasn't confirmed, check that you gave every Seed XOR part"))
+            }
+        }
+    }
 }
 
-fn get_mnemonic_code_from_combine_cli(splitmonic: Splitmonic) -> Result<String> {
+fn get_split_phrases_from_combine_cli(splitmonic: Splitmonic) -> Result<Vec<String>> {
     match splitmonic {
         Splitmonic::Combine {
             all_split_phrases: Some(split_phrases),
             ..
         } => {
-            let split_phrases: Vec<String> = split_phrases
+            input::warn_secret_in_arguments("split-phrases");
+
+            Ok(split_phrases
                 .iter()
                 .map(|phrase| phrase.trim().to_string())
-                .collect();
+                .collect())
+        }
 
-            splitmonic::validation::validate_split_phrases(split_phrases.clone())?;
+        Splitmonic::Combine {
+            split_phrases_stdin: true,
+            ..
+        } => Ok(input::split_phrases_from_text(&input::read_stdin()?)),
 
-            Ok(splitmonic::recover_mnemonic_code(split_phrases)?)
-        }
+        Splitmonic::Combine {
+            split_phrases_fd: Some(fd),
+            ..
+        } => Ok(input::split_phrases_from_text(&input::read_fd(fd)?)),
 
         Splitmonic::Combine {
-            split_phrase_files: Some(ref file_paths),
+            split_phrase_files,
+            qr_images,
             split_phrases_1,
             split_phrases_2,
             split_phrases_3,
+            encoding,
             ..
-        } => {
-            let split_phrases = get_split_phrases_from_files(
-                file_paths,
-                vec![split_phrases_1, split_phrases_2, split_phrases_3],
-            );
-            splitmonic::validation::validate_split_phrases(split_phrases.clone())?;
+        } if split_phrase_files.is_some() || qr_images.is_some() => {
+            let phrases_direct = vec![split_phrases_1, split_phrases_2, split_phrases_3];
+            if phrases_direct.iter().any(Option::is_some) {
+                input::warn_secret_in_arguments("split-phrases");
+            }
 
-            Ok(splitmonic::recover_mnemonic_code(split_phrases)?)
+            get_split_phrases_from_files(
+                &split_phrase_files.unwrap_or_default(),
+                &qr_images.unwrap_or_default(),
+                phrases_direct,
+                encoding,
+            )
         }
 
         Splitmonic::Combine {
@@ -221,15 +2015,18 @@ fn get_mnemonic_code_from_combine_cli(splitmonic: Splitmonic) -> Result<String>
             split_phrases_3,
             ..
         } => {
-            let split_phrases = vec![split_phrases_1, split_phrases_2, split_phrases_3]
+            let split_phrases = [split_phrases_1, split_phrases_2, split_phrases_3]
                 .iter()
                 .filter_map(|phrase| phrase.as_ref())
                 .map(|phrase| clean_and_combine_phrase(phrase))
                 .collect::<Vec<String>>();
 
-            splitmonic::validation::validate_split_phrases(split_phrases.clone())?;
+            if split_phrases.is_empty() {
+                return input::prompt_split_phrases(splitmonic::THRESHOLD as usize);
+            }
 
-            Ok(splitmonic::recover_mnemonic_code(split_phrases)?)
+            input::warn_secret_in_arguments("split-phrases");
+            Ok(split_phrases)
         }
 
         // any other combinations are impossible
@@ -248,46 +2045,77 @@ fn clean_and_combine_phrase(phrase: &[String]) -> String {
 
 fn get_split_phrases_from_files(
     file_paths: &[String],
+    qr_images: &[PathBuf],
     phrases_direct: Vec<Option<Vec<String>>>,
-) -> Vec<String> {
-    let phrases_from_files = file_paths
+    encoding: Encoding,
+) -> Result<Vec<String>> {
+    let mut split_phrases = file_paths
         .iter()
-        .map(|file| read_and_get_phrases_from_file(file))
-        .filter_map(Result::ok)
-        .map(|phrase| clean_and_combine_phrase(&phrase));
+        .map(|file| match encoding {
+            Encoding::Words => read_and_get_phrases_from_file(file),
+            _ => Ok(splitmonic::share_file::read_encoded_share_file(file)?),
+        })
+        .map(|phrase| phrase.map(|phrase| clean_and_combine_phrase(&phrase)))
+        .collect::<Result<Vec<String>>>()?;
 
-    let phrases_direct = phrases_direct
-        .iter()
-        .filter_map(|phrase| phrase.as_ref())
-        .map(|phrase| clean_and_combine_phrase(phrase));
+    for qr_image in qr_images {
+        split_phrases.extend(qr::read_qr_image(qr_image)?);
+    }
 
-    phrases_from_files.chain(phrases_direct).collect()
+    split_phrases.extend(
+        phrases_direct
+            .iter()
+            .filter_map(|phrase| phrase.as_ref())
+            .map(|phrase| clean_and_combine_phrase(phrase)),
+    );
+
+    Ok(split_phrases)
 }
 
 fn read_and_get_phrases_from_file(path: &str) -> Result<Vec<String>> {
-    let file_contents =
-        std::fs::read_to_string(path).wrap_err_with(|| format!("Unable to read file: {}", path))?;
+    Ok(splitmonic::share_file::read_share_file(path)?)
+}
+
+fn setup_split_tui() -> Result<()> {
+    let terminal = setup_terminal()?;
+
+    // Setup input handling
+    let (tx, rx) = unbounded();
+    let mut split_app = SplitApp::new(tx.clone(), rx);
 
-    let words = extracts_words_from_file_contents(file_contents);
+    spawn_input_thread(tx, split_app::Event::Input, || split_app::Event::Tick);
+    split_app.start_event_loop(terminal)?;
 
-    Ok(words)
+    Ok(())
 }
 
-fn extracts_words_from_file_contents(file_contents: String) -> Vec<String> {
-    let mut words = Vec::with_capacity(28);
+fn setup_generate_tui(mnemonic: String) -> Result<()> {
+    let terminal = setup_terminal()?;
 
-    for line in file_contents.lines() {
-        let word: String = line.chars().filter(|char| char.is_alphabetic()).collect();
+    // Setup input handling
+    let (tx, rx) = unbounded();
+    let mut split_app = SplitApp::with_generated_mnemonic(tx.clone(), rx, mnemonic);
 
-        if !word.is_empty() {
-            words.push(word)
-        }
-    }
+    spawn_input_thread(tx, split_app::Event::Input, || split_app::Event::Tick);
+    split_app.start_event_loop(terminal)?;
 
-    words
+    Ok(())
 }
 
-fn setup_split_tui() -> Result<()> {
+fn setup_combine_tui() -> Result<()> {
+    let terminal = setup_terminal()?;
+
+    // Setup input handling
+    let (tx, rx) = unbounded();
+    let mut combine_app = CombineApp::new(tx.clone(), rx);
+
+    spawn_input_thread(tx, combine_app::Event::Input, || combine_app::Event::Tick);
+    combine_app.start_event_loop(terminal)?;
+
+    Ok(())
+}
+
+fn setup_terminal() -> Result<Term> {
     let mut stdout = io::stdout();
 
     terminal::enable_raw_mode()?;
@@ -295,11 +2123,16 @@ fn setup_split_tui() -> Result<()> {
 
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
+    terminal.clear()?;
 
-    // Setup input handling
-    let (tx, rx) = unbounded();
-    let mut split_app = SplitApp::new(tx.clone(), rx);
+    Ok(terminal)
+}
 
+fn spawn_input_thread<E: Send + 'static>(
+    tx: Sender<E>,
+    input_event: fn(KeyEvent) -> E,
+    tick_event: fn() -> E,
+) {
     let tick_rate = Duration::from_secs(5);
     thread::spawn(move || {
         let mut last_tick = Instant::now();
@@ -311,26 +2144,23 @@ fn setup_split_tui() -> Result<()> {
 
             if event::poll(timeout).unwrap() {
                 if let CEvent::Key(key) = event::read().unwrap() {
-                    tx.send(split_app::Event::Input(key)).unwrap();
+                    tx.send(input_event(key)).unwrap();
                 }
             }
 
             if last_tick.elapsed() >= tick_rate {
-                tx.send(split_app::Event::Tick).unwrap();
+                tx.send(tick_event()).unwrap();
                 last_tick = Instant::now();
             }
         }
     });
-
-    terminal.clear()?;
-    split_app.start_event_loop(terminal)?;
-
-    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::temp_dir::TempDir;
+
     const MNEMONIC_CODE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
 
     #[test]
@@ -344,6 +2174,161 @@ mod tests {
         assert_eq!(&mnemonic_code, MNEMONIC_CODE);
     }
 
+    #[test]
+    fn splits_and_combines_with_another_encoding() {
+        let (split_phrases, _) =
+            get_split_phrases(MNEMONIC_CODE.to_string(), true, Encoding::Base58).unwrap();
+
+        let all_split_phrases = format!("-s={}", split_phrases[2..].join(","));
+        let splitmonic = Splitmonic::from_iter(&[
+            "splitmonic",
+            "combine",
+            "--encoding=base58",
+            &all_split_phrases,
+        ]);
+
+        let mnemonic_code = get_mnemonic_code_from_combine_cli(splitmonic).unwrap();
+
+        assert_eq!(&mnemonic_code, MNEMONIC_CODE);
+    }
+
+    #[test]
+    fn splits_to_files_and_combines_with_every_encoding() {
+        let dir = TempDir::new("encodings");
+
+        for name in &[
+            "words",
+            "indexes",
+            "padded-indexes",
+            "binary",
+            "hex",
+            "base32",
+            "base58",
+            "bytewords",
+        ] {
+            let (split_phrases, _) =
+                get_split_phrases(MNEMONIC_CODE.to_string(), false, name.parse().unwrap()).unwrap();
+
+            let paths = split_phrases[1..4]
+                .iter()
+                .enumerate()
+                .map(|(index, split_phrase)| {
+                    let path = dir.join(format!("{}_{}.txt", name, index));
+                    output::write_new_file(&path, split_phrase.as_bytes()).unwrap();
+                    path.to_string_lossy().to_string()
+                })
+                .collect::<Vec<String>>();
+
+            let encoding = format!("--encoding={}", name);
+            let files = format!("-f={}", paths.join(","));
+            let splitmonic = Splitmonic::from_iter(&["splitmonic", "combine", &encoding, &files]);
+
+            let mnemonic_code = get_mnemonic_code_from_combine_cli(splitmonic).unwrap();
+            assert_eq!(&mnemonic_code, MNEMONIC_CODE, "{}", name);
+        }
+
+        let (split_phrases, _) =
+            get_split_phrases(MNEMONIC_CODE.to_string(), false, Encoding::Hex).unwrap();
+        let splitmonic = Splitmonic::from_iter(&[
+            "splitmonic",
+            "combine",
+            "--encoding=hex",
+            &format!("--sp1={}", split_phrases[0]),
+            &format!("--sp2={}", split_phrases[2]),
+            &format!("--sp3={}", split_phrases[4]),
+        ]);
+        assert_eq!(
+            get_mnemonic_code_from_combine_cli(splitmonic).unwrap(),
+            MNEMONIC_CODE
+        );
+    }
+
+    #[test]
+    fn combines_split_phrases_longer_than_28_tokens() {
+        let (split_phrases, _) =
+            get_split_phrases(MNEMONIC_CODE.to_string(), false, Encoding::Bytewords).unwrap();
+        assert!(split_phrases[0].split(' ').count() > 28);
+
+        let mut args = vec!["splitmonic", "combine", "--encoding=bytewords"];
+        for (flag, split_phrase) in ["--sp1", "--sp2", "--sp3"].iter().zip(&[
+            &split_phrases[0],
+            &split_phrases[2],
+            &split_phrases[4],
+        ]) {
+            args.push(flag);
+            args.extend(split_phrase.split(' '));
+        }
+
+        let splitmonic = Splitmonic::from_iter(&args);
+        assert_eq!(
+            get_mnemonic_code_from_combine_cli(splitmonic).unwrap(),
+            MNEMONIC_CODE
+        );
+    }
+
+    #[test]
+    fn combines_split_phrases_written_as_word_numbers() {
+        let (split_phrases, _) =
+            get_split_phrases(MNEMONIC_CODE.to_string(), false, Encoding::Binary).unwrap();
+        let padded = word_numbers::phrase_to_word_numbers(
+            &word_numbers::word_numbers_to_phrase(&split_phrases[1]).unwrap(),
+            WordNumbers::Padded,
+        )
+        .unwrap();
+
+        let all_split_phrases = format!("-s={},{},{}", split_phrases[0], padded, split_phrases[4]);
+        let splitmonic = Splitmonic::from_iter(&["splitmonic", "combine", &all_split_phrases]);
+
+        let mnemonic_code = get_mnemonic_code_from_combine_cli(splitmonic).unwrap();
+
+        assert_eq!(&mnemonic_code, MNEMONIC_CODE);
+    }
+
+    #[test]
+    fn combines_and_inspects_files_of_word_numbers() {
+        let dir = TempDir::new("word_numbers");
+
+        let split_phrases = splitmonic::get_split_phrases(MNEMONIC_CODE.to_string()).unwrap();
+        let decimal =
+            word_numbers::phrase_to_word_numbers(&split_phrases[0], WordNumbers::Decimal).unwrap();
+        let binary =
+            word_numbers::phrase_to_word_numbers(&split_phrases[2], WordNumbers::Binary).unwrap();
+        let padded =
+            word_numbers::phrase_to_word_numbers(&split_phrases[3], WordNumbers::Padded).unwrap();
+
+        // plain text, one word number per line, and the numbered format
+        let numbered = padded
+            .split(' ')
+            .enumerate()
+            .map(|(index, number)| format!("{}: {}", index + 1, number))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let files = [
+            (dir.join("decimal.txt"), decimal),
+            (dir.join("binary.txt"), binary.replace(' ', "\n")),
+            (dir.join("padded.txt"), numbered),
+        ];
+        for (path, contents) in &files {
+            output::write_new_file(path, contents.as_bytes()).unwrap();
+        }
+
+        let paths = files
+            .iter()
+            .map(|(path, _)| path.to_string_lossy().to_string())
+            .collect::<Vec<String>>();
+        let splitmonic =
+            Splitmonic::from_iter(&["splitmonic", "combine", &format!("-f={}", paths.join(","))]);
+        assert_eq!(
+            get_mnemonic_code_from_combine_cli(splitmonic).unwrap(),
+            MNEMONIC_CODE
+        );
+
+        let splitmonic = Splitmonic::from_iter(&["splitmonic", "inspect", "-f", &paths[2]]);
+        let info = get_split_phrase_info_from_inspect_cli(splitmonic).unwrap();
+        assert_eq!(info.share_index, 4);
+    }
+
     #[test]
     fn combines_using_phrases_passed_in_separately() {
         let splitmonic = Splitmonic::from_iter(&["splitmonic", "combine", 
@@ -356,6 +2341,397 @@ mod tests {
         assert_eq!(&mnemonic_code, MNEMONIC_CODE);
     }
 
+    #[test]
+    fn splits_without_an_output_dir() {
+        let splitmonic = Splitmonic::from_iter_safe(&["splitmonic", "split", "--mnemonic-stdin"]);
+        assert!(splitmonic.is_ok());
+    }
+
+    #[test]
+    fn inspects_a_split_phrase_passed_in_directly() {
+        let splitmonic = Splitmonic::from_iter(&["splitmonic", "inspect",
+        "-s=embody, fog, drop, able, network, accident, hedgehog, sibling, toilet, outdoor, quick, subway, hurdle, picture, property, false, quit, notable, panther, crucial, already, supply, mother, beef, recycle, spell, rich, enhance"]);
+
+        let info = get_split_phrase_info_from_inspect_cli(splitmonic).unwrap();
+
+        assert_eq!(info.set_id, "embody fog drop");
+        assert_eq!(info.share_index, 2);
+        assert!(info.is_valid());
+    }
+
+    #[test]
+    fn splits_and_combines_a_hex_secret() {
+        let splitmonic = Splitmonic::from_iter(&[
+            "splitmonic",
+            "split-secret",
+            "--hex=deadbeef00ff",
+            "-t=2",
+            "-n=3",
+        ]);
+
+        let secret_phrases = get_secret_phrases_from_split_secret_cli(splitmonic).unwrap();
+        assert_eq!(secret_phrases.len(), 3);
+
+        let secret =
+            get_secret_from_combine_secret_cli(Some(secret_phrases[1..].to_vec()), None).unwrap();
+
+        assert_eq!(hex::encode(secret), "deadbeef00ff");
+    }
+
+    #[test]
+    fn exports_and_imports_vault_shares() {
+        let secret_phrases = splitmonic::secret::split_secret(b"hunter2", 2, 3).unwrap();
+        let splitmonic = Splitmonic::from_iter(&[
+            "splitmonic",
+            "export-vault",
+            "--hex",
+            "-s",
+            &secret_phrases.join(","),
+        ]);
+
+        let vault_shares = match splitmonic {
+            Splitmonic::ExportVault {
+                secret_phrases,
+                split_phrases,
+                hex,
+                ..
+            } => {
+                get_vault_shares_from_export_vault_cli(secret_phrases, split_phrases, hex).unwrap()
+            }
+            _ => unreachable!(),
+        };
+        assert_eq!(vault_shares.len(), 3);
+        assert!(vault_shares[0].ends_with("01"));
+
+        let splitmonic = Splitmonic::from_iter(&[
+            "splitmonic",
+            "import-vault",
+            "-t=2",
+            "--hex",
+            "-k",
+            &vault_shares[1..].join(","),
+        ]);
+
+        let secret_phrases = match splitmonic {
+            Splitmonic::ImportVault {
+                vault_shares: Some(vault_shares),
+                threshold,
+                hex,
+                ..
+            } => {
+                vault::vault_shares_to_secret_phrases(&vault_shares, threshold, vault_encoding(hex))
+                    .unwrap()
+            }
+            _ => unreachable!(),
+        };
+        let secret = get_secret_from_combine_secret_cli(Some(secret_phrases), None).unwrap();
+
+        assert_eq!(secret, b"hunter2");
+    }
+
+    #[test]
+    fn exports_and_imports_ssss_shares() {
+        let split_phrases = splitmonic::get_split_phrases(MNEMONIC_CODE.to_string()).unwrap();
+        let splitmonic = Splitmonic::from_iter(&[
+            "splitmonic",
+            "export-ssss",
+            "-t=2",
+            "-n=3",
+            "-p",
+            &split_phrases[..3].join(","),
+        ]);
+
+        let ssss_shares = match splitmonic {
+            Splitmonic::ExportSsss {
+                secret_phrases,
+                split_phrases,
+                threshold,
+                shares,
+                ..
+            } => get_ssss_shares_from_export_ssss_cli(
+                secret_phrases,
+                split_phrases,
+                threshold,
+                shares,
+            )
+            .unwrap(),
+            _ => unreachable!(),
+        };
+        assert_eq!(ssss_shares.len(), 3);
+        assert!(ssss_shares[2].starts_with("3-"));
+
+        let splitmonic = Splitmonic::from_iter(&[
+            "splitmonic",
+            "import-ssss",
+            "-t=2",
+            "-k",
+            &ssss_shares[1..].join(","),
+        ]);
+
+        let secret_phrases = match splitmonic {
+            Splitmonic::ImportSsss {
+                ssss_shares: Some(ssss_shares),
+                threshold,
+                shares,
+                ..
+            } => ssss::ssss_shares_to_secret_phrases(&ssss_shares, threshold, shares).unwrap(),
+            _ => unreachable!(),
+        };
+        assert_eq!(secret_phrases.len(), 5);
+
+        let entropy =
+            get_secret_from_combine_secret_cli(Some(secret_phrases[3..].to_vec()), None).unwrap();
+
+        // the entropy of abandon (x23) art
+        assert_eq!(entropy, vec![0; 32]);
+    }
+
+    #[test]
+    fn reads_secrets_from_stdin_or_a_file_descriptor_instead_of_arguments() {
+        for args in &[
+            vec!["recover-words", "--mnemonic-stdin"],
+            vec!["split-secret", "--secret-fd", "3"],
+            vec!["split-secret", "--hex-stdin"],
+            vec!["combine-secret", "--secret-phrases-stdin"],
+            vec!["export-vault", "--split-phrases-fd", "3"],
+            vec!["import-vault", "-t=3", "--vault-shares-stdin"],
+            vec!["export-ssss", "--secret-phrases-stdin"],
+            vec!["import-ssss", "-t=3", "--ssss-shares-fd", "3"],
+        ] {
+            let args = std::iter::once("splitmonic").chain(args.iter().copied());
+            assert!(Splitmonic::from_iter_safe(args).is_ok());
+        }
+
+        for args in &[
+            vec!["recover-words"],
+            vec!["split-secret", "--secret=hunter2", "--secret-stdin"],
+            vec!["import-vault", "-t=3", "-k=abcd", "--vault-shares-fd", "3"],
+        ] {
+            let args = std::iter::once("splitmonic").chain(args.iter().copied());
+            assert!(Splitmonic::from_iter_safe(args).is_err());
+        }
+
+        assert_eq!(
+            get_phrases_from_cli(Some(vec![" one ".to_string()]), false, None, "vault-shares")
+                .unwrap(),
+            Some(vec!["one".to_string()])
+        );
+        assert_eq!(
+            get_phrases_from_cli(None, false, None, "split-phrases").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn combines_sskr_shares_from_every_group_in_files() {
+        let dir = TempDir::new("sskr");
+
+        let groups = vec!["2-of-3".parse().unwrap(), "3-of-5".parse().unwrap()];
+        let (shares, _) =
+            get_sskr_shares(MNEMONIC_CODE.to_string(), Some(2), Some(groups)).unwrap();
+
+        // 5 shares are needed, 2 from the first group and 3 from the second
+        let quorum = [
+            shares[0][0].to_ur(),
+            shares[0][1].to_bytewords(),
+            shares[1][0].to_ur().to_uppercase(),
+            shares[1][2].to_bytewords(),
+            shares[1][4].to_ur(),
+        ];
+        let paths = quorum
+            .iter()
+            .enumerate()
+            .map(|(index, share)| {
+                let path = dir.join(format!("sskr_{}.txt", index));
+                output::write_new_file(&path, share.as_bytes()).unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect::<Vec<String>>();
+
+        let files = format!("-f={}", paths.join(","));
+        let splitmonic = Splitmonic::from_iter(&["splitmonic", "combine", &files]);
+        assert_eq!(
+            get_mnemonic_code_from_combine_cli(splitmonic).unwrap(),
+            MNEMONIC_CODE
+        );
+
+        let all_split_phrases = format!("-s={}", quorum.join(","));
+        let splitmonic = Splitmonic::from_iter(&["splitmonic", "combine", &all_split_phrases]);
+        assert_eq!(
+            get_mnemonic_code_from_combine_cli(splitmonic).unwrap(),
+            MNEMONIC_CODE
+        );
+    }
+
+    #[test]
+    fn splits_and_combines_sskr_shares() {
+        let groups = vec!["2-of-3".parse().unwrap(), "1-of-1".parse().unwrap()];
+        let (shares, fingerprint) =
+            get_sskr_shares(MNEMONIC_CODE.to_string(), Some(2), Some(groups)).unwrap();
+        assert_eq!(fingerprint, "5436d724");
+
+        let all_split_phrases = format!(
+            "-s={},{},{}",
+            shares[0][0].to_ur(),
+            shares[0][2].to_bytewords(),
+            shares[1][0].to_ur()
+        );
+        let splitmonic = Splitmonic::from_iter(&["splitmonic", "combine", &all_split_phrases]);
+
+        let mnemonic_code = get_mnemonic_code_from_combine_cli(splitmonic).unwrap();
+
+        assert_eq!(&mnemonic_code, MNEMONIC_CODE);
+    }
+
+    #[test]
+    fn splits_and_combines_codex32_shares() {
+        let (shares, fingerprint) =
+            get_codex32_shares(MNEMONIC_CODE.to_string(), Some(2), Some(3), Some("test")).unwrap();
+        assert_eq!(fingerprint, "5436d724");
+
+        let all_split_phrases = format!(
+            "-s={},{},{}",
+            shares[2],
+            shares[0].to_string().to_uppercase(),
+            shares[1]
+        );
+        let splitmonic = Splitmonic::from_iter(&["splitmonic", "combine", &all_split_phrases]);
+
+        let mnemonic_code = get_mnemonic_code_from_combine_cli(splitmonic).unwrap();
+
+        assert_eq!(&mnemonic_code, MNEMONIC_CODE);
+    }
+
+    #[test]
+    fn combines_more_than_3_codex32_shares_from_files() {
+        let dir = TempDir::new("codex32");
+
+        let (shares, _) =
+            get_codex32_shares(MNEMONIC_CODE.to_string(), Some(4), Some(6), None).unwrap();
+
+        let paths = shares[1..5]
+            .iter()
+            .enumerate()
+            .map(|(index, share)| {
+                let path = dir.join(format!("codex32_{}.txt", index));
+                let share = match index {
+                    0 => share.to_string().to_uppercase(),
+                    _ => share.to_string(),
+                };
+                output::write_new_file(&path, share.as_bytes()).unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect::<Vec<String>>();
+
+        let files = format!("-f={}", paths.join(","));
+        let splitmonic = Splitmonic::from_iter(&["splitmonic", "combine", &files]);
+        assert_eq!(
+            get_mnemonic_code_from_combine_cli(splitmonic).unwrap(),
+            MNEMONIC_CODE
+        );
+    }
+
+    #[test]
+    fn rejects_split_options_the_scheme_doesnt_use() {
+        let unused = |args: &[&str]| {
+            Splitmonic::from_iter([&["splitmonic", "split"], args].concat()).unused_split_options()
+        };
+
+        assert!(unused(&["--no-verify", "--print-cards=cards"]).is_empty());
+        assert!(unused(&["--scheme=xor", "--parts=4"]).is_empty());
+        assert!(unused(&["--scheme=codex32", "--threshold=2", "--shares=3"]).is_empty());
+
+        assert_eq!(
+            unused(&["--scheme=xor", "--no-verify", "--encoding=hex"]),
+            vec![
+                ("--no-verify", "--scheme splitmonic"),
+                ("--encoding", "--scheme splitmonic")
+            ]
+        );
+        assert_eq!(
+            unused(&["--encoding=hex", "--file-name={n}.txt", "--seed-qr=compact"]),
+            vec![
+                ("--file-name", "split phrases written as words"),
+                ("--seed-qr", "split phrases written as words")
+            ]
+        );
+        assert_eq!(
+            unused(&["--scheme=sskr", "--parts=2", "--identifier=test"]),
+            vec![
+                ("--identifier", "--scheme codex32"),
+                ("--parts", "--scheme xor")
+            ]
+        );
+    }
+
+    #[test]
+    fn combines_seed_xor_parts() {
+        let (parts, _) = get_seed_xor_parts(MNEMONIC_CODE.to_string(), Some(2)).unwrap();
+
+        let sp1 = format!("--sp1={}", parts[1].replace(" ", ","));
+        let sp2 = format!("--sp2={}", parts[0].replace(" ", ","));
+        let splitmonic = Splitmonic::from_iter(&[
+            "splitmonic",
+            "combine",
+            &sp1,
+            &sp2,
+            "--expect-fingerprint=5436d724",
+        ]);
+
+        let mnemonic_code = get_mnemonic_code_from_combine_cli(splitmonic).unwrap();
+
+        assert_eq!(&mnemonic_code, MNEMONIC_CODE);
+    }
+
+    #[test]
+    fn combines_more_than_3_seed_xor_parts_only_with_the_expected_fingerprint() {
+        let (parts, fingerprint) = get_seed_xor_parts(MNEMONIC_CODE.to_string(), Some(5)).unwrap();
+        assert_eq!(fingerprint, "5436d724");
+
+        let all_parts = format!("-s={}", parts.join(","));
+        let splitmonic = Splitmonic::from_iter(&[
+            "splitmonic",
+            "combine",
+            &all_parts,
+            "--expect-fingerprint=5436D724",
+        ]);
+
+        let mnemonic_code = get_mnemonic_code_from_combine_cli(splitmonic).unwrap();
+        assert_eq!(&mnemonic_code, MNEMONIC_CODE);
+
+        // 4 of the 5 parts still combine into a valid mnemonic, but not the one that was split
+        let some_parts = format!("-s={}", parts[..4].join(","));
+        let splitmonic = Splitmonic::from_iter(&[
+            "splitmonic",
+            "combine",
+            &some_parts,
+            "--expect-fingerprint=5436d724",
+        ]);
+
+        let error = get_mnemonic_code_from_combine_cli(splitmonic).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("doesn't match the expected fingerprint 5436d724"));
+    }
+
+    #[test]
+    fn needs_the_expected_fingerprint_to_combine_seed_xor_parts_with_json_output() {
+        let (parts, _) = get_seed_xor_parts(MNEMONIC_CODE.to_string(), Some(2)).unwrap();
+
+        let all_parts = format!("-s={}", parts.join(","));
+        let splitmonic =
+            Splitmonic::from_iter(&["splitmonic", "combine", &all_parts, "--format=json"]);
+
+        let error = get_mnemonic_code_from_combine_cli(splitmonic).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("use --expect-fingerprint instead"));
+        assert_eq!(
+            Failure::new("combining", error).exit_code(),
+            exit::ExitCode::InvalidInput
+        );
+    }
+
     #[test]
     fn extracts_words_from_output_file_format() {
         let file_contents = "
@@ -399,6 +2775,9 @@ mod tests {
         .map(ToString::to_string)
         .collect();
 
-        assert_eq!(extracts_words_from_file_contents(file_contents), words_list)
+        assert_eq!(
+            splitmonic::share_file::parse_shares(&file_contents),
+            Ok(vec![words_list])
+        )
     }
 }
@@ -0,0 +1,266 @@
+//! Writing split phrases to files from the CLI, each file is only readable by its owner and is
+//! synced to disk before splitmonic exits, existing files are never overwritten
+
+use eyre::{Context, Result};
+use splitmonic::share_file::{self, ShareFileHeader};
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// How the result of a command is printed, `json` is meant for scripts and its fields won't change
+/// between versions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown output format: {}", format)),
+        }
+    }
+}
+
+/// The file name template used when none is given, the same names the split TUI uses
+pub const DEFAULT_FILE_NAME_TEMPLATE: &str = "phrases_{n}_of_{total}.txt";
+
+/// The text of a split phrase file in the versioned share file format, see [share_file]
+pub fn split_phrase_file_text(split_phrase: &str) -> Result<String> {
+    let header = ShareFileHeader::for_split_phrase(
+        split_phrase,
+        Some(utc_now()),
+        Some(format!("splitmonic {}", env!("CARGO_PKG_VERSION"))),
+    )?;
+
+    Ok(share_file::share_file_text(split_phrase, &header))
+}
+
+/// The current time in UTC, ex: `2021-06-01T12:00:00Z`
+fn utc_now() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+
+    let (days, seconds_of_day) = (seconds / 86400, seconds % 86400);
+    let (year, month, day) = civil_from_days(days as i64);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60
+    )
+}
+
+/// Converts days since 1970-01-01 to a (year, month, day) date, from Howard Hinnant's
+/// `civil_from_days` algorithm
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+/// Fills in the file name template, `{n}` is the split phrase number, `{total}` the number of
+/// split phrases and `{fingerprint}` the mnemonic fingerprint
+pub fn file_name(template: &str, number: usize, total: usize, fingerprint: &str) -> String {
+    template
+        .replace("{n}", &number.to_string())
+        .replace("{total}", &total.to_string())
+        .replace("{fingerprint}", fingerprint)
+}
+
+/// Works out where each split phrase is written to. Either all split phrases go in one directory,
+/// or each split phrase gets its own directory (ex: separate USB sticks)
+fn output_paths(
+    output_dirs: &[PathBuf],
+    template: &str,
+    total: usize,
+    fingerprint: &str,
+) -> Result<Vec<PathBuf>> {
+    if template.contains('/') || template.contains(std::path::MAIN_SEPARATOR) {
+        return Err(eyre::eyre!(
+            "The file name template can't contain a path separator: {}",
+            template
+        ));
+    }
+
+    match output_dirs.len() {
+        1 if !template.contains("{n}") => Err(eyre::eyre!(
+            "The file name template needs `{{n}}` when all split phrases are written to one directory: {}",
+            template
+        )),
+
+        1 => Ok((1..=total)
+            .map(|number| output_dirs[0].join(file_name(template, number, total, fingerprint)))
+            .collect()),
+
+        len if len == total => Ok(output_dirs
+            .iter()
+            .enumerate()
+            .map(|(index, dir)| dir.join(file_name(template, index + 1, total, fingerprint)))
+            .collect()),
+
+        len => Err(eyre::eyre!(
+            "Expected 1 output directory, or {} (one for each split phrase), got {}",
+            total,
+            len
+        )),
+    }
+}
+
+/// Writes each split phrase to its own file and returns the paths written to. Nothing is written
+/// if any of the files already exist.
+pub fn write_split_phrases(
+    split_phrases: &[String],
+    output_dirs: &[PathBuf],
+    template: &str,
+    fingerprint: &str,
+) -> Result<Vec<PathBuf>> {
+    let paths = output_paths(output_dirs, template, split_phrases.len(), fingerprint)?;
+
+    if let Some(existing) = paths.iter().find(|path| path.exists()) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!(
+                "Refusing to overwrite existing file: {}",
+                existing.display()
+            ),
+        )
+        .into());
+    }
+
+    for (split_phrase, path) in split_phrases.iter().zip(&paths) {
+        write_new_file(path, split_phrase_file_text(split_phrase)?.as_bytes())
+            .wrap_err_with(|| format!("Unable to write split phrase to: {}", path.display()))?;
+    }
+
+    Ok(paths)
+}
+
+/// Creates a new file that only its owner can read, fails if the file already exists
+pub fn write_new_file(path: &Path, contents: &[u8]) -> Result<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    std::fs::create_dir_all(dir)?;
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+
+    // sync the directory too, so the new file's entry is on disk before the USB stick is pulled
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp_dir::TempDir;
+
+    fn split_phrases() -> Vec<String> {
+        splitmonic::get_split_phrases("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art".to_string()).unwrap()
+    }
+
+    #[test]
+    fn writes_each_split_phrase_to_its_own_directory() {
+        let root = TempDir::new("own_directory");
+        let dirs = (1..=5)
+            .map(|number| root.join(format!("usb{}", number)))
+            .collect::<Vec<PathBuf>>();
+
+        let split_phrases = split_phrases();
+        let paths = write_split_phrases(
+            &split_phrases,
+            &dirs,
+            "share_{n}_{fingerprint}.txt",
+            "5436d724",
+        )
+        .unwrap();
+
+        assert_eq!(paths[2], root.join("usb3").join("share_3_5436d724.txt"));
+        assert_eq!(
+            share_file::read_share_file(&paths[2]).unwrap().join(" "),
+            split_phrases[2]
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&paths[2]).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn refuses_to_overwrite_existing_files() {
+        let dir = TempDir::new("overwrite");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("phrases_4_of_5.txt"), "keep me").unwrap();
+
+        let result = write_split_phrases(
+            &split_phrases(),
+            &[dir.to_path_buf()],
+            DEFAULT_FILE_NAME_TEMPLATE,
+            "5436d724",
+        );
+
+        assert!(result.is_err());
+        assert!(!dir.join("phrases_1_of_5.txt").exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.join("phrases_4_of_5.txt")).unwrap(),
+            "keep me"
+        );
+    }
+
+    #[test]
+    fn converts_days_to_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(11016), (2000, 2, 29));
+        assert_eq!(civil_from_days(18779), (2021, 6, 1));
+    }
+
+    #[test]
+    fn errors_on_templates_that_would_collide() {
+        let dirs = [PathBuf::from("one")];
+
+        assert!(output_paths(&dirs, "phrases.txt", 5, "5436d724").is_err());
+        assert!(output_paths(&dirs, "../phrases_{n}.txt", 5, "5436d724").is_err());
+        assert!(output_paths(&[dirs[0].clone(), dirs[0].clone()], "p_{n}", 5, "").is_err());
+    }
+}
@@ -0,0 +1,303 @@
+//! QR codes of split phrases, using the compact word index encoding from [splitmonic::qr], shown
+//! in the terminal with unicode half blocks, saved as PNG and SVG files, or read back from photos
+//! and screenshots
+
+use eyre::Result;
+use image::{DynamicImage, ImageError, ImageFormat, Luma};
+use qrcode::{
+    bits::Bits,
+    render::{svg, unicode::Dense1x2},
+    Color, EcLevel, QrCode, Version,
+};
+use splitmonic::qr::SeedQrFormat;
+use std::io::Cursor;
+use std::path::Path;
+
+/// The size in pixels of each QR code module in PNG files
+const PNG_MODULE_SIZE: u32 = 8;
+
+pub fn split_phrase_qr(split_phrase: &str) -> Result<QrCode> {
+    let bytes = splitmonic::qr::encode_compact(split_phrase)?;
+    Ok(QrCode::with_error_correction_level(bytes, EcLevel::M)?)
+}
+
+/// A SeedQR of a 12 or 24 word mnemonic code, and the data in it
+pub struct SeedQr {
+    pub format: SeedQrFormat,
+    pub payload: Vec<u8>,
+    pub qr: QrCode,
+}
+
+impl SeedQr {
+    /// Uses the version and mode SeedQR readers expect: numeric mode for a standard SeedQR, byte
+    /// mode for a CompactSeedQR, and low error correction
+    pub fn new(mnemonic_code: &str, format: SeedQrFormat) -> Result<Self> {
+        let payload = splitmonic::qr::seed_qr_payload(mnemonic_code, format)?;
+        let twelve_words = payload.len() == 48 || payload.len() == 16;
+
+        let version = match (format, twelve_words) {
+            (SeedQrFormat::Standard, true) => 2,
+            (SeedQrFormat::Standard, false) => 3,
+            (SeedQrFormat::Compact, true) => 1,
+            (SeedQrFormat::Compact, false) => 2,
+        };
+
+        let mut bits = Bits::new(Version::Normal(version));
+        match format {
+            SeedQrFormat::Standard => bits.push_numeric_data(&payload)?,
+            SeedQrFormat::Compact => bits.push_byte_data(&payload)?,
+        }
+        bits.push_terminator(EcLevel::L)?;
+
+        Ok(Self {
+            format,
+            payload,
+            qr: QrCode::with_bits(bits, EcLevel::L)?,
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self.format {
+            SeedQrFormat::Standard => "SeedQR",
+            SeedQrFormat::Compact => "CompactSeedQR",
+        }
+    }
+
+    /// The digits of a standard SeedQR, or the bytes of a CompactSeedQR as hex
+    pub fn payload_text(&self) -> String {
+        match self.format {
+            SeedQrFormat::Standard => String::from_utf8_lossy(&self.payload).to_string(),
+            SeedQrFormat::Compact => hex::encode(&self.payload),
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let width = self.qr.width();
+        let rows = self
+            .qr
+            .to_colors()
+            .chunks(width)
+            .map(|modules| {
+                modules
+                    .iter()
+                    .map(|module| if *module == Color::Dark { '1' } else { '0' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>();
+
+        serde_json::json!({
+            "format": self.name(),
+            "payload": self.payload_text(),
+            "size": width,
+            "rows": rows,
+        })
+    }
+}
+
+/// The QR code as a grid to copy onto a SeedQR template by hand, with the columns and rows
+/// numbered and the dark modules filled in
+pub fn to_grid(qr: &QrCode) -> Vec<String> {
+    let width = qr.width();
+    let header = (1..=width)
+        .map(|column| format!("{:>3}", column))
+        .collect::<String>();
+
+    let mut grid = vec![format!("   {}", header)];
+    for (row, modules) in qr.to_colors().chunks(width).enumerate() {
+        let modules = modules
+            .iter()
+            .map(|module| match module {
+                Color::Dark => "  ■",
+                Color::Light => "  ·",
+            })
+            .collect::<String>();
+
+        grid.push(format!("{:>3}{}", row + 1, modules));
+    }
+
+    grid
+}
+
+/// The QR code as lines of unicode half blocks, two rows of modules per line. The colors are
+/// inverted so the QR code scans on terminals with a dark background.
+pub fn to_half_blocks(qr: &QrCode) -> Vec<String> {
+    qr.render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .quiet_zone(true)
+        .build()
+        .lines()
+        .map(ToString::to_string)
+        .collect()
+}
+
+pub fn to_svg(qr: &QrCode) -> String {
+    qr.render::<svg::Color>()
+        .dark_color(svg::Color("#000000"))
+        .light_color(svg::Color("#ffffff"))
+        .quiet_zone(true)
+        .build()
+}
+
+pub fn to_png(qr: &QrCode) -> Result<Vec<u8>> {
+    let image = qr
+        .render::<Luma<u8>>()
+        .module_dimensions(PNG_MODULE_SIZE, PNG_MODULE_SIZE)
+        .quiet_zone(true)
+        .build();
+
+    let mut png = Cursor::new(vec![]);
+    image.write_to(&mut png, ImageFormat::Png)?;
+
+    Ok(png.into_inner())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum QrImageError {
+    #[error("Unable to read {path}: {source}")]
+    Unreadable {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("{path} is not a PNG or JPEG image: {reason}")]
+    NotAnImage { path: String, reason: String },
+
+    #[error("No QR code found in {path}")]
+    NoQrCode { path: String },
+
+    #[error("The QR code in {path} can't be read: {reason}")]
+    Undecodable { path: String, reason: String },
+}
+
+/// Reads the split phrases from every QR code in a PNG or JPEG image
+pub fn read_qr_image(path: &Path) -> Result<Vec<String>, QrImageError> {
+    let display = path.display().to_string();
+
+    let image = image::open(path).map_err(|error| match error {
+        ImageError::IoError(source) => QrImageError::Unreadable {
+            path: display.clone(),
+            source,
+        },
+        error => QrImageError::NotAnImage {
+            path: display.clone(),
+            reason: error.to_string(),
+        },
+    })?;
+
+    decode_qr_image(image, &display)
+}
+
+fn decode_qr_image(image: DynamicImage, path: &str) -> Result<Vec<String>, QrImageError> {
+    let undecodable = |reason: String| QrImageError::Undecodable {
+        path: path.to_string(),
+        reason,
+    };
+
+    let mut image = rqrr::PreparedImage::prepare(image.to_luma8());
+    let grids = image.detect_grids();
+
+    if grids.is_empty() {
+        return Err(QrImageError::NoQrCode {
+            path: path.to_string(),
+        });
+    }
+
+    grids
+        .iter()
+        .map(|grid| {
+            let mut bytes = vec![];
+            grid.decode_to(&mut bytes)
+                .map_err(|error| undecodable(error.to_string()))?;
+
+            split_phrase_from_payload(&bytes).map_err(|error| undecodable(error.to_string()))
+        })
+        .collect()
+}
+
+/// QR codes made by splitmonic hold the compact encoding, but a QR code holding the words of the
+/// split phrase as text (made by another tool) is read too
+fn split_phrase_from_payload(bytes: &[u8]) -> Result<String, splitmonic::Error> {
+    match std::str::from_utf8(bytes) {
+        Ok(text)
+            if text
+                .chars()
+                .all(|char| char.is_ascii_lowercase() || char.is_whitespace())
+                && text.contains(char::is_whitespace) =>
+        {
+            Ok(text.split_whitespace().collect::<Vec<&str>>().join(" "))
+        }
+        _ => splitmonic::qr::decode_compact(bytes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPLIT_PHRASE: &str = "embody fog drop about embrace visa adapt winner wine dash fabric snack drip auction deputy visit shift animal various bread country lecture assist marriage merit goat gravity glove";
+
+    #[test]
+    fn renders_a_qr_code_that_fits_in_the_terminal() {
+        let qr = split_phrase_qr(SPLIT_PHRASE).unwrap();
+        let lines = to_half_blocks(&qr);
+
+        // the modules plus a 4 module quiet zone on each side, 2 rows of modules per line
+        let size = qr.width() + 8;
+        assert_eq!(lines.len(), size.div_ceil(2));
+        assert!(lines.iter().all(|line| line.chars().count() == size));
+        assert!(size <= 45);
+    }
+
+    #[test]
+    fn saves_png_and_svg_files() {
+        let qr = split_phrase_qr(SPLIT_PHRASE).unwrap();
+
+        assert!(to_png(&qr).unwrap().starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(to_svg(&qr).contains("<svg"));
+    }
+
+    #[test]
+    fn creates_seed_qrs_that_read_back() {
+        let body = splitmonic::qr::split_phrase_body(SPLIT_PHRASE).unwrap();
+
+        let standard = SeedQr::new(&body, SeedQrFormat::Standard).unwrap();
+        assert_eq!(standard.qr.width(), 29);
+        let compact = SeedQr::new(&body, SeedQrFormat::Compact).unwrap();
+        assert_eq!(compact.qr.width(), 25);
+        assert_eq!(compact.payload_text().len(), 64);
+
+        let grid = to_grid(&compact.qr);
+        assert_eq!(grid.len(), 26);
+        assert!(grid[0].ends_with(" 25"));
+        assert!(grid[25].starts_with(" 25  ■"));
+
+        let image = DynamicImage::ImageLuma8(standard.qr.render::<Luma<u8>>().build());
+        let mut image = rqrr::PreparedImage::prepare(image.to_luma8());
+        let (_, digits) = image.detect_grids()[0].decode().unwrap();
+        assert_eq!(digits, standard.payload_text());
+    }
+
+    #[test]
+    fn reads_split_phrases_back_from_images() {
+        let png = to_png(&split_phrase_qr(SPLIT_PHRASE).unwrap()).unwrap();
+        let image = image::load_from_memory(&png).unwrap();
+        assert_eq!(
+            decode_qr_image(image, "share.png").unwrap(),
+            vec![SPLIT_PHRASE]
+        );
+
+        let text_qr = QrCode::new(SPLIT_PHRASE).unwrap();
+        let image = DynamicImage::ImageLuma8(text_qr.render::<Luma<u8>>().build());
+        assert_eq!(
+            decode_qr_image(image, "share.png").unwrap(),
+            vec![SPLIT_PHRASE]
+        );
+
+        let blank = DynamicImage::new_luma8(100, 100);
+        assert!(matches!(
+            decode_qr_image(blank, "blank.png"),
+            Err(QrImageError::NoQrCode { .. })
+        ));
+    }
+}
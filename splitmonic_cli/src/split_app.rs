@@ -1,6 +1,12 @@
 mod view;
 
-use crate::{ui::util::stateful_list::StatefulList, Term};
+use crate::{
+    output, qr,
+    ui::message::{Error, Message},
+    ui::util::stateful_list::StatefulList,
+    ui::InputMode,
+    Term,
+};
 use crossbeam_channel::{Receiver, Sender};
 use eyre::Result;
 use splitmonic::wordlist::english::English;
@@ -11,11 +17,11 @@ use crossterm::{
     execute, terminal,
 };
 use maplit::hashmap;
-use std::{borrow::Cow, collections::HashMap, fs::File, io::Write, path::PathBuf};
+use std::{borrow::Cow, collections::HashMap, path::PathBuf};
 
 pub enum Effect {
     ReceivedMessage(Message),
-    ReceivedPhrases(Vec<String>),
+    ReceivedPhrases(Vec<String>, String),
 }
 
 impl Effect {
@@ -27,8 +33,8 @@ impl Effect {
         Self::ReceivedMessage(Message::success(message.into()))
     }
 
-    fn phrases(phrases: Vec<String>) -> Self {
-        Self::ReceivedPhrases(phrases)
+    fn phrases(phrases: Vec<String>, fingerprint: String) -> Self {
+        Self::ReceivedPhrases(phrases, fingerprint)
     }
 }
 
@@ -38,21 +44,6 @@ pub enum Event {
     Tick,
 }
 
-#[derive(Debug, thiserror::Error)]
-pub enum Error {
-    #[error(transparent)]
-    Lib(#[from] splitmonic::Error),
-
-    #[error(transparent)]
-    Other(#[from] eyre::Report),
-}
-
-pub enum InputMode {
-    Normal,
-    Inserting,
-    Editing(Option<usize>),
-}
-
 pub enum Screen {
     WordInput(InputMode),
     List,
@@ -60,24 +51,6 @@ pub enum Screen {
     SaveLocationInput,
 }
 
-#[derive(Debug)]
-pub enum Message {
-    None,
-    Debug(String),
-    Error(Error),
-    Success(String),
-}
-
-impl Message {
-    fn success(message: String) -> Self {
-        Self::Success(message)
-    }
-
-    fn error(error: Error) -> Self {
-        Self::Error(error)
-    }
-}
-
 pub struct SplitApp {
     tx: Sender<Event>,
     rx: Receiver<Event>,
@@ -94,6 +67,16 @@ pub struct SplitApp {
 
     pub phrases: [StatefulList<String>; 5],
     pub selected_phrases: HashMap<usize, bool>,
+    pub fingerprint: Option<String>,
+
+    /// the mnemonic was generated by splitmonic, it is never shown and can't be edited
+    pub generated: bool,
+
+    /// the number of words in the mnemonic, 24 unless a shorter mnemonic was generated
+    pub word_count: usize,
+
+    /// show the QR code of the selected split phrase instead of the words
+    pub show_qr: bool,
 }
 
 impl SplitApp {
@@ -108,6 +91,10 @@ impl SplitApp {
             mnemonic: StatefulList::new(),
             phrases: empty_phrases(),
             selected_phrases: hashmap! {0 => false, 1 => false, 2 => false, 3 => false, 4 => false},
+            fingerprint: None,
+            generated: false,
+            word_count: 24,
+            show_qr: false,
             should_quit: false,
             save_location: dirs::home_dir()
                 .as_ref()
@@ -117,6 +104,23 @@ impl SplitApp {
         }
     }
 
+    /// Starts with a freshly generated mnemonic, which is split right away
+    pub fn with_generated_mnemonic(
+        tx: Sender<Event>,
+        rx: Receiver<Event>,
+        mnemonic_code: String,
+    ) -> Self {
+        let mut split_app = Self::new(tx, rx);
+
+        split_app.generated = true;
+        split_app.mnemonic =
+            StatefulList::with_items(mnemonic_code.split(' ').map(ToString::to_string).collect());
+        split_app.word_count = split_app.mnemonic.len();
+        split_app.split_mnemonic();
+
+        split_app
+    }
+
     pub fn start_event_loop(&mut self, mut terminal: Term) -> Result<()> {
         loop {
             terminal.draw(|f| view::draw(self, f))?;
@@ -136,7 +140,8 @@ impl SplitApp {
                         self.update_in_phrase_list(event, phrase_list_index)
                     }
                 },
-                Event::Effect(Effect::ReceivedPhrases(phrases)) => {
+                Event::Effect(Effect::ReceivedPhrases(phrases, fingerprint)) => {
+                    self.fingerprint = Some(fingerprint);
                     self.select_all_phrases();
                     self.select_phrase_list(None, 0);
 
@@ -173,7 +178,7 @@ impl SplitApp {
             KeyCode::Char(char) => {
                 self.input.push(char);
 
-                match English::starting_with(&self.input).as_slice() {
+                match self.words_starting_with(&self.input, edit).as_slice() {
                     [] => {
                         self.autocomplete = "";
                         self.input.pop();
@@ -190,7 +195,7 @@ impl SplitApp {
             KeyCode::Backspace => {
                 self.input.pop();
 
-                match English::starting_with(&self.input).as_slice() {
+                match self.words_starting_with(&self.input, edit).as_slice() {
                     [] => self.autocomplete = "",
                     [head, ..] => self.autocomplete = head,
                 }
@@ -201,8 +206,11 @@ impl SplitApp {
                 self.screen = Screen::List;
             }
             KeyCode::Tab => {
-                if let Some(word) = English::next_starting_with(&self.input, self.autocomplete) {
-                    self.autocomplete = word;
+                let words = self.words_starting_with(&self.input, edit);
+
+                if let Some(position) = words.iter().position(|word| *word == self.autocomplete) {
+                    // if the last word cycle back to the first word in the list
+                    self.autocomplete = words[(position + 1) % words.len()];
                 }
             }
             KeyCode::Enter => {
@@ -214,12 +222,46 @@ impl SplitApp {
         }
     }
 
+    /// The words starting with `start` that can be entered in the current place, only the words
+    /// that give the mnemonic a valid checksum can be entered as the last word
+    fn words_starting_with(&self, start: &str, edit: Option<usize>) -> Vec<&'static str> {
+        let words = English::starting_with(start);
+        let last_index = self.word_count - 1;
+
+        let is_last_word = match edit {
+            Some(index) => index == last_index,
+            None => self.mnemonic.len() >= last_index,
+        };
+
+        if !is_last_word {
+            return words;
+        }
+
+        let first_words = self
+            .mnemonic
+            .items
+            .iter()
+            .take(last_index)
+            .map(String::as_str)
+            .collect::<Vec<&str>>();
+
+        match splitmonic::validation::valid_last_words(&first_words) {
+            Some(last_words) => words
+                .into_iter()
+                .filter(|word| last_words.contains(word))
+                .collect(),
+            None => words,
+        }
+    }
+
     fn update_input_in_normal(&mut self, key_event: KeyEvent) {
         match key_event.code {
             KeyCode::Char('q') => {
                 self.should_quit = true;
             }
-            KeyCode::Char('i') => self.screen = Screen::WordInput(InputMode::Inserting),
+            KeyCode::Char('i') if !self.generated => {
+                self.screen = Screen::WordInput(InputMode::Inserting)
+            }
             KeyCode::Esc => self.screen = Screen::WordInput(InputMode::Normal),
             KeyCode::Down | KeyCode::Tab => {
                 self.mnemonic.select();
@@ -233,15 +275,25 @@ impl SplitApp {
     }
 
     fn update_in_list(&mut self, key_event: KeyEvent) {
+        // a generated mnemonic can't be changed
+        if self.generated
+            && (matches!(key_event.code, KeyCode::Char('i' | 'e' | 'd'))
+                || key_event.modifiers.contains(KeyModifiers::ALT))
+        {
+            return;
+        }
+
         match key_event.code {
             KeyCode::Char('i') => {
                 self.phrases = empty_phrases();
+                self.fingerprint = None;
                 self.mnemonic.unselect();
                 self.screen = Screen::WordInput(InputMode::Inserting)
             }
             KeyCode::Char('e') => {
                 let current = self.mnemonic.selected();
                 self.phrases = empty_phrases();
+                self.fingerprint = None;
                 self.mnemonic.unselect();
                 self.screen = Screen::WordInput(InputMode::Editing(current))
             }
@@ -255,20 +307,7 @@ impl SplitApp {
 
             KeyCode::Right => self.screen = Screen::PhraseList(0),
 
-            KeyCode::Enter if self.mnemonic.len() == 24 => {
-                let mnemonic_code = self.mnemonic.items.join(" ");
-                match splitmonic::get_split_phrases(mnemonic_code) {
-                    Ok(phrases) => self
-                        .tx
-                        .send(Event::Effect(Effect::phrases(phrases)))
-                        .expect("should always send"),
-
-                    Err(error) => self
-                        .tx
-                        .send(Event::Effect(Effect::error(error)))
-                        .expect("should always send"),
-                }
-            }
+            KeyCode::Enter if self.mnemonic.len() == self.word_count => self.split_mnemonic(),
 
             KeyCode::Up => {
                 if self.mnemonic.items.is_empty() {
@@ -345,11 +384,33 @@ impl SplitApp {
                 };
             }
 
+            KeyCode::Char('r') => self.show_qr = !self.show_qr,
+
             KeyCode::Tab => self.screen = Screen::SaveLocationInput,
             _ => {}
         }
     }
 
+    fn split_mnemonic(&self) {
+        let mnemonic_code = self.mnemonic.items.join(" ");
+        let split_phrases =
+            splitmonic::fingerprint::get_fingerprint(&mnemonic_code).and_then(|fingerprint| {
+                splitmonic::get_split_phrases(mnemonic_code).map(|phrases| (phrases, fingerprint))
+            });
+
+        match split_phrases {
+            Ok((phrases, fingerprint)) => self
+                .tx
+                .send(Event::Effect(Effect::phrases(phrases, fingerprint)))
+                .expect("should always send"),
+
+            Err(error) => self
+                .tx
+                .send(Event::Effect(Effect::error(error)))
+                .expect("should always send"),
+        }
+    }
+
     fn select_phrase_list(&mut self, current: Option<usize>, phrase_list_index: usize) {
         if let Some(current) = current {
             self.phrases[current].unselect()
@@ -366,50 +427,78 @@ impl SplitApp {
         }
 
         match (place, self.mnemonic.len()) {
-            (None, 24) => {
+            (None, len) if len == self.word_count => {
                 self.mnemonic.pop();
                 self.mnemonic.push(word);
                 self.screen = Screen::List
             }
             (None, len) => {
                 self.mnemonic.push(word);
-                if len == 23 {
+                if len + 1 == self.word_count {
                     self.screen = Screen::List
                 }
             }
             (Some(index), len) => {
                 self.mnemonic.items[index] = word;
-                if len == 24 {
+                if len == self.word_count {
                     self.screen = Screen::List
                 } else {
                     self.screen = Screen::WordInput(InputMode::Inserting)
                 }
             }
         }
+
+        if self.mnemonic.len() == self.word_count {
+            if let Err(error) = self.validate_mnemonic() {
+                self.tx
+                    .send(Event::Effect(Effect::error(error)))
+                    .expect("should always send")
+            }
+        }
+    }
+
+    fn validate_mnemonic(&self) -> Result<(), splitmonic::validation::Error> {
+        splitmonic::validation::validate_mnemonic_code(&self.mnemonic.items.join(" "))
+    }
+
+    pub fn is_valid_mnemonic(&self) -> bool {
+        self.mnemonic.len() == self.word_count && self.validate_mnemonic().is_ok()
     }
 
     fn save_phrases(&self) -> Result<(), eyre::Error> {
         let save_location = PathBuf::from(&self.save_location);
-        std::fs::create_dir_all(&save_location)?;
+        let fingerprint = self.fingerprint.as_deref().unwrap_or_default();
 
+        let mut files = vec![];
         for (index, is_selected) in &self.selected_phrases {
             if *is_selected {
-                let mut path = PathBuf::from(&self.save_location);
-                path.push(&format!("phrases_{}_of_5.txt", index + 1));
-
-                let mut file = File::create(path)?;
+                let path = save_location.join(output::file_name(
+                    output::DEFAULT_FILE_NAME_TEMPLATE,
+                    index + 1,
+                    self.phrases.len(),
+                    fingerprint,
+                ));
+
+                let split_phrase = self.phrases[*index].items.join(" ");
+                let text = output::split_phrase_file_text(&split_phrase)?;
+
+                // the QR codes go next to the text file, to move the split phrase by camera
+                let qr = qr::split_phrase_qr(&split_phrase)?;
+                files.push((path.with_extension("png"), qr::to_png(&qr)?));
+                files.push((path.with_extension("svg"), qr::to_svg(&qr).into_bytes()));
+                files.push((path, text.into_bytes()));
+            }
+        }
 
-                let text = self.phrases[*index]
-                    .items
-                    .iter()
-                    .enumerate()
-                    .map(|(index, word)| format!("{}: {}", (index + 1), word))
-                    .collect::<Vec<String>>()
-                    .join("\n");
+        if let Some((existing, _)) = files.iter().find(|(path, _)| path.exists()) {
+            return Err(eyre::eyre!(
+                "Refusing to overwrite existing file: {}",
+                existing.display()
+            ));
+        }
 
-                file.write_all(text.as_bytes())?;
-                file.flush()?;
-            }
+        for (path, contents) in &files {
+            output::write_new_file(path, contents)?;
         }
 
         Ok(())
@@ -441,3 +530,84 @@ fn empty_phrases() -> [StatefulList<String>; 5] {
         StatefulList::with_capacity(28),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp_dir::TempDir;
+    use crossbeam_channel::unbounded;
+
+    #[test]
+    fn only_offers_valid_last_words() {
+        let (tx, rx) = unbounded();
+        let mut app = SplitApp::new(tx, rx);
+
+        app.mnemonic = StatefulList::with_items(vec!["abandon".to_string(); 22]);
+        assert_eq!(app.words_starting_with("ar", None).len(), 18);
+
+        app.mnemonic.push("abandon".to_string());
+        assert_eq!(app.words_starting_with("ar", None), vec!["art"]);
+        assert_eq!(app.words_starting_with("", None).len(), 8);
+
+        // editing any other word offers every word
+        app.mnemonic.push("art".to_string());
+        assert_eq!(app.words_starting_with("ar", Some(3)).len(), 18);
+        assert_eq!(app.words_starting_with("ar", Some(23)), vec!["art"]);
+    }
+
+    #[test]
+    fn uses_the_word_count_of_a_generated_mnemonic() {
+        let (tx, rx) = unbounded();
+        let mnemonic_code = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let app = SplitApp::with_generated_mnemonic(tx, rx, mnemonic_code.to_string());
+
+        assert_eq!(app.word_count, 12);
+        assert!(app.is_valid_mnemonic());
+        assert_eq!(app.words_starting_with("ab", Some(11)), vec!["about"]);
+
+        match app.rx.try_recv() {
+            Ok(Event::Effect(Effect::ReceivedPhrases(phrases, fingerprint))) => {
+                assert_eq!(phrases.len(), 5);
+                assert_eq!(fingerprint, "73c5da0a");
+            }
+            _ => panic!("the generated mnemonic should be split"),
+        }
+    }
+
+    #[test]
+    fn saves_only_new_files_readable_by_the_owner() {
+        let (tx, rx) = unbounded();
+        let mut app = SplitApp::new(tx, rx);
+
+        let dir = TempDir::new("tui");
+        app.save_location = dir.to_string_lossy().to_string();
+
+        let split_phrases = splitmonic::get_split_phrases("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art".to_string()).unwrap();
+        for (index, split_phrase) in split_phrases.iter().enumerate() {
+            app.phrases[index] = StatefulList::with_items(
+                split_phrase.split(' ').map(ToString::to_string).collect(),
+            );
+        }
+        app.selected_phrases.insert(1, true);
+
+        app.save_phrases().unwrap();
+        let path = dir.join("phrases_2_of_5.txt");
+        assert_eq!(
+            splitmonic::share_file::read_share_file(&path)
+                .unwrap()
+                .join(" "),
+            split_phrases[1]
+        );
+        assert!(path.with_extension("png").exists());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // saving again never overwrites the files
+        assert!(app.save_phrases().is_err());
+    }
+}
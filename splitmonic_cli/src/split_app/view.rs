@@ -1,11 +1,14 @@
 use crate::{
-    split_app::{InputMode, Screen, SplitApp},
+    qr,
+    split_app::{Screen, SplitApp},
+    ui::message::messages_area,
     ui::util::stateful_list::StatefulList,
+    ui::InputMode,
     Backend,
 };
 
 use tui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Span, Spans, Text},
     widgets::{Block, Borders, List, ListItem, Paragraph},
@@ -16,7 +19,7 @@ use unicode_width::UnicodeWidthStr;
 pub fn draw(app: &mut SplitApp, frame: &mut Frame<Backend>) {
     let help_box_size = match &app.screen {
         Screen::List => 4,
-        Screen::PhraseList(_) => 4,
+        Screen::PhraseList(_) => 5,
         _ => 1,
     };
 
@@ -80,10 +83,10 @@ pub fn draw(app: &mut SplitApp, frame: &mut Frame<Backend>) {
 
     frame.render_widget(save_area(app), chunks[3]);
 
-    frame.render_widget(messages_area(app), chunks[4])
+    frame.render_widget(messages_area(&app.message), chunks[4])
 }
 
-fn help_message_block(app: &SplitApp) -> Paragraph {
+fn help_message_block(app: &SplitApp) -> Paragraph<'_> {
     let (mut text, style) = match app.screen {
         Screen::WordInput(InputMode::Normal) => (
             Text::from(Spans::from(vec![
@@ -142,7 +145,7 @@ fn help_message_block(app: &SplitApp) -> Paragraph {
                     Span::raw("to edit word "),
                 ])));
 
-                if app.mnemonic.len() == 24 {
+                if app.mnemonic.len() == app.word_count {
                     texts.extend(Text::from(Spans::from(vec![
                         Span::styled(
                             "      <ENTER> ",
@@ -180,6 +183,11 @@ fn help_message_block(app: &SplitApp) -> Paragraph {
                     Span::raw("to select/deselect all"),
                 ])));
 
+                texts.extend(Text::from(Spans::from(vec![
+                    Span::styled("        r ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::raw("to show/hide the QR code of the selected phrase"),
+                ])));
+
                 texts.extend(Text::from(Spans::from(vec![
                     Span::styled(
                         "        <TAB> ",
@@ -209,13 +217,13 @@ fn help_message_block(app: &SplitApp) -> Paragraph {
     Paragraph::new(text)
 }
 
-fn input_block(app: &SplitApp) -> Paragraph {
+fn input_block(app: &SplitApp) -> Paragraph<'_> {
     let input_text = match app.screen {
         Screen::WordInput(InputMode::Inserting | InputMode::Editing(_)) => {
             let autocomplete = if app.autocomplete.len() >= app.input.len() {
                 &app.autocomplete[app.input.len()..]
             } else {
-                &app.autocomplete
+                app.autocomplete
             };
 
             vec![Spans::from(vec![
@@ -226,40 +234,63 @@ fn input_block(app: &SplitApp) -> Paragraph {
         _ => vec![Spans::from(Span::raw(""))],
     };
 
+    let last_index = app.word_count - 1;
+    let title = match app.screen {
+        Screen::WordInput(InputMode::Editing(Some(index))) if index == last_index => {
+            "Input (last word, valid checksums only)"
+        }
+        Screen::WordInput(InputMode::Inserting) if app.mnemonic.len() >= last_index => {
+            "Input (last word, valid checksums only)"
+        }
+        _ => "Input",
+    };
+
     Paragraph::new(input_text)
         .style(match app.screen {
             Screen::WordInput(InputMode::Inserting) => Style::default().fg(Color::Yellow),
             Screen::WordInput(InputMode::Editing(_)) => Style::default().fg(Color::Yellow),
             _ => Style::default(),
         })
-        .block(Block::default().borders(Borders::ALL).title("Input"))
+        .block(Block::default().borders(Borders::ALL).title(title))
 }
 
-fn mnemonic_block<'a, 'b>(app: &'a SplitApp) -> List<'b> {
+fn mnemonic_block<'a>(app: &SplitApp) -> List<'a> {
     let messages: Vec<ListItem> = app
         .mnemonic
         .items
         .iter()
         .enumerate()
         .map(|(i, m)| {
-            let content = vec![Spans::from(Span::raw(format!("{}: {}", i + 1, m)))];
+            // never show a generated mnemonic
+            let word = if app.generated { "********" } else { m };
+            let content = vec![Spans::from(Span::raw(format!("{}: {}", i + 1, word)))];
             ListItem::new(content)
         })
         .collect();
 
     let block_border_style = match (&app.screen, app.mnemonic.len()) {
-        (_, 24) => Style::default().fg(Color::Green),
+        _ if app.generated => Style::default().fg(Color::Green),
+        (_, len) if len == app.word_count && app.is_valid_mnemonic() => {
+            Style::default().fg(Color::Green)
+        }
+        (_, len) if len == app.word_count => Style::default().fg(Color::Red),
         (Screen::List, _) => Style::default().fg(Color::Yellow),
         _ => Style::default(),
     };
 
+    let title = match app.mnemonic.len() {
+        _ if app.generated => "Mnemonic (generated)",
+        len if len == app.word_count && !app.is_valid_mnemonic() => "Mnemonic (invalid checksum)",
+        _ => "Mnemonic",
+    };
+
     // Create a List from all list items and highlight the currently selected one
     List::new(messages)
         .style(Style::default())
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("Mnemonic")
+                .title(title)
                 .border_style(block_border_style),
         )
         .highlight_style(
@@ -333,13 +364,28 @@ fn render_phrases_blocks(app: &mut SplitApp, frame: &mut Frame<Backend>, chunks:
         )
         .split(chunks[1]);
 
+    let title = match &app.fingerprint {
+        Some(fingerprint) => format!("Phrases (mnemonic fingerprint: {})", fingerprint),
+        None => "Phrases".to_string(),
+    };
+
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::DarkGray))
-        .title(Span::styled("Phrases", Style::default()));
+        .title(Span::styled(title, Style::default()));
 
     frame.render_widget(block, chunks[1]);
 
+    if let (true, Screen::PhraseList(index)) = (app.show_qr, &app.screen) {
+        let area = chunks[1].inner(&Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+
+        frame.render_widget(qr_block(&app.phrases[*index], *index), area);
+        return;
+    }
+
     for (index, phrases) in app.phrases.iter_mut().enumerate() {
         let mblock = phrase_block(
             *app.selected_phrases.get(&index).unwrap_or(&false),
@@ -351,7 +397,28 @@ fn render_phrases_blocks(app: &mut SplitApp, frame: &mut Frame<Backend>, chunks:
     }
 }
 
-fn save_area(app: &SplitApp) -> Paragraph {
+fn qr_block<'a>(phrases: &StatefulList<String>, index: usize) -> Paragraph<'a> {
+    let title = format!("{} of 5 (QR code)", index + 1);
+
+    let text = match qr::split_phrase_qr(&phrases.items.join(" ")) {
+        Ok(qr) => Text::from(
+            qr::to_half_blocks(&qr)
+                .into_iter()
+                .map(Spans::from)
+                .collect::<Vec<Spans>>(),
+        ),
+        Err(error) => Text::from(error.to_string()),
+    };
+
+    Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::Green)),
+    )
+}
+
+fn save_area(app: &SplitApp) -> Paragraph<'_> {
     let style = match app.screen {
         Screen::SaveLocationInput => Style::default().fg(Color::Yellow),
         _ => Style::default().fg(Color::DarkGray),
@@ -368,44 +435,3 @@ fn save_area(app: &SplitApp) -> Paragraph {
                 .border_style(style),
         )
 }
-
-fn messages_area(app: &SplitApp) -> Paragraph {
-    use crate::split_app::Message;
-
-    let dark_gray = Style::default().fg(Color::DarkGray);
-    let gray = Style::default().fg(Color::Gray);
-    let light_red = Style::default().fg(Color::LightRed);
-    let red = Style::default().fg(Color::Red);
-    let green = Style::default().fg(Color::Green);
-    let light_green = Style::default().fg(Color::LightGreen);
-
-    match &app.message {
-        Message::None => Paragraph::new("").style(dark_gray).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Messages")
-                .border_style(dark_gray),
-        ),
-
-        Message::Error(error) => Paragraph::new(error.to_string()).style(red).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Error")
-                .border_style(light_red.add_modifier(Modifier::BOLD)),
-        ),
-
-        Message::Success(string) => Paragraph::new(string.as_str()).style(green).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Success")
-                .border_style(light_green.add_modifier(Modifier::BOLD)),
-        ),
-
-        Message::Debug(string) => Paragraph::new(string.as_str()).style(gray).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Debug")
-                .border_style(dark_gray),
-        ),
-    }
-}
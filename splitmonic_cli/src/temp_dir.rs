@@ -0,0 +1,37 @@
+//! A temporary directory for tests, removed when the test ends even if it panics
+
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// A path for a new directory in the system's temp directory, unique to this test run. The
+    /// directory isn't created, anything left from a previous run is removed
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("splitmonic_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        Self(dir)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
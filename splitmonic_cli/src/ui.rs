@@ -1 +1,8 @@
+pub mod message;
 pub mod util;
+
+pub enum InputMode {
+    Normal,
+    Inserting,
+    Editing(Option<usize>),
+}
@@ -0,0 +1,74 @@
+use tui::{
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Paragraph},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Lib(#[from] splitmonic::Error),
+
+    #[error(transparent)]
+    Validation(#[from] splitmonic::validation::Error),
+
+    #[error(transparent)]
+    Other(#[from] eyre::Report),
+}
+
+#[derive(Debug)]
+pub enum Message {
+    None,
+    #[allow(dead_code)]
+    Debug(String),
+    Error(Error),
+    Success(String),
+}
+
+impl Message {
+    pub fn success(message: String) -> Self {
+        Self::Success(message)
+    }
+
+    pub fn error(error: Error) -> Self {
+        Self::Error(error)
+    }
+}
+
+pub fn messages_area(message: &Message) -> Paragraph<'_> {
+    let dark_gray = Style::default().fg(Color::DarkGray);
+    let gray = Style::default().fg(Color::Gray);
+    let light_red = Style::default().fg(Color::LightRed);
+    let red = Style::default().fg(Color::Red);
+    let green = Style::default().fg(Color::Green);
+    let light_green = Style::default().fg(Color::LightGreen);
+
+    match message {
+        Message::None => Paragraph::new("").style(dark_gray).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Messages")
+                .border_style(dark_gray),
+        ),
+
+        Message::Error(error) => Paragraph::new(error.to_string()).style(red).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Error")
+                .border_style(light_red.add_modifier(Modifier::BOLD)),
+        ),
+
+        Message::Success(string) => Paragraph::new(string.as_str()).style(green).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Success")
+                .border_style(light_green.add_modifier(Modifier::BOLD)),
+        ),
+
+        Message::Debug(string) => Paragraph::new(string.as_str()).style(gray).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Debug")
+                .border_style(dark_gray),
+        ),
+    }
+}